@@ -27,8 +27,18 @@ use crate::{
     DxfResult,
     ExtensionGroup,
     Handle,
+    HatchBoundaryPath,
+    HatchGradientColor,
+    HatchPatternDefinitionLine,
     LwPolylineVertex,
+    MeshEdge,
+    MeshSubentityOverride,
     Point,
+    SweepOptions,
+    TableCell,
+    TransformationMatrix,
+    Transparency,
+    UnknownEntity,
     Vector,
     XData,
 };
@@ -156,7 +166,7 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
     }
 
     ////////////////////////////////////////////////////// apply_individual_pair
-    fun.push_str("    pub(crate) fn apply_individual_pair(&mut self, pair: &CodePair, iter: &mut CodePairPutBack) -> DxfResult<()> {\n");
+    fun.push_str("    pub(crate) fn apply_individual_pair(&mut self, pair: &CodePair, iter: &mut CodePairPutBack) -> DxfResult<bool> {\n");
     fun.push_str("        match pair.code {\n");
     for c in &entity.children {
         if c.name == "Field" {
@@ -179,6 +189,13 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
                     field = name(c),
                     read_fun = read_fun
                 ));
+                for (code, reader) in true_color_readers(c, &format!("self.{}", name(c))) {
+                    fun.push_str(&format!(
+                        "            {code} => {{ {reader} }},\n",
+                        code = code,
+                        reader = reader
+                    ));
+                }
             }
         } else if c.name == "Pointer" {
             fun.push_str(&format!(
@@ -193,9 +210,9 @@ fn generate_base_entity(fun: &mut String, element: &Element) {
     fun.push_str("                let x = XData::read_item(pair.assert_string()?, iter)?;\n");
     fun.push_str("                self.x_data.push(x);\n");
     fun.push_str("            },\n");
-    fun.push_str("            _ => (), // unknown code, just ignore\n");
+    fun.push_str("            _ => return Ok(false), // unknown code\n");
     fun.push_str("        }\n");
-    fun.push_str("        Ok(())\n");
+    fun.push_str("        Ok(true)\n");
     fun.push_str("    }\n");
 
     ///////////////////////////////////////////////////////////// add_code_pairs
@@ -226,6 +243,8 @@ fn generate_entity_types(fun: &mut String, element: &Element) {
         }
     }
 
+    // types not described in the spec are preserved verbatim
+    fun.push_str("    Unknown(UnknownEntity),\n");
     fun.push_str("}\n");
     fun.push('\n');
 
@@ -422,6 +441,8 @@ fn generate_is_supported_on_version(fun: &mut String, element: &Element) {
             ));
         }
     }
+    // unknown types are written back exactly as they were read, whatever the version
+    fun.push_str("            EntityType::Unknown(_) => { true },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -461,6 +482,7 @@ fn generate_type_string(fun: &mut String, element: &Element) {
             ));
         }
     }
+    fun.push_str("            EntityType::Unknown(ref ent) => { &ent.type_string },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -527,6 +549,16 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
                                     ));
                                 }
                             }
+                            for (code, reader) in true_color_readers(f, &format!("ent.{}", name(f)))
+                            {
+                                if seen_codes.insert(code) {
+                                    fun.push_str(&format!(
+                                        "                    {code} => {{ {reader}; }},\n",
+                                        code = code,
+                                        reader = reader
+                                    ));
+                                }
+                            }
                         } else if f.name == "Pointer" {
                             if allow_multiples(f) {
                                 fun.push_str(&format!("                    {code} => {{ ent.__{field}_handle.push(pair.as_handle()?); }},\n", code=code(f), field=name(f)));
@@ -547,6 +579,7 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
         }
     }
 
+    fun.push_str("            EntityType::Unknown(ref mut unknown) => { unknown.code_pairs.push(pair.clone()); },\n");
     fun.push_str("        }\n");
     fun.push_str("        Ok(true)\n");
     fun.push_str("    }\n");
@@ -578,6 +611,7 @@ fn generate_get_code_pairs(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            EntityType::Unknown(ref unknown) => { pairs.extend(unknown.code_pairs.iter().cloned()); },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -623,6 +657,11 @@ fn generate_write_code_pairs(entity: &Element) -> Vec<String> {
                     for line in write_lines_for_field(field, vec![]) {
                         commands.push(line);
                     }
+                    for line in
+                        true_color_writers(field, &format!("ent.{}", name(field)), "version")
+                    {
+                        commands.push(line);
+                    }
                 }
                 "Pointer" => {
                     panic!("not used");
@@ -711,6 +750,13 @@ fn generate_write_code_pairs_for_write_order(
             }
             commands.push(String::from("}"));
         }
+        "WriteTrueColor" => {
+            let field_name = write_command.attributes.get("Field").unwrap();
+            let field = field_with_name(entity, field_name);
+            for line in true_color_writers(field, &format!("ent.{}", field_name), "version") {
+                commands.push(line);
+            }
+        }
         "WriteExtensionData" => {
             commands.push(String::from("if version >= AcadVersion::R14 {"));
             commands.push(String::from(
@@ -34,6 +34,7 @@ use crate::{
     SectionTypeSettings,
     TableCellStyle,
     TransformationMatrix,
+    UnknownObject,
     Vector,
     XData,
 };
@@ -234,6 +235,8 @@ fn generate_object_types(fun: &mut String, element: &Element) {
         }
     }
 
+    // types not described in the spec are preserved verbatim
+    fun.push_str("    Unknown(UnknownObject),\n");
     fun.push_str("}\n");
     fun.push('\n');
 
@@ -415,6 +418,8 @@ fn generate_is_supported_on_version(fun: &mut String, element: &Element) {
             ));
         }
     }
+    // unknown types are written back exactly as they were read, whatever the version
+    fun.push_str("            ObjectType::Unknown(_) => { true },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -450,6 +455,7 @@ fn generate_type_string(fun: &mut String, element: &Element) {
             ));
         }
     }
+    fun.push_str("            ObjectType::Unknown(ref obj) => { &obj.type_string },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 }
@@ -522,6 +528,16 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
                                     ));
                                 }
                             }
+                            for (code, reader) in true_color_readers(f, &format!("obj.{}", name(f)))
+                            {
+                                if seen_codes.insert(code) {
+                                    fun.push_str(&format!(
+                                        "                    {code} => {{ {reader}; }},\n",
+                                        code = code,
+                                        reader = reader
+                                    ));
+                                }
+                            }
                         } else if f.name == "Pointer" {
                             if allow_multiples(f) {
                                 fun.push_str(&format!("                    {code} => {{ obj.__{field}_handle.push(pair.as_handle()?); }},\n", code=code(f), field=name(f)));
@@ -549,6 +565,7 @@ fn generate_try_apply_code_pair(fun: &mut String, element: &Element) {
         }
     }
 
+    fun.push_str("            ObjectType::Unknown(ref mut unknown) => { unknown.code_pairs.push(pair.clone()); },\n");
     fun.push_str("        }\n");
     fun.push_str("        Ok(true)\n");
     fun.push_str("    }\n");
@@ -594,6 +611,7 @@ fn generate_write(fun: &mut String, element: &Element) {
             }
         }
     }
+    fun.push_str("            ObjectType::Unknown(ref unknown) => { pairs.extend(unknown.code_pairs.iter().cloned()); },\n");
     fun.push_str("        }\n");
     fun.push_str("    }\n");
 
@@ -641,6 +659,11 @@ fn generate_write_code_pairs(object: &Element) -> Vec<String> {
                     for line in write_lines_for_field(field, vec![]) {
                         commands.push(line);
                     }
+                    for line in
+                        true_color_writers(field, &format!("obj.{}", name(field)), "version")
+                    {
+                        commands.push(line);
+                    }
                 }
                 _ => panic!("unexpected item {} in object", field.name),
             }
@@ -723,6 +746,13 @@ fn generate_write_code_pairs_for_write_order(
             }
             commands.push(String::from("}"));
         }
+        "WriteTrueColor" => {
+            let field_name = write_command.attributes.get("Field").unwrap();
+            let field = field_with_name(object, field_name);
+            for line in true_color_writers(field, &format!("obj.{}", field_name), "version") {
+                commands.push(line);
+            }
+        }
         "WriteExtensionData" => {
             commands.push(String::from("if version >= AcadVersion::R14 {"));
             commands.push(String::from(
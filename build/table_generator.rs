@@ -138,6 +138,43 @@ fn generate_table_items(fun: &mut String, element: &Element) {
         fun.push_str("    pub fn set_owner<'a>(&mut self, item: &'a mut DrawingItemMut, drawing: &'a mut Drawing) {\n");
         fun.push_str("        self.__owner_handle = drawing.assign_and_get_handle(item);\n");
         fun.push_str("    }\n");
+        for field in &table_item.children {
+            for flag in &field.children {
+                if flag.name == "Flag" {
+                    let flag_name = name(flag);
+                    let mask = attr(flag, "Mask");
+                    fun.push_str(&format!(
+                        "    pub fn {name}(&self) -> bool {{\n",
+                        name = flag_name
+                    ));
+                    fun.push_str(&format!(
+                        "        self.{field} & {mask} != 0\n",
+                        field = name(field),
+                        mask = mask
+                    ));
+                    fun.push_str("    }\n");
+                    fun.push_str(&format!(
+                        "    pub fn set_{name}(&mut self, val: bool) {{\n",
+                        name = flag_name
+                    ));
+                    fun.push_str("        if val {\n");
+                    fun.push_str(&format!(
+                        "            self.{field} |= {mask};\n",
+                        field = name(field),
+                        mask = mask
+                    ));
+                    fun.push_str("        }\n");
+                    fun.push_str("        else {\n");
+                    fun.push_str(&format!(
+                        "            self.{field} &= !{mask};\n",
+                        field = name(field),
+                        mask = mask
+                    ));
+                    fun.push_str("        }\n");
+                    fun.push_str("    }\n");
+                }
+            }
+        }
         fun.push_str("}\n");
         fun.push('\n');
     }
@@ -283,6 +320,13 @@ fn generate_table_reader(fun: &mut String, element: &Element) {
                         ));
                     }
                 }
+                for (code, reader) in true_color_readers(field, &format!("item.{}", name(field))) {
+                    fun.push_str(&format!(
+                        "                                    {code} => {{ {reader}; }},\n",
+                        code = code,
+                        reader = reader
+                    ));
+                }
             }
         }
 
@@ -384,11 +428,11 @@ fn generate_table_writer(fun: &mut String, element: &Element) {
         // TODO: assign and write table handles
         fun.push_str("    if write_handles {\n");
         fun.push_str(&format!(
-            "        pairs.push(CodePair::new_str(5, \"{handle}\"));\n",
-            handle = format!("{:X}", table_i),
+            "        pairs.push(CodePair::new_str(5, \"{handle:X}\"));\n",
+            handle = table_i,
         ));
         fun.push_str("    }\n");
-        fun.push_str("\n");
+        fun.push('\n');
 
         let item_type = name(table_item);
 
@@ -430,8 +474,8 @@ fn generate_table_writer(fun: &mut String, element: &Element) {
         // Add parent group code 330 if version is above AcadVersion::R2018 use the table_i handle
         fun.push_str("            if drawing.header.version >= AcadVersion::R2018 {\n");
         fun.push_str(&format!(
-            "                pairs.push(CodePair::new_string(330, \"{parent}\"));\n",
-            parent = format!("{:X}", table_i),
+            "                pairs.push(CodePair::new_string(330, \"{parent:X}\"));\n",
+            parent = table_i,
         ));
         fun.push_str("            }");
 
@@ -449,7 +493,15 @@ fn generate_table_writer(fun: &mut String, element: &Element) {
             class_name = attr(table_item, "ClassName")
         ));
         fun.push_str("        pairs.push(CodePair::new_string(2, &item.name));\n");
-        fun.push_str("        pairs.push(CodePair::new_i16(70, 0));\n"); // TODO: flags
+        if table_item
+            .children
+            .iter()
+            .any(|f| name(f) == "flags" && code(f) == 70)
+        {
+            fun.push_str("        pairs.push(CodePair::new_i16(70, item.flags as i16));\n");
+        } else {
+            fun.push_str("        pairs.push(CodePair::new_i16(70, 0));\n"); // TODO: flags
+        }
 
         // Check if there is a custom table writer
         if !attr(table_item, "CustomWriter").is_empty() {
@@ -583,6 +635,14 @@ fn generate_table_field_writer(field: &Element, fun: &mut String) {
         if !predicates.is_empty() {
             fun.push_str("        }\n");
         }
+
+        for line in true_color_writers(
+            field,
+            &format!("item.{}", name(field)),
+            "drawing.header.version",
+        ) {
+            fun.push_str(&format!("        {}\n", line));
+        }
     }
 }
 
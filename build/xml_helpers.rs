@@ -70,6 +70,85 @@ pub fn field_reader(element: &Element) -> String {
     }
 }
 
+/// Returns the codes of the 24-bit color and the color name that are stored alongside a `Color` field.
+fn true_color_codes(element: &Element) -> Option<(i32, i32)> {
+    let codes = attr(element, "TrueColorCodes");
+    if codes.is_empty() {
+        None
+    } else {
+        let codes = codes
+            .split(',')
+            .map(|c| c.parse::<i32>().unwrap())
+            .collect::<Vec<_>>();
+        Some((codes[0], codes[1]))
+    }
+}
+
+/// Returns the code and read statement of each value stored alongside a `Color` field.
+pub fn true_color_readers(element: &Element, field_access: &str) -> Vec<(i32, String)> {
+    match true_color_codes(element) {
+        Some((rgb_code, name_code)) => vec![
+            (
+                rgb_code,
+                format!(
+                    "{}.set_true_color_raw_value(pair.assert_i32()?)",
+                    field_access
+                ),
+            ),
+            (
+                name_code,
+                format!(
+                    "{}.set_color_name_raw_value(pair.assert_string()?)",
+                    field_access
+                ),
+            ),
+        ],
+        None => vec![],
+    }
+}
+
+/// Returns the statements that write the values stored alongside a `Color` field.  These aren't written before R2004;
+/// the field's color index holds the closest approximation instead.  With `WriteEmptyColorName="true"` the color name
+/// is always written, empty if the color isn't from a color book.
+pub fn true_color_writers(element: &Element, field_access: &str, version: &str) -> Vec<String> {
+    match true_color_codes(element) {
+        Some((rgb_code, name_code)) => {
+            let min_version = match min_version(element).as_str() {
+                "" => String::from("R2004"),
+                v => String::from(v),
+            };
+            let mut lines = vec![
+                format!("if {} >= AcadVersion::{} {{", version, min_version),
+                format!(
+                    "    if let Some(v) = {}.true_color_raw_value() {{",
+                    field_access
+                ),
+                format!("        pairs.push(CodePair::new_i32({}, v));", rgb_code),
+                String::from("    }"),
+            ];
+            if attr(element, "WriteEmptyColorName") == "true" {
+                lines.push(format!(
+                    "    pairs.push(CodePair::new_str({}, {}.color_name_raw_value().unwrap_or(\"\")));",
+                    name_code, field_access
+                ));
+            } else {
+                lines.push(format!(
+                    "    if let Some(v) = {}.color_name_raw_value() {{",
+                    field_access
+                ));
+                lines.push(format!(
+                    "        pairs.push(CodePair::new_str({}, v));",
+                    name_code
+                ));
+                lines.push(String::from("    }"));
+            }
+            lines.push(String::from("}"));
+            lines
+        }
+        None => vec![],
+    }
+}
+
 pub fn methods_for_pointer_access(pointer: &Element) -> String {
     let mut fun = String::new();
     let typ = attr(pointer, "Type");
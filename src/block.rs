@@ -11,6 +11,9 @@ use crate::extension_data;
 use crate::helper_functions::*;
 use crate::x_data;
 
+/// Guards against blocks that (directly or indirectly) insert themselves.
+pub(crate) const MAX_BLOCK_NESTING: usize = 32;
+
 /// A block is a collection of entities.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -121,7 +124,30 @@ impl Default for Block {
 
 // internal visibility only
 impl Block {
+    /// Finds the block named `name` that is drawn through `depth` levels of `Insert`s.  Returns `None` if there is no
+    /// such block or it is nested more than `MAX_BLOCK_NESTING` levels deep.
+    pub(crate) fn find_nested<'a>(
+        drawing: &'a Drawing,
+        name: &str,
+        depth: usize,
+    ) -> Option<&'a Block> {
+        if depth > MAX_BLOCK_NESTING {
+            return None;
+        }
+
+        drawing.blocks().find(|b| b.name.eq_ignore_ascii_case(name))
+    }
     pub(crate) fn read_block(drawing: &mut Drawing, iter: &mut CodePairPutBack) -> DxfResult<()> {
+        let block = Block::read(iter)?;
+        if block.handle.is_empty() {
+            drawing.add_block(block);
+        } else {
+            drawing.add_block_no_handle_set(block);
+        }
+
+        Ok(())
+    }
+    pub(crate) fn read(iter: &mut CodePairPutBack) -> DxfResult<Block> {
         // match code pair:
         //   0/ENDBLK -> swallow code pairs and return
         //   0/* -> read entity and add to collection
@@ -150,17 +176,12 @@ impl Block {
                                 }
                             }
 
-                            if current.handle.is_empty() {
-                                drawing.add_block(current);
-                            } else {
-                                drawing.add_block_no_handle_set(current);
-                            }
-                            break;
+                            return Ok(current);
                         }
                         CodePair { code: 0, .. } => {
                             // should be an entity
                             iter.put_back(Ok(pair));
-                            let mut iter = EntityIter { iter };
+                            let mut iter = EntityIter::new(iter);
                             iter.read_entities_into_vec(&mut current.entities)?;
                         }
                         _ => {
@@ -199,8 +220,6 @@ impl Block {
                 None => return Err(DxfError::UnexpectedEndOfInput),
             }
         }
-
-        Ok(())
     }
     pub(crate) fn add_code_pairs(
         &self,
@@ -437,8 +456,14 @@ mod tests {
             CodePair::new_str(0, "UNSUPPORTED_ENTITY"),
             CodePair::new_str(0, "LINE"),
         ]);
-        assert_eq!(1, block.entities.len());
+        assert_eq!(2, block.entities.len());
         match block.entities[0].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string)
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+        match block.entities[1].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
@@ -450,11 +475,17 @@ mod tests {
             CodePair::new_str(0, "LINE"),
             CodePair::new_str(0, "UNSUPPORTED_ENTITY"),
         ]);
-        assert_eq!(1, block.entities.len());
+        assert_eq!(2, block.entities.len());
         match block.entities[0].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
+        match block.entities[1].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string)
+            }
+            _ => panic!("expected an unknown entity"),
+        }
     }
 
     #[test]
@@ -464,12 +495,18 @@ mod tests {
             CodePair::new_str(0, "UNSUPPORTED_ENTITY"),
             CodePair::new_str(0, "CIRCLE"),
         ]);
-        assert_eq!(2, block.entities.len());
+        assert_eq!(3, block.entities.len());
         match block.entities[0].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
         match block.entities[1].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string)
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+        match block.entities[2].specific {
             EntityType::Circle(_) => (),
             _ => panic!("expected a circle"),
         }
@@ -0,0 +1,310 @@
+use crate::{LwPolylineSegment, ObjectCoordinateSystem, Point, Vector};
+
+/// Represents an axis-aligned box in world coordinates, e.g., the extents of an entity or a drawing.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox {
+    /// The corner with the smallest X, Y, and Z values.
+    pub min: Point,
+    /// The corner with the largest X, Y, and Z values.
+    pub max: Point,
+}
+
+// public implementation
+impl BoundingBox {
+    /// Creates a new `BoundingBox` spanning the two specified corners, which may be given in any order.
+    pub fn new(p1: Point, p2: Point) -> Self {
+        let mut bbox = BoundingBox::from_point(&p1);
+        bbox.include_point(&p2);
+        bbox
+    }
+    /// Creates a new `BoundingBox` that only contains the specified point.
+    pub fn from_point(p: &Point) -> Self {
+        BoundingBox {
+            min: p.clone(),
+            max: p.clone(),
+        }
+    }
+    /// Creates the smallest `BoundingBox` containing all of the specified points, or `None` if there are none.
+    pub fn from_points<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Point>,
+    {
+        let mut result: Option<BoundingBox> = None;
+        for p in points {
+            match result {
+                Some(ref mut bbox) => bbox.include_point(p),
+                None => result = Some(BoundingBox::from_point(p)),
+            }
+        }
+
+        result
+    }
+    /// Grows the box to contain the specified point.
+    pub fn include_point(&mut self, p: &Point) {
+        self.min.x = self.min.x.min(p.x);
+        self.min.y = self.min.y.min(p.y);
+        self.min.z = self.min.z.min(p.z);
+        self.max.x = self.max.x.max(p.x);
+        self.max.y = self.max.y.max(p.y);
+        self.max.z = self.max.z.max(p.z);
+    }
+    /// Grows the box to contain the other box.
+    pub fn include(&mut self, other: &BoundingBox) {
+        self.include_point(&other.min);
+        self.include_point(&other.max);
+    }
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        let mut result = self.clone();
+        result.include(other);
+        result
+    }
+    /// Returns the size of the box along each axis.
+    pub fn size(&self) -> Vector {
+        Vector::new(
+            self.max.x - self.min.x,
+            self.max.y - self.min.y,
+            self.max.z - self.min.z,
+        )
+    }
+    /// Returns the point in the middle of the box.
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+            (self.min.z + self.max.z) / 2.0,
+        )
+    }
+    /// Returns the eight corners of the box.
+    pub fn corners(&self) -> [Point; 8] {
+        let (min, max) = (&self.min, &self.max);
+        [
+            Point::new(min.x, min.y, min.z),
+            Point::new(max.x, min.y, min.z),
+            Point::new(min.x, max.y, min.z),
+            Point::new(max.x, max.y, min.z),
+            Point::new(min.x, min.y, max.z),
+            Point::new(max.x, min.y, max.z),
+            Point::new(min.x, max.y, max.z),
+            Point::new(max.x, max.y, max.z),
+        ]
+    }
+    /// Returns `true` if the point is inside or on the boundary of the box.
+    pub fn contains_point(&self, p: &Point) -> bool {
+        p.x >= self.min.x
+            && p.x <= self.max.x
+            && p.y >= self.min.y
+            && p.y <= self.max.y
+            && p.z >= self.min.z
+            && p.z <= self.max.z
+    }
+    /// Returns `true` if the boxes overlap or touch.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+}
+
+// internal visibility only
+impl BoundingBox {
+    /// Returns the box moved by the specified offset.
+    pub(crate) fn translated(&self, offset: &Vector) -> BoundingBox {
+        BoundingBox {
+            min: offset_point(&self.min, offset, 1.0),
+            max: offset_point(&self.max, offset, 1.0),
+        }
+    }
+}
+
+/// Returns `p + v * factor`.
+pub(crate) fn offset_point(p: &Point, v: &Vector, factor: f64) -> Point {
+    Point::new(p.x + v.x * factor, p.y + v.y * factor, p.z + v.z * factor)
+}
+
+/// Accumulates points and curves into a `BoundingBox`.
+#[derive(Default)]
+pub(crate) struct ExtentsBuilder {
+    bbox: Option<BoundingBox>,
+}
+
+impl ExtentsBuilder {
+    pub fn point(&mut self, p: &Point) {
+        match self.bbox {
+            Some(ref mut bbox) => bbox.include_point(p),
+            None => self.bbox = Some(BoundingBox::from_point(p)),
+        }
+    }
+    pub fn points<'a, I>(&mut self, points: I)
+    where
+        I: IntoIterator<Item = &'a Point>,
+    {
+        for p in points {
+            self.point(p);
+        }
+    }
+    pub fn bounding_box(&mut self, bbox: &BoundingBox) {
+        self.point(&bbox.min);
+        self.point(&bbox.max);
+    }
+    /// Adds the elliptical arc `center + u * cos(t) + v * sin(t)` where `t` runs counter-clockwise from `start` to
+    /// `end`, in radians.  The arc is a full ellipse if `start` and `end` are equivalent.  Along each axis the
+    /// extremes are found where the derivative of the curve is zero, so the result is exact.
+    pub fn elliptic_arc(&mut self, center: &Point, u: &Vector, v: &Vector, start: f64, end: f64) {
+        let full_turn = 2.0 * std::f64::consts::PI;
+        let mut sweep = (end - start).rem_euclid(full_turn);
+        if sweep == 0.0 {
+            sweep = full_turn;
+        }
+
+        let at = |t: f64| {
+            let p = offset_point(center, u, t.cos());
+            offset_point(&p, v, t.sin())
+        };
+        self.point(&at(start));
+        self.point(&at(start + sweep));
+        for (u_component, v_component) in [(u.x, v.x), (u.y, v.y), (u.z, v.z)] {
+            if u_component == 0.0 && v_component == 0.0 {
+                continue;
+            }
+
+            let extreme = v_component.atan2(u_component);
+            for t in [extreme, extreme + std::f64::consts::PI] {
+                if (t - start).rem_euclid(full_turn) <= sweep {
+                    self.point(&at(t));
+                }
+            }
+        }
+    }
+    /// Adds the 2D polyline segment from `p1` to `p2` in the object coordinate system `ocs`.  A non-zero `bulge` makes
+    /// the segment an arc.
+    pub fn ocs_bulge_segment(
+        &mut self,
+        ocs: &ObjectCoordinateSystem,
+        p1: (f64, f64),
+        p2: (f64, f64),
+        bulge: f64,
+        elevation: f64,
+    ) {
+        let (x1, y1) = p1;
+        let (x2, y2) = p2;
+        self.point(&ocs.to_wcs(&Point::new(x1, y1, elevation)));
+        self.point(&ocs.to_wcs(&Point::new(x2, y2, elevation)));
+        if let LwPolylineSegment::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            is_counter_clockwise,
+        } =
+            LwPolylineSegment::from_bulge(&Point::new(x1, y1, 0.0), &Point::new(x2, y2, 0.0), bulge)
+        {
+            let (start, end) = if is_counter_clockwise {
+                (start_angle, end_angle)
+            } else {
+                (end_angle, start_angle)
+            };
+            self.elliptic_arc(
+                &ocs.to_wcs(&Point::new(center.x, center.y, elevation)),
+                &ocs.x_axis().scaled(radius),
+                &ocs.y_axis().scaled(radius),
+                start.to_radians(),
+                end.to_radians(),
+            );
+        }
+    }
+    /// Adds the current extents again, moved by `offset`, as happens when an entity has a thickness.
+    pub fn extrude(&mut self, offset: &Vector) {
+        if let Some(bbox) = self.bbox.clone() {
+            self.bounding_box(&bbox.translated(offset));
+        }
+    }
+    pub fn build(self) -> Option<BoundingBox> {
+        self.bbox
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_box(expected_min: (f64, f64, f64), expected_max: (f64, f64, f64), bbox: BoundingBox) {
+        let close = |a: (f64, f64, f64), b: (f64, f64, f64)| {
+            (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9 && (a.2 - b.2).abs() < 1e-9
+        };
+        assert!(
+            close(expected_min, bbox.min.tuple()) && close(expected_max, bbox.max.tuple()),
+            "expected {:?} - {:?}, got {:?}",
+            expected_min,
+            expected_max,
+            bbox
+        );
+    }
+
+    #[test]
+    fn from_points() {
+        let points = vec![
+            Point::new(1.0, 5.0, 0.0),
+            Point::new(-2.0, 3.0, 1.0),
+            Point::new(0.0, 0.0, -1.0),
+        ];
+        let bbox = BoundingBox::from_points(&points).unwrap();
+        assert_box((-2.0, 0.0, -1.0), (1.0, 5.0, 1.0), bbox);
+        assert!(BoundingBox::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = BoundingBox::new(Point::new(0.0, 0.0, 0.0), Point::new(2.0, 2.0, 0.0));
+        let b = BoundingBox::new(Point::new(3.0, 3.0, 0.0), Point::new(1.0, 1.0, 0.0));
+        let c = BoundingBox::new(Point::new(5.0, 5.0, 0.0), Point::new(6.0, 6.0, 0.0));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
+        assert!(b.contains_point(&Point::new(2.0, 2.0, 0.0)));
+        assert_box((0.0, 0.0, 0.0), (6.0, 6.0, 0.0), a.union(&c));
+    }
+
+    #[test]
+    fn quarter_arc_extents() {
+        let mut builder = ExtentsBuilder::default();
+        builder.elliptic_arc(
+            &Point::origin(),
+            &Vector::x_axis(),
+            &Vector::y_axis(),
+            0.0,
+            std::f64::consts::FRAC_PI_2,
+        );
+        assert_box((0.0, 0.0, 0.0), (1.0, 1.0, 0.0), builder.build().unwrap());
+    }
+
+    #[test]
+    fn arc_crossing_zero_extents() {
+        // from 270 degrees to 90 degrees passes through 0 degrees
+        let mut builder = ExtentsBuilder::default();
+        builder.elliptic_arc(
+            &Point::origin(),
+            &Vector::x_axis(),
+            &Vector::y_axis(),
+            3.0 * std::f64::consts::FRAC_PI_2,
+            std::f64::consts::FRAC_PI_2,
+        );
+        assert_box((0.0, -1.0, 0.0), (1.0, 1.0, 0.0), builder.build().unwrap());
+    }
+
+    #[test]
+    fn bulge_segment_extents() {
+        // a bulge of 1 is a semicircle; going from (0,0) to (2,0) counter-clockwise bulges below the chord
+        let ocs = ObjectCoordinateSystem::default();
+        let mut builder = ExtentsBuilder::default();
+        builder.ocs_bulge_segment(&ocs, (0.0, 0.0), (2.0, 0.0), 1.0, 0.0);
+        assert_box((0.0, -1.0, 0.0), (2.0, 0.0, 0.0), builder.build().unwrap());
+
+        let mut builder = ExtentsBuilder::default();
+        builder.ocs_bulge_segment(&ocs, (0.0, 0.0), (2.0, 0.0), -1.0, 0.0);
+        assert_box((0.0, 0.0, 0.0), (2.0, 1.0, 0.0), builder.build().unwrap());
+    }
+}
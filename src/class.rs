@@ -192,10 +192,8 @@ impl Class {
                             class.class_name = pair.assert_string()?;
                         }
                     }
-                    3 => {
-                        if drawing.header.version >= AcadVersion::R14 {
-                            class.application_name = pair.assert_string()?;
-                        }
+                    3 if drawing.header.version >= AcadVersion::R14 => {
+                        class.application_name = pair.assert_string()?;
                     }
                     90 => {
                         if drawing.header.version <= AcadVersion::R13 {
@@ -1,4 +1,6 @@
-use crate::{CodePair, CodePairValue, DxfError, DxfResult, ExpectedType};
+use crate::{
+    CodePair, CodePairValue, DxfError, DxfResult, ErrorContext, ExpectedType, FilePosition,
+};
 
 use crate::code_pair_value::un_escape_ascii_to_unicode;
 use crate::helper_functions::*;
@@ -7,20 +9,28 @@ use std::io::{Cursor, Read};
 
 pub(crate) trait CodePairIter: Iterator<Item = DxfResult<CodePair>> {
     fn read_as_utf8(&mut self);
+    /// Describes what a `CodePair` offset from this iterator refers to.
+    fn file_position(&self, offset: usize) -> FilePosition;
 }
 
 /// Directly returns code pairs; primarily used in tests.
+#[cfg(test)]
 pub(crate) struct DirectCodePairIter {
     pairs: Vec<CodePair>,
     offset: usize,
 }
 
+#[cfg(test)]
 impl CodePairIter for DirectCodePairIter {
     fn read_as_utf8(&mut self) {
         // noop
     }
+    fn file_position(&self, offset: usize) -> FilePosition {
+        FilePosition::Line(offset)
+    }
 }
 
+#[cfg(test)]
 impl Iterator for DirectCodePairIter {
     type Item = DxfResult<CodePair>;
     fn next(&mut self) -> Option<DxfResult<CodePair>> {
@@ -54,6 +64,9 @@ impl<T: Read> CodePairIter for TextCodePairIter<T> {
     fn read_as_utf8(&mut self) {
         self.string_encoding = encoding_rs::UTF_8;
     }
+    fn file_position(&self, offset: usize) -> FilePosition {
+        FilePosition::Line(offset)
+    }
 }
 
 impl<T: Read> Iterator for TextCodePairIter<T> {
@@ -100,7 +113,21 @@ impl<T: Read> TextCodePairIter<T> {
         }
 
         let code_offset = self.offset;
-        let code = try_into_option!(parse_i32(String::from(code_line), code_offset));
+        let code = match parse_i32(code_line, code_offset) {
+            Ok(code) => code,
+            Err(e) => {
+                let context = ErrorContext {
+                    raw_value: Some(String::from(code_line)),
+                    position: Some(FilePosition::Line(code_offset)),
+                    ..Default::default()
+                };
+
+                // consume the value so that reading can resume at the next code pair
+                self.offset += 1;
+                let _ = read_line(&mut self.reader, false, self.string_encoding);
+                return Some(Err(e.with_context(context)));
+            }
+        };
 
         // Read value.  If no line is available die horribly.
         self.offset += 1;
@@ -110,26 +137,53 @@ impl<T: Read> TextCodePairIter<T> {
         };
 
         // construct the value pair
+        let value = match self.parse_value(code, value_line) {
+            Ok(v) => v,
+            Err((e, raw_value)) => {
+                let context = ErrorContext {
+                    code: Some(code),
+                    raw_value: Some(raw_value),
+                    position: Some(FilePosition::Line(self.offset)),
+                    ..Default::default()
+                };
+                return Some(Err(e.with_context(context)));
+            }
+        };
+
+        Some(Ok(CodePair::new(code, value, code_offset)))
+    }
+    /// Converts the value line to the type required by the code.  On failure the unparsed value is returned with the
+    /// error.
+    fn parse_value(
+        &self,
+        code: i32,
+        value_line: String,
+    ) -> Result<CodePairValue, (DxfError, String)> {
         let expected_type = match ExpectedType::new(code) {
             Some(t) => t,
-            None => return Some(Err(DxfError::UnexpectedEnumValue(self.offset))),
+            None => return Err((DxfError::UnexpectedEnumValue(self.offset), value_line)),
         };
         let value = match expected_type {
-            ExpectedType::Boolean => {
-                CodePairValue::Boolean(try_into_option!(parse_i16(value_line, self.offset)))
-            }
-            ExpectedType::Integer => {
-                CodePairValue::Integer(try_into_option!(parse_i32(value_line, self.offset)))
-            }
-            ExpectedType::Long => {
-                CodePairValue::Long(try_into_option!(parse_i64(value_line, self.offset)))
-            }
-            ExpectedType::Short => {
-                CodePairValue::Short(try_into_option!(parse_i16(value_line, self.offset)))
-            }
-            ExpectedType::Double => {
-                CodePairValue::Double(try_into_option!(parse_f64(value_line, self.offset)))
-            }
+            ExpectedType::Boolean => match parse_i16(&value_line, self.offset) {
+                Ok(v) => CodePairValue::Boolean(v),
+                Err(e) => return Err((e, value_line)),
+            },
+            ExpectedType::Integer => match parse_i32(&value_line, self.offset) {
+                Ok(v) => CodePairValue::Integer(v),
+                Err(e) => return Err((e, value_line)),
+            },
+            ExpectedType::Long => match parse_i64(&value_line, self.offset) {
+                Ok(v) => CodePairValue::Long(v),
+                Err(e) => return Err((e, value_line)),
+            },
+            ExpectedType::Short => match parse_i16(&value_line, self.offset) {
+                Ok(v) => CodePairValue::Short(v),
+                Err(e) => return Err((e, value_line)),
+            },
+            ExpectedType::Double => match parse_f64(&value_line, self.offset) {
+                Ok(v) => CodePairValue::Double(v),
+                Err(e) => return Err((e, value_line)),
+            },
             ExpectedType::Str => {
                 let value_line = if self.string_encoding == encoding_rs::WINDOWS_1252 {
                     un_escape_ascii_to_unicode(&value_line)
@@ -143,12 +197,12 @@ impl<T: Read> TextCodePairIter<T> {
                 let mut data = vec![];
                 match parse_hex_string(&value_line, &mut data, self.offset) {
                     Ok(()) => CodePairValue::Binary(data),
-                    Err(e) => return Some(Err(e)),
+                    Err(e) => return Err((e, value_line)),
                 }
             }
         };
 
-        Some(Ok(CodePair::new(code, value, code_offset)))
+        Ok(value)
     }
 }
 
@@ -164,6 +218,9 @@ impl<T: Read> CodePairIter for BinaryCodePairIter<T> {
     fn read_as_utf8(&mut self) {
         // noop
     }
+    fn file_position(&self, offset: usize) -> FilePosition {
+        FilePosition::Byte(offset)
+    }
 }
 
 impl<T: Read> Iterator for BinaryCodePairIter<T> {
@@ -296,7 +353,18 @@ where
 {
     let mut bytes = vec![];
     reader.read_to_end(&mut bytes)?;
-    let mut cursor = Cursor::new(bytes);
+    new_streaming_code_pair_iter(Cursor::new(bytes), string_encoding, first_line)
+}
+
+/// Creates a code pair iterator that pulls from the reader on demand instead of buffering all of the input.
+pub(crate) fn new_streaming_code_pair_iter<T>(
+    mut cursor: T,
+    string_encoding: &'static Encoding,
+    first_line: String,
+) -> DxfResult<Box<dyn CodePairIter>>
+where
+    T: Read + 'static,
+{
     let iter: Box<dyn CodePairIter> = match &*first_line {
         "AutoCAD Binary DXF" => {
             // swallow 0x1A,0x00
@@ -1,15 +1,49 @@
 use crate::code_pair_iter::CodePairIter;
 use crate::dxf_result::DxfResult;
-use crate::CodePair;
+use crate::enums::AcadVersion;
+use crate::{
+    CodePair, CodePairValue, Diagnostic, DiagnosticSeverity, DxfError, ErrorContext, Handle,
+};
+
+/// Where in the file the most recently read code pair came from.
+#[derive(Clone, Debug)]
+struct ReadContext {
+    section: String,
+    item_type: String,
+    handle: Handle,
+    offset: usize,
+}
+
+impl Default for ReadContext {
+    fn default() -> Self {
+        ReadContext {
+            section: String::new(),
+            item_type: String::new(),
+            handle: Handle::empty(),
+            offset: 0,
+        }
+    }
+}
 
 pub(crate) struct CodePairPutBack {
     top: Vec<DxfResult<CodePair>>,
     iter: Box<dyn CodePairIter>,
+    context: ReadContext,
+    version: AcadVersion,
+    lenient: bool,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl CodePairPutBack {
     pub fn from_code_pair_iter(iter: Box<dyn CodePairIter>) -> Self {
-        CodePairPutBack { top: vec![], iter }
+        CodePairPutBack {
+            top: vec![],
+            iter,
+            context: ReadContext::default(),
+            version: AcadVersion::R12,
+            lenient: false,
+            diagnostics: vec![],
+        }
     }
     pub fn put_back(&mut self, item: DxfResult<CodePair>) {
         self.top.push(item);
@@ -17,22 +51,153 @@ impl CodePairPutBack {
     pub fn read_as_utf8(&mut self) {
         self.iter.read_as_utf8()
     }
+    /// Describes the item currently being read.
+    pub fn error_context(&self) -> ErrorContext {
+        fn non_empty(s: &str) -> Option<String> {
+            if s.is_empty() {
+                None
+            } else {
+                Some(String::from(s))
+            }
+        }
+
+        ErrorContext {
+            section: non_empty(&self.context.section),
+            item_type: non_empty(&self.context.item_type),
+            handle: if self.context.handle.is_empty() {
+                None
+            } else {
+                Some(self.context.handle)
+            },
+            position: Some(self.iter.file_position(self.context.offset)),
+            ..Default::default()
+        }
+    }
+    /// The version from the `$ACADVER` header variable, or R12 (the `Header` default) if it hasn't been read.
+    pub fn version(&self) -> AcadVersion {
+        self.version
+    }
+    pub fn set_version(&mut self, version: AcadVersion) {
+        self.version = version;
+    }
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+    /// Records a problem with the current item.
+    pub fn add_diagnostic(&mut self, severity: DiagnosticSeverity, message: String) {
+        self.add_diagnostic_at(severity, self.context.offset, message);
+    }
+    fn add_diagnostic_at(&mut self, severity: DiagnosticSeverity, offset: usize, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            section: self.context.section.clone(),
+            item_type: self.context.item_type.clone(),
+            handle: self.context.handle,
+            position: self.iter.file_position(offset),
+            message,
+        });
+    }
+    /// Passes `result` through, unless the reader is lenient and the error only affected a single value, in which case
+    /// the value keeps its default and a diagnostic is recorded.
+    pub fn recover_value_error(&mut self, result: DxfResult<()>) -> DxfResult<()> {
+        match result {
+            Err(ref e) if self.lenient => match Self::recoverable_value_error_offset(e) {
+                Some(offset) => {
+                    let message = format!("the value was ignored: {}", e);
+                    self.add_diagnostic_at(DiagnosticSeverity::Warning, offset, message);
+                    Ok(())
+                }
+                None => result,
+            },
+            _ => result,
+        }
+    }
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+    /// Discards code pairs until the next 0/* pair, which is left to be read next.
+    pub fn skip_to_next_item(&mut self) -> DxfResult<()> {
+        loop {
+            match self.next() {
+                Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                    self.put_back(Ok(pair));
+                    return Ok(());
+                }
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            }
+        }
+    }
+    fn update_context(&mut self, pair: &CodePair) {
+        match (pair.code, &pair.value) {
+            (0, CodePairValue::Str(s)) => {
+                self.context.item_type.clone_from(s);
+                self.context.handle = Handle::empty();
+                self.context.offset = pair.offset;
+            }
+            (2, CodePairValue::Str(s)) if self.context.item_type == "SECTION" => {
+                self.context.section.clone_from(s);
+            }
+            (9, CodePairValue::Str(s)) if self.context.section == "HEADER" => {
+                self.context.item_type.clone_from(s);
+                self.context.offset = pair.offset;
+            }
+            (5, _) | (105, _) if self.context.handle.is_empty() => {
+                if let Ok(handle) = pair.as_handle() {
+                    self.context.handle = handle;
+                }
+            }
+            _ => (),
+        }
+    }
+    /// Returns the offset of the error if it only affected a single value and the following code pairs can still be
+    /// trusted.
+    fn recoverable_value_error_offset(e: &DxfError) -> Option<usize> {
+        match *e.root_cause() {
+            DxfError::ParseFloatError(_, offset) => Some(offset),
+            DxfError::ParseIntError(_, offset) => Some(offset),
+            DxfError::ParseError(offset) => Some(offset),
+            DxfError::UnexpectedEnumValue(offset) => Some(offset),
+            _ => None,
+        }
+    }
 }
 
 impl Iterator for CodePairPutBack {
     type Item = DxfResult<CodePair>;
 
     fn next(&mut self) -> Option<DxfResult<CodePair>> {
-        if self.top.is_empty() {
-            loop {
+        let pair = match self.top.pop() {
+            Some(pair) => Some(pair),
+            None => loop {
                 let pair = self.iter.next();
                 match pair {
                     Some(Ok(CodePair { code: 999, .. })) => (), // a 999 comment code, try again
-                    _ => return pair,
+                    Some(Err(ref e)) if self.lenient => {
+                        match Self::recoverable_value_error_offset(e) {
+                            Some(offset) => {
+                                let message = format!("the value was ignored: {}", e);
+                                self.add_diagnostic_at(
+                                    DiagnosticSeverity::Warning,
+                                    offset,
+                                    message,
+                                );
+                            }
+                            None => break pair,
+                        }
+                    }
+                    _ => break pair,
                 }
-            }
-        } else {
-            Some(self.top.pop().unwrap())
+            },
+        };
+        if let Some(Ok(ref pair)) = pair {
+            self.update_context(pair);
         }
+
+        pair
     }
 }
@@ -1,10 +1,84 @@
 use crate::tables::Layer;
 
-/// Represents an indexed color.
+/// The RGB values of the AutoCAD Color Index (ACI) palette.  Index 0 (`BYBLOCK`) is listed as black.
+static ACI_PALETTE: [(u8, u8, u8); 256] = aci_palette();
+
+const fn aci_palette() -> [(u8, u8, u8); 256] {
+    const STANDARD_COLORS: [(u8, u8, u8); 9] = [
+        (255, 0, 0),
+        (255, 255, 0),
+        (0, 255, 0),
+        (0, 255, 255),
+        (0, 0, 255),
+        (255, 0, 255),
+        (255, 255, 255),
+        (128, 128, 128),
+        (192, 192, 192),
+    ];
+    const BRIGHTNESS_LEVELS: [u16; 5] = [255, 165, 127, 76, 38];
+    const GRAYS: [u8; 6] = [51, 80, 105, 130, 190, 255];
+
+    let mut palette = [(0, 0, 0); 256];
+    let mut i = 0;
+    while i < STANDARD_COLORS.len() {
+        palette[i + 1] = STANDARD_COLORS[i];
+        i += 1;
+    }
+
+    // indices 10-249 are 24 hues 15 degrees apart, each at 5 brightness levels; odd indices are half saturated
+    let mut index = 10;
+    while index < 250 {
+        let hue = (index / 10 - 1) as u16 * 15;
+        let level = BRIGHTNESS_LEVELS[(index % 10) / 2];
+        let (r, g, b) = hue_to_rgb(hue, level);
+        palette[index] = if index % 2 == 0 {
+            (r as u8, g as u8, b as u8)
+        } else {
+            (
+                ((r + level) / 2) as u8,
+                ((g + level) / 2) as u8,
+                ((b + level) / 2) as u8,
+            )
+        };
+        index += 1;
+    }
+
+    let mut i = 0;
+    while i < GRAYS.len() {
+        let gray = GRAYS[i];
+        palette[250 + i] = (gray, gray, gray);
+        i += 1;
+    }
+
+    palette
+}
+
+/// Returns the fully saturated color with the specified hue (in degrees) and brightness.
+const fn hue_to_rgb(hue: u16, level: u16) -> (u16, u16, u16) {
+    let rising = level * (hue % 60) / 60;
+    let falling = level * (60 - hue % 60) / 60;
+    match hue / 60 {
+        0 => (level, rising, 0),
+        1 => (falling, level, 0),
+        2 => (0, level, rising),
+        3 => (0, falling, level),
+        4 => (rising, 0, level),
+        _ => (level, 0, falling),
+    }
+}
+
+/// Represents a color.
+///
+/// A color is either an index into the AutoCAD Color Index (ACI) palette, one of the special values that defer to
+/// another item's color (e.g., `BYLAYER`), a 24-bit true color, or a named color from a color book.  True colors also
+/// carry the closest palette index, which is what's written to versions that predate true color support (before
+/// `R2004`).
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     raw_value: i16,
+    true_color: Option<i32>,
+    color_name: Option<String>,
 }
 
 impl Color {
@@ -26,25 +100,34 @@ impl Color {
     }
     /// Sets the color to default back to the item's layer's color.
     pub fn set_by_layer(&mut self) {
-        self.raw_value = 256
+        *self = Color::by_layer()
     }
     /// Sets the color to default back to the containing block's color.
     pub fn set_by_block(&mut self) {
-        self.raw_value = 0
+        *self = Color::by_block()
     }
     /// Sets the color to default back to the containing entity's color.
     pub fn set_by_entity(&mut self) {
-        self.raw_value = 257
+        *self = Color::by_entity()
     }
     /// Sets the color to represent a `Layer` that is turned off.
     pub fn turn_off(&mut self) {
         self.raw_value = -1
     }
-    /// Returns `true` if the color represents a proper color index.
+    /// Returns `true` if the color represents a proper color index.  This is also the case for true colors, which
+    /// carry their closest index.
     pub fn is_index(&self) -> bool {
         self.raw_value >= 1 && self.raw_value <= 255
     }
-    /// Gets an `Option<u8>` of the indexable value of the color.
+    /// Returns `true` if the color is a 24-bit true color.
+    pub fn is_true_color(&self) -> bool {
+        self.true_color.is_some()
+    }
+    /// Returns `true` if the color is a named color from a color book.
+    pub fn is_from_color_book(&self) -> bool {
+        self.color_name.is_some()
+    }
+    /// Gets an `Option<u8>` of the indexable value of the color.  For true colors this is the closest palette index.
     pub fn index(&self) -> Option<u8> {
         if self.is_index() {
             Some(self.raw_value as u8)
@@ -52,30 +135,128 @@ impl Color {
             None
         }
     }
+    /// Gets the red, green, and blue components of a true color.
+    pub fn true_color(&self) -> Option<(u8, u8, u8)> {
+        self.true_color.map(|value| {
+            (
+                (value >> 16 & 0xFF) as u8,
+                (value >> 8 & 0xFF) as u8,
+                (value & 0xFF) as u8,
+            )
+        })
+    }
+    /// Gets the names of the color book and of the color within it, e.g., `("PANTONE+ Solid Coated", "PANTONE 286 C")`.
+    pub fn color_book(&self) -> Option<(&str, &str)> {
+        self.color_name
+            .as_deref()
+            .map(|name| name.split_once('$').unwrap_or(("", name)))
+    }
+    /// Gets the red, green, and blue components of the color, either directly from a true color or by looking the index
+    /// up in the standard palette.  Returns `None` for colors that defer to another item, e.g., `BYLAYER`.
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        match self.true_color() {
+            Some(rgb) => Some(rgb),
+            None => self.index().map(Color::index_to_rgb),
+        }
+    }
+    /// Sets the color to the specified true color.
+    pub fn set_true_color(&mut self, r: u8, g: u8, b: u8) {
+        *self = Color::from_rgb(r, g, b)
+    }
     pub(crate) fn raw_value(&self) -> i16 {
         self.raw_value
     }
     pub(crate) fn from_raw_value(val: i16) -> Color {
-        Color { raw_value: val }
+        Color {
+            raw_value: val,
+            ..Default::default()
+        }
+    }
+    /// Sets the value of the index code pair without discarding any true color that was already read.
+    pub(crate) fn set_raw_value(&mut self, val: i16) {
+        self.raw_value = val;
+    }
+    pub(crate) fn true_color_raw_value(&self) -> Option<i32> {
+        self.true_color
+    }
+    /// Sets the value of the 24-bit color code pair, as `0x00RRGGBB`.
+    pub(crate) fn set_true_color_raw_value(&mut self, val: i32) {
+        let val = val & 0xFF_FFFF;
+        self.true_color = Some(val);
+        if !(1..=255).contains(&self.raw_value.abs()) {
+            // no index was given; fall back to the closest one, keeping a turned off layer turned off
+            let index = i16::from(Color::nearest_index(
+                (val >> 16) as u8,
+                (val >> 8) as u8,
+                val as u8,
+            ));
+            self.raw_value = if self.raw_value < 0 { -index } else { index };
+        }
+    }
+    /// Gets the value of the color name code pair, as `BOOK$COLOR`.
+    pub(crate) fn color_name_raw_value(&self) -> Option<&str> {
+        self.color_name.as_deref()
+    }
+    /// Sets the value of the color name code pair; an empty name means the color isn't from a color book.
+    pub(crate) fn set_color_name_raw_value(&mut self, val: String) {
+        self.color_name = if val.is_empty() { None } else { Some(val) };
     }
     /// Creates a `Color` that defaults to the item's layer's color.
     pub fn by_layer() -> Color {
-        Color { raw_value: 256 }
+        Color::from_raw_value(256)
     }
     /// Creates a `Color` that defaults back to the containing block's color.
     pub fn by_block() -> Color {
-        Color { raw_value: 0 }
+        Color::from_raw_value(0)
     }
     /// Creates a `Color` that defaults back to the containing entity's color.
     pub fn by_entity() -> Color {
-        Color { raw_value: 257 }
+        Color::from_raw_value(257)
     }
     /// Creates a `Color` from the specified index.
     pub fn from_index(i: u8) -> Color {
+        Color::from_raw_value(i16::from(i))
+    }
+    /// Creates a 24-bit true `Color` from the specified red, green, and blue components.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Color {
+        Color {
+            raw_value: i16::from(Color::nearest_index(r, g, b)),
+            true_color: Some(i32::from(r) << 16 | i32::from(g) << 8 | i32::from(b)),
+            color_name: None,
+        }
+    }
+    /// Creates a `Color` from a color book.  The red, green, and blue components are used by readers that don't have
+    /// access to the color book.
+    pub fn from_color_book(book_name: &str, color_name: &str, r: u8, g: u8, b: u8) -> Color {
         Color {
-            raw_value: i16::from(i),
+            color_name: Some(format!("{}${}", book_name, color_name)),
+            ..Color::from_rgb(r, g, b)
         }
     }
+    /// Gets the red, green, and blue components of the specified entry in the standard palette.
+    pub fn index_to_rgb(index: u8) -> (u8, u8, u8) {
+        ACI_PALETTE[usize::from(index)]
+    }
+    /// Gets the index of the standard palette entry that is closest to the specified color.  The special values
+    /// `BYBLOCK` and `BYLAYER` are never returned.
+    pub fn nearest_index(r: u8, g: u8, b: u8) -> u8 {
+        let distance = |&(pr, pg, pb): &(u8, u8, u8)| {
+            let dr = i32::from(pr) - i32::from(r);
+            let dg = i32::from(pg) - i32::from(g);
+            let db = i32::from(pb) - i32::from(b);
+            dr * dr + dg * dg + db * db
+        };
+        let mut nearest = 1;
+        for index in 2..=255 {
+            if distance(&ACI_PALETTE[usize::from(index)])
+                < distance(&ACI_PALETTE[usize::from(nearest)])
+            {
+                nearest = index;
+            }
+        }
+
+        nearest
+    }
     pub(crate) fn writable_color_value(&self, layer: &Layer) -> i16 {
         let value = self.raw_value().abs();
         if layer.is_layer_on {
@@ -97,6 +278,9 @@ impl Color {
         } else if self.is_turned_off() {
             // Turned off - use ByLayer with special flag
             -1073741824
+        } else if let Some(rgb) = self.true_color {
+            // true color: encode as 0xC2000000 | RGB
+            0xC2000000u32 as i32 | rgb
         } else if self.is_index() {
             // ACI color index: encode as 0x03000000 | index
             0x03000000 | (self.raw_value as i32)
@@ -111,6 +295,10 @@ impl Color {
         match raw {
             -1073741824 => Color::by_layer(), // 0xC0000000
             -1056964608 => Color::by_block(), // 0xC1000000
+            _ if (raw & 0xFF000000u32 as i32) == 0xC2000000u32 as i32 => {
+                // true color: extract lower three bytes
+                Color::from_rgb((raw >> 16) as u8, (raw >> 8) as u8, raw as u8)
+            }
             _ if (raw & 0xFF000000u32 as i32) == 0x03000000 => {
                 // ACI color: extract lower byte
                 let index = (raw & 0xFF) as u8;
@@ -120,3 +308,81 @@ impl Color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    #[test]
+    fn palette_values() {
+        assert_eq!((255, 0, 0), Color::index_to_rgb(1));
+        assert_eq!((255, 255, 255), Color::index_to_rgb(7));
+        assert_eq!((255, 0, 0), Color::index_to_rgb(10));
+        assert_eq!((255, 127, 127), Color::index_to_rgb(11));
+        assert_eq!((165, 103, 82), Color::index_to_rgb(23));
+        assert_eq!((0, 191, 255), Color::index_to_rgb(140));
+        assert_eq!((51, 51, 51), Color::index_to_rgb(250));
+    }
+
+    #[test]
+    fn nearest_index() {
+        assert_eq!(1, Color::nearest_index(255, 0, 0));
+        assert_eq!(1, Color::nearest_index(250, 5, 5));
+        assert_eq!(7, Color::nearest_index(255, 255, 255));
+        assert_eq!(140, Color::nearest_index(0, 190, 250));
+    }
+
+    #[test]
+    fn true_color_keeps_closest_index() {
+        let color = Color::from_rgb(0, 190, 250);
+        assert!(color.is_true_color());
+        assert_eq!(Some((0, 190, 250)), color.true_color());
+        assert_eq!(Some((0, 190, 250)), color.rgb());
+        assert_eq!(Some(140), color.index());
+        assert_eq!(Some(0x00BEFA), color.true_color_raw_value());
+    }
+
+    #[test]
+    fn true_color_read_after_index() {
+        let mut color = Color::from_raw_value(5);
+        color.set_true_color_raw_value(0xFF0000);
+        assert_eq!(Some(5), color.index());
+        assert_eq!(Some((255, 0, 0)), color.true_color());
+
+        // without an index, the closest one is used
+        let mut color = Color::by_layer();
+        color.set_true_color_raw_value(0xFF0000);
+        assert_eq!(Some(1), color.index());
+    }
+
+    #[test]
+    fn color_book() {
+        let color = Color::from_color_book("RAL CLASSIC", "RAL 3020", 204, 6, 5);
+        assert!(color.is_from_color_book());
+        assert_eq!(Some(("RAL CLASSIC", "RAL 3020")), color.color_book());
+        assert_eq!(Some("RAL CLASSIC$RAL 3020"), color.color_name_raw_value());
+        assert_eq!(Some((204, 6, 5)), color.true_color());
+    }
+
+    #[test]
+    fn empty_color_name_is_not_from_color_book() {
+        let mut color = Color::from_index(1);
+        color.set_color_name_raw_value(String::new());
+        assert!(!color.is_from_color_book());
+        assert_eq!(None, color.color_name_raw_value());
+    }
+
+    #[test]
+    fn index_color_rgb() {
+        assert_eq!(Some((255, 255, 0)), Color::from_index(2).rgb());
+        assert_eq!(None, Color::by_layer().rgb());
+        assert!(!Color::from_index(2).is_true_color());
+    }
+
+    #[test]
+    fn mleader_true_color_round_trip() {
+        let color = Color::from_rgb(1, 2, 3);
+        assert_eq!(0xC2010203u32 as i32, color.to_mleader_raw_value());
+        assert_eq!(color, Color::from_mleader_raw_value(0xC2010203u32 as i32));
+    }
+}
@@ -10,16 +10,21 @@ use crate::header::*;
 use crate::objects::*;
 use crate::tables::*;
 
-use crate::{CodePair, CodePairValue, DxfError, DxfResult, Handle};
+use crate::{
+    BoundingBox, CodePair, CodePairValue, Diagnostic, DiagnosticSeverity, DxfError, DxfResult,
+    EffectiveProperties, Handle, InsertContext, LoadOptions, Point, SpatialIndex,
+};
 
 use crate::dxb_reader::DxbReader;
 use crate::dxb_writer::DxbWriter;
 use crate::entity_iter::EntityIter;
 use crate::helper_functions::*;
 use crate::object_iter::ObjectIter;
+use crate::table::LAYER_TRANSPARENCY_APPLICATION_NAME;
 
 use crate::block::Block;
 use crate::class::Class;
+use crate::raw_section::RawSection;
 
 use crate::code_pair_iter::{new_code_pair_iter_from_reader, CodePairIter};
 use crate::code_pair_writer::CodePairWriter;
@@ -29,13 +34,22 @@ use crate::thumbnail;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Cursor, Read, Write};
 
-use itertools::put_back;
 use std::collections::HashSet;
 use std::iter::Iterator;
 use std::path::Path;
 
 pub(crate) const AUTO_REPLACE_HANDLE: Handle = Handle(0xFFFF_FFFF_FFFF_FFFF);
 
+const STANDARD_SECTION_NAMES: [&str; 7] = [
+    "HEADER",
+    "CLASSES",
+    "TABLES",
+    "BLOCKS",
+    "ENTITIES",
+    "OBJECTS",
+    "THUMBNAILIMAGE",
+];
+
 /// Represents a DXF drawing.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -73,9 +87,16 @@ pub struct Drawing {
     /// Internal collection of objects.
     __objects: Vec<Object>,
 
+    /// The optional index of entity bounding boxes.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    __spatial_index: Option<SpatialIndex>,
+
     /// The thumbnail image preview of the drawing.
     #[cfg_attr(feature = "serialize", serde(skip))]
     pub thumbnail: Option<DynamicImage>,
+
+    /// Sections not otherwise understood, e.g., `ACDSDATA`.  These are written back out verbatim.
+    pub raw_sections: Vec<RawSection>,
 }
 
 // public implementation
@@ -97,7 +118,9 @@ impl Drawing {
             __blocks: vec![],
             __entities: vec![],
             __objects: vec![],
+            __spatial_index: None,
             thumbnail: None,
+            raw_sections: vec![],
         };
         drawing.normalize();
         drawing
@@ -114,37 +137,62 @@ impl Drawing {
     where
         T: Read + ?Sized,
     {
-        let first_line = read_line(reader, true, encoding)?;
+        let options = LoadOptions {
+            encoding,
+            ..Default::default()
+        };
+        let (drawing, _diagnostics) = Drawing::load_with_options(reader, &options)?;
+        Ok(drawing)
+    }
+    /// Loads a `Drawing` from anything that implements the `Read` trait using the specified options.  Also returns any
+    /// problems that were skipped because `options.lenient` was set.
+    pub fn load_with_options<T>(
+        reader: &mut T,
+        options: &LoadOptions,
+    ) -> DxfResult<(Drawing, Vec<Diagnostic>)>
+    where
+        T: Read + ?Sized,
+    {
+        let first_line = read_line(reader, true, options.encoding)?;
         match &*first_line {
             "AutoCAD DXB 1.0" => {
                 let mut reader = DxbReader::new(reader);
-                reader.load()
+                Ok((reader.load()?, vec![]))
             }
             _ => {
-                let iter = new_code_pair_iter_from_reader(reader, encoding, first_line)?;
-                Drawing::load_from_iter(iter)
+                let iter = new_code_pair_iter_from_reader(reader, options.encoding, first_line)?;
+                Drawing::load_from_iter(iter, options.lenient)
             }
         }
     }
     /// Loads a `Drawing` from the specified `CodePairIter`.
-    pub(crate) fn load_from_iter(iter: Box<dyn CodePairIter>) -> DxfResult<Drawing> {
+    pub(crate) fn load_from_iter(
+        iter: Box<dyn CodePairIter>,
+        lenient: bool,
+    ) -> DxfResult<(Drawing, Vec<Diagnostic>)> {
         let mut drawing = Drawing::new();
         drawing.clear();
         let mut iter = CodePairPutBack::from_code_pair_iter(iter);
-        Drawing::read_sections(&mut drawing, &mut iter)?;
+        iter.set_lenient(lenient);
+        Drawing::read_sections(&mut drawing, &mut iter)
+            .map_err(|e| e.with_context(iter.error_context()))?;
         match iter.next() {
             Some(Ok(CodePair {
                 code: 0,
                 value: CodePairValue::Str(ref s),
                 ..
-            })) if s == "EOF" => Ok(drawing),
-            Some(Ok(pair)) => Err(DxfError::UnexpectedCodePair(
-                pair,
-                String::from("expected 0/EOF"),
-            )),
-            Some(Err(e)) => Err(e),
-            None => Ok(drawing),
+            })) if s == "EOF" => (),
+            Some(Ok(pair)) => {
+                return Err(DxfError::UnexpectedCodePair(
+                    pair,
+                    String::from("expected 0/EOF"),
+                ))
+            }
+            Some(Err(e)) => return Err(e),
+            None => (),
         }
+
+        Ok((drawing, iter.take_diagnostics()))
     }
     /// Loads a `Drawing` from disk, using a `BufReader`.
     pub fn load_file(path: impl AsRef<Path>) -> DxfResult<Drawing> {
@@ -159,6 +207,15 @@ impl Drawing {
         let mut buf_reader = BufReader::new(file);
         Drawing::load_with_encoding(&mut buf_reader, encoding)
     }
+    /// Loads a `Drawing` from disk, using a `BufReader` with the specified options.
+    pub fn load_file_with_options(
+        path: impl AsRef<Path>,
+        options: &LoadOptions,
+    ) -> DxfResult<(Drawing, Vec<Diagnostic>)> {
+        let file = File::open(&path)?;
+        let mut buf_reader = BufReader::new(file);
+        Drawing::load_with_options(&mut buf_reader, options)
+    }
     /// Writes a `Drawing` to anything that implements the `Write` trait.
     pub fn save<T>(&self, writer: &mut T) -> DxfResult<()>
     where
@@ -175,16 +232,11 @@ impl Drawing {
     }
     /// Gets all code pairs that will be written.
     pub(crate) fn code_pairs(&self) -> DxfResult<Vec<CodePair>> {
-        let write_handles = self.header.version >= AcadVersion::R13 || self.header.handles_enabled;
+        let write_handles = self.write_handles();
         let mut pairs = Vec::new();
-        self.header.add_code_pairs(&mut pairs);
-        self.add_classes_pairs(&mut pairs);
-        self.add_tables_pairs(&mut pairs, write_handles);
-        self.add_blocks_pairs(&mut pairs, write_handles);
+        self.add_pairs_before_entities(&mut pairs, write_handles);
         self.add_entities_pairs(&mut pairs, write_handles);
-        self.add_objects_pairs(&mut pairs);
-        self.add_thumbnail_pairs(&mut pairs)?;
-        pairs.push(CodePair::new_str(0, "EOF"));
+        self.add_pairs_after_entities(&mut pairs)?;
         Ok(pairs)
     }
     fn save_internal<T>(&self, writer: &mut T, as_ascii: bool) -> DxfResult<()>
@@ -409,35 +461,30 @@ impl Drawing {
     }
     /// Adds an entity to the `Drawing`.
     pub fn add_entity(&mut self, mut entity: Entity) -> &Entity {
-        entity.common.handle = self.next_handle();
+        self.set_entity_handles(&mut entity);
 
-        // set child handles
-        match entity.specific {
-            EntityType::Insert(ref mut ins) => {
-                ins.__seqend_handle = self.next_handle();
-                for a in ins.__attributes_and_handles.iter_mut() {
-                    if a.1 == AUTO_REPLACE_HANDLE {
-                        a.1 = self.next_handle();
-                    }
-                }
-            }
-            EntityType::Polyline(ref mut poly) => {
-                poly.__seqend_handle = self.next_handle();
-                for v in poly.__vertices_and_handles.iter_mut() {
-                    if v.1 == AUTO_REPLACE_HANDLE {
-                        v.1 = self.next_handle();
-                    }
-                }
+        // ensure invariants
+        self.add_entity_no_handle_set(entity);
+
+        // the index is taken out while the extents are computed, since they need the whole drawing
+        if let Some(mut index) = self.__spatial_index.take() {
+            let entity = self.__entities.last().unwrap();
+            if let Some(bbox) = entity.bounding_box(self) {
+                index.insert(entity.common.handle, &bbox);
             }
-            _ => (),
+            self.__spatial_index = Some(index);
         }
 
-        // ensure invariants
-        self.add_entity_no_handle_set(entity)
+        self.__entities.last().unwrap()
     }
     /// Removes the specified `Entity` from the `Drawing`.
     pub fn remove_entity(&mut self, index: usize) -> Option<Entity> {
-        Drawing::remove_item(&mut self.__entities, index)
+        let entity = Drawing::remove_item(&mut self.__entities, index);
+        if let (Some(spatial_index), Some(entity)) = (self.__spatial_index.as_mut(), &entity) {
+            spatial_index.remove(entity.common.handle);
+        }
+
+        entity
     }
     /// Returns an iterator for all contained objects.
     pub fn objects(&self) -> impl Iterator<Item = &Object> {
@@ -475,10 +522,57 @@ impl Drawing {
         self.__blocks.clear();
         self.__entities.clear();
         self.__objects.clear();
+        if let Some(index) = self.__spatial_index.as_mut() {
+            index.clear();
+        }
         self.thumbnail = None;
+        self.raw_sections.clear();
 
         self.header.next_available_handle = Handle(1);
     }
+    /// Builds an index of the bounding boxes of all entities for fast region and nearest-entity queries.  Once built,
+    /// the index is kept up to date by `add_entity()`, `remove_entity()`, and `clear()`; it must be rebuilt after
+    /// entities are modified in place or blocks referenced by `Insert` entities change.
+    pub fn build_spatial_index(&mut self) {
+        self.__spatial_index = Some(SpatialIndex::from_drawing(self));
+    }
+    /// Returns the index built by `build_spatial_index()`, if any.
+    pub fn spatial_index(&self) -> Option<&SpatialIndex> {
+        self.__spatial_index.as_ref()
+    }
+    /// Discards the index built by `build_spatial_index()` so that adding and removing entities no longer updates it.
+    pub fn drop_spatial_index(&mut self) {
+        self.__spatial_index = None;
+    }
+    /// Returns the extents of all model space entities, or `None` if none of them have measurable geometry.  See
+    /// `Entity::bounding_box()` for how each entity is measured.
+    pub fn extents(&self) -> Option<BoundingBox> {
+        self.entity_extents(false)
+    }
+    /// Returns the extents of all paper space entities, or `None` if none of them have measurable geometry.
+    pub fn paper_space_extents(&self) -> Option<BoundingBox> {
+        self.entity_extents(true)
+    }
+    /// Sets the header's `$EXTMIN`/`$EXTMAX` and `$PEXTMIN`/`$PEXTMAX` values from the current entities.  Empty extents
+    /// are written the way AutoCAD does, with the minimum greater than the maximum.
+    pub fn update_extents(&mut self) {
+        let (min, max) = Drawing::extents_or_empty(self.extents());
+        self.header.minimum_drawing_extents = min;
+        self.header.maximum_drawing_extents = max;
+        let (min, max) = Drawing::extents_or_empty(self.paper_space_extents());
+        self.header.paperspace_minimum_drawing_extents = min;
+        self.header.paperspace_maximum_drawing_extents = max;
+    }
+    /// Resolves the BYLAYER and BYBLOCK display properties of the entity, along with whether it's visible and plotted.
+    /// Entities that are drawn through `Insert` entities need the matching `InsertContext`; see
+    /// `InsertContext::nested()`.
+    pub fn effective_properties(
+        &self,
+        entity: &Entity,
+        context: &InsertContext,
+    ) -> EffectiveProperties {
+        EffectiveProperties::resolve(self, entity, context)
+    }
     /// Normalizes the `Drawing` by ensuring expected items are present.
     pub fn normalize(&mut self) {
         // TODO: check for duplicates
@@ -658,6 +752,51 @@ impl Drawing {
         self.header.next_available_handle = self.header.next_available_handle.next_handle_value();
         result
     }
+    pub(crate) fn set_entity_handles(&mut self, entity: &mut Entity) {
+        entity.common.handle = self.next_handle();
+
+        // set child handles
+        match entity.specific {
+            EntityType::Insert(ref mut ins) => {
+                ins.__seqend_handle = self.next_handle();
+                for a in ins.__attributes_and_handles.iter_mut() {
+                    if a.1 == AUTO_REPLACE_HANDLE {
+                        a.1 = self.next_handle();
+                    }
+                }
+            }
+            EntityType::Polyline(ref mut poly) => {
+                poly.__seqend_handle = self.next_handle();
+                for v in poly.__vertices_and_handles.iter_mut() {
+                    if v.1 == AUTO_REPLACE_HANDLE {
+                        v.1 = self.next_handle();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    /// The number of handles `set_entity_handles()` will take for `entity`.
+    pub(crate) fn entity_handle_count(entity: &Entity) -> u64 {
+        let unset_children = match entity.specific {
+            EntityType::Insert(ref ins) => {
+                1 + ins
+                    .__attributes_and_handles
+                    .iter()
+                    .filter(|a| a.1 == AUTO_REPLACE_HANDLE)
+                    .count()
+            }
+            EntityType::Polyline(ref poly) => {
+                1 + poly
+                    .__vertices_and_handles
+                    .iter()
+                    .filter(|v| v.1 == AUTO_REPLACE_HANDLE)
+                    .count()
+            }
+            _ => 0,
+        };
+        1 + unset_children as u64
+    }
     fn remove_item<T>(collection: &mut Vec<T>, index: usize) -> Option<T> {
         if index < collection.len() {
             Some(collection.remove(index))
@@ -756,10 +895,10 @@ impl Drawing {
         }
     }
     fn ensure_dictionary_element_is_present(&mut self) {
-        if !self.objects().any(|o| match o.specific {
-            ObjectType::Dictionary(_) => true,
-            _ => false,
-        }) {
+        if !self
+            .objects()
+            .any(|o| matches!(o.specific, ObjectType::Dictionary(_)))
+        {
             self.add_object(Object::new(ObjectType::Dictionary(Dictionary {
                 ..Default::default()
             })));
@@ -788,6 +927,10 @@ impl Drawing {
                 Some(&d.dimension_base.dimension_style_name)
             }
             EntityType::OrdinateDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
+            EntityType::ArcDimension(ref d) => Some(&d.dimension_base.dimension_style_name),
+            EntityType::JoggedRadialDimension(ref d) => {
+                Some(&d.dimension_base.dimension_style_name)
+            }
             EntityType::Leader(ref l) => Some(&l.dimension_style_name),
             EntityType::Tolerance(ref t) => Some(&t.dimension_style_name),
             _ => None,
@@ -914,6 +1057,56 @@ impl Drawing {
             });
         }
     }
+    fn entity_extents(&self, is_in_paper_space: bool) -> Option<BoundingBox> {
+        let mut result: Option<BoundingBox> = None;
+        for entity in self
+            .entities()
+            .filter(|e| e.common.is_in_paper_space == is_in_paper_space)
+        {
+            if let Some(bbox) = entity.bounding_box(self) {
+                match result {
+                    Some(ref mut result) => result.include(&bbox),
+                    None => result = Some(bbox),
+                }
+            }
+        }
+
+        result
+    }
+    fn extents_or_empty(extents: Option<BoundingBox>) -> (Point, Point) {
+        match extents {
+            Some(bbox) => (bbox.min, bbox.max),
+            None => (
+                Point::new(1.0e20, 1.0e20, 1.0e20),
+                Point::new(-1.0e20, -1.0e20, -1.0e20),
+            ),
+        }
+    }
+    pub(crate) fn write_handles(&self) -> bool {
+        self.header.version >= AcadVersion::R13 || self.header.handles_enabled
+    }
+    /// Adds all sections that precede `ENTITIES`.
+    pub(crate) fn add_pairs_before_entities(&self, pairs: &mut Vec<CodePair>, write_handles: bool) {
+        self.add_raw_sections_pairs(pairs, "");
+        self.header.add_code_pairs(pairs);
+        self.add_raw_sections_pairs(pairs, "HEADER");
+        self.add_classes_pairs(pairs);
+        self.add_raw_sections_pairs(pairs, "CLASSES");
+        self.add_tables_pairs(pairs, write_handles);
+        self.add_raw_sections_pairs(pairs, "TABLES");
+        self.add_blocks_pairs(pairs, write_handles);
+        self.add_raw_sections_pairs(pairs, "BLOCKS");
+    }
+    /// Adds all sections that follow `ENTITIES` and the final `0/EOF`.
+    pub(crate) fn add_pairs_after_entities(&self, pairs: &mut Vec<CodePair>) -> DxfResult<()> {
+        self.add_raw_sections_pairs(pairs, "ENTITIES");
+        self.add_objects_pairs(pairs);
+        self.add_raw_sections_pairs(pairs, "OBJECTS");
+        self.add_thumbnail_pairs(pairs)?;
+        self.add_raw_sections_pairs(pairs, "THUMBNAILIMAGE");
+        pairs.push(CodePair::new_str(0, "EOF"));
+        Ok(())
+    }
     pub(crate) fn add_classes_pairs(&self, pairs: &mut Vec<CodePair>) {
         if self.classes.is_empty() {
             return;
@@ -984,7 +1177,22 @@ impl Drawing {
         }
         Ok(())
     }
+    fn add_raw_sections_pairs(&self, pairs: &mut Vec<CodePair>, preceding_section: &str) {
+        for section in &self.raw_sections {
+            let is_last = !STANDARD_SECTION_NAMES.contains(&&*section.preceding_section);
+            let matches = if preceding_section == "THUMBNAILIMAGE" {
+                section.preceding_section == preceding_section || is_last
+            } else {
+                section.preceding_section == preceding_section
+            };
+            if matches {
+                section.add_code_pairs(pairs);
+            }
+        }
+    }
     fn read_sections(drawing: &mut Drawing, iter: &mut CodePairPutBack) -> DxfResult<()> {
+        // raw sections remember the last standard section seen so they can be re-emitted in place
+        let mut preceding_section = String::new();
         loop {
             match iter.next() {
                 Some(Ok(pair @ CodePair { code: 0, .. })) => match &*pair.assert_string()? {
@@ -1012,7 +1220,14 @@ impl Drawing {
                                 "THUMBNAILIMAGE" => {
                                     drawing.thumbnail = thumbnail::read_thumbnail(iter)?;
                                 }
-                                _ => Drawing::swallow_section(iter)?,
+                                _ => {
+                                    let section = RawSection::read(&s, &preceding_section, iter)?;
+                                    drawing.raw_sections.push(section);
+                                }
+                            }
+
+                            if STANDARD_SECTION_NAMES.contains(&&*s) {
+                                preceding_section = s;
                             }
 
                             match iter.next() {
@@ -1021,6 +1236,14 @@ impl Drawing {
                                     value: CodePairValue::Str(ref s),
                                     ..
                                 })) if s == "ENDSEC" => (),
+                                Some(Ok(pair)) if iter.is_lenient() => {
+                                    let message = format!(
+                                        "the code pair {:?} and the rest of the section were skipped",
+                                        pair
+                                    );
+                                    iter.add_diagnostic(DiagnosticSeverity::Error, message);
+                                    Drawing::skip_past_end_of_section(iter)?;
+                                }
                                 Some(Ok(pair)) => {
                                     return Err(DxfError::UnexpectedCodePair(
                                         pair,
@@ -1040,6 +1263,11 @@ impl Drawing {
                         Some(Err(e)) => return Err(e),
                         None => return Err(DxfError::UnexpectedEndOfInput),
                     },
+                    _ if iter.is_lenient() => {
+                        let message = String::from("the item outside of a section was skipped");
+                        iter.add_diagnostic(DiagnosticSeverity::Error, message);
+                        iter.skip_to_next_item()?;
+                    }
                     _ => {
                         return Err(DxfError::UnexpectedCodePair(
                             pair,
@@ -1047,6 +1275,12 @@ impl Drawing {
                         ))
                     }
                 },
+                Some(Ok(pair)) if iter.is_lenient() => {
+                    let message =
+                        format!("the code pair {:?} outside of a section was skipped", pair);
+                    iter.add_diagnostic(DiagnosticSeverity::Error, message);
+                    iter.skip_to_next_item()?;
+                }
                 Some(Ok(pair)) => {
                     return Err(DxfError::UnexpectedCodePair(
                         pair,
@@ -1060,24 +1294,21 @@ impl Drawing {
 
         Ok(())
     }
-    fn swallow_section(iter: &mut CodePairPutBack) -> DxfResult<()> {
+    fn skip_past_end_of_section(iter: &mut CodePairPutBack) -> DxfResult<()> {
         loop {
             match iter.next() {
                 Some(Ok(pair)) => {
                     if pair.code == 0 && pair.assert_string()? == "ENDSEC" {
-                        iter.put_back(Ok(pair));
-                        break;
+                        return Ok(());
                     }
                 }
                 Some(Err(e)) => return Err(e),
-                None => break,
+                None => return Ok(()),
             }
         }
-
-        Ok(())
     }
     fn read_entities(&mut self, iter: &mut CodePairPutBack) -> DxfResult<()> {
-        let mut iter = EntityIter { iter };
+        let mut iter = EntityIter::new(iter);
         let mut entities = vec![];
         iter.read_entities_into_vec(&mut entities)?;
         for e in entities {
@@ -1090,8 +1321,8 @@ impl Drawing {
         Ok(())
     }
     fn read_objects(&mut self, iter: &mut CodePairPutBack) -> DxfResult<()> {
-        let iter = put_back(ObjectIter { iter });
-        for o in iter {
+        let mut iter = ObjectIter::new(iter);
+        for o in iter.by_ref() {
             if o.common.handle.is_empty() {
                 self.add_object(o);
             } else {
@@ -1099,7 +1330,10 @@ impl Drawing {
             }
         }
 
-        Ok(())
+        match iter.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
     fn read_section_item<F>(
         &mut self,
@@ -1180,6 +1414,13 @@ impl Drawing {
         self.ensure_app_id_is_present("ACADANNOTATIVE");
         self.ensure_app_id_is_present("ACAD_NAV_VCDISPLAY");
         self.ensure_app_id_is_present("ACAD_MLEADERVER");
+        if self
+            .layers()
+            .flat_map(|l| l.x_data.iter())
+            .any(|x| x.application_name == LAYER_TRANSPARENCY_APPLICATION_NAME)
+        {
+            self.ensure_app_id_is_present(LAYER_TRANSPARENCY_APPLICATION_NAME);
+        }
     }
     fn normalize_block_records(&mut self) {
         // ensure all block records that should exist do
@@ -2130,4 +2371,111 @@ mod tests {
         let views = drawing.views().filter(|&v| v.name == "some-view");
         assert_eq!(1, views.count());
     }
+
+    #[test]
+    fn read_unsupported_section_as_raw_section() {
+        let drawing = drawing_from_pairs(vec![
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "ENTITIES"),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "ACDSDATA"),
+            CodePair::new_i16(70, 2),
+            CodePair::new_str(0, "ACDSSCHEMA"),
+            CodePair::new_i32(90, 0),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "EOF"),
+        ]);
+        assert_eq!(1, drawing.raw_sections.len());
+        let section = &drawing.raw_sections[0];
+        assert_eq!("ACDSDATA", section.name);
+        assert_eq!("ENTITIES", section.preceding_section);
+        assert_eq!(
+            vec![
+                CodePair::new_i16(70, 2),
+                CodePair::new_str(0, "ACDSSCHEMA"),
+                CodePair::new_i32(90, 0),
+            ],
+            section.code_pairs
+        );
+    }
+
+    #[test]
+    fn write_raw_section_in_original_position() {
+        let mut drawing = Drawing::new();
+        drawing.raw_sections.push(RawSection {
+            name: String::from("APP_SECTION"),
+            code_pairs: vec![CodePair::new_str(1, "some value")],
+            preceding_section: String::from("ENTITIES"),
+        });
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(2, "ENTITIES"),
+                CodePair::new_str(0, "ENDSEC"),
+                CodePair::new_str(0, "SECTION"),
+                CodePair::new_str(2, "APP_SECTION"),
+                CodePair::new_str(1, "some value"),
+                CodePair::new_str(0, "ENDSEC"),
+                CodePair::new_str(0, "EOF"),
+            ],
+        );
+    }
+
+    #[test]
+    fn write_raw_section_before_header() {
+        let mut drawing = Drawing::new();
+        drawing.raw_sections.push(RawSection {
+            name: String::from("APP_SECTION"),
+            code_pairs: vec![],
+            preceding_section: String::new(),
+        });
+        let pairs = drawing.code_pairs().unwrap();
+        assert_eq!(
+            vec![
+                CodePair::new_str(0, "SECTION"),
+                CodePair::new_str(2, "APP_SECTION"),
+                CodePair::new_str(0, "ENDSEC"),
+                CodePair::new_str(0, "SECTION"),
+                CodePair::new_str(2, "HEADER"),
+            ],
+            pairs[..5]
+        );
+    }
+
+    #[test]
+    fn round_trip_modified_raw_section() {
+        let mut drawing = drawing_from_pairs(vec![
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "HEADER"),
+            CodePair::new_str(9, "$ACADVER"),
+            CodePair::new_str(1, "AC1015"),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "OBJECTS"),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "ACDSDATA"),
+            CodePair::new_i16(70, 2),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "EOF"),
+        ]);
+        drawing.raw_sections[0]
+            .code_pairs
+            .push(CodePair::new_i16(71, 6));
+        drawing
+            .raw_sections
+            .push(RawSection::new("OTHER_SECTION", vec![]));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+        assert_eq!(2, drawing.raw_sections.len());
+        assert_eq!("ACDSDATA", drawing.raw_sections[0].name);
+        assert_eq!("OBJECTS", drawing.raw_sections[0].preceding_section);
+        assert_eq!(
+            vec![CodePair::new_i16(70, 2), CodePair::new_i16(71, 6)],
+            drawing.raw_sections[0].code_pairs
+        );
+        assert_eq!("OTHER_SECTION", drawing.raw_sections[1].name);
+        assert_eq!("OBJECTS", drawing.raw_sections[1].preceding_section);
+    }
 }
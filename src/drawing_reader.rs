@@ -0,0 +1,490 @@
+use encoding_rs::Encoding;
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::entities::*;
+use crate::{Block, CodePair, CodePairValue, DxfError, DxfResult, Header};
+
+use crate::code_pair_iter::new_streaming_code_pair_iter;
+use crate::dxb_reader::DxbReader;
+use crate::entity_iter::{next_combined_entity, EntityIter};
+use crate::helper_functions::*;
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use itertools::put_back;
+
+/// An item produced while streaming a drawing with a `DrawingReader`.
+#[derive(Debug, Clone)]
+pub enum DrawingEvent {
+    /// The drawing's header.  This is produced before any blocks or entities.
+    Header(Box<Header>),
+    /// A block from the `BLOCKS` section, including its entities.
+    Block(Box<Block>),
+    /// An entity from the `ENTITIES` section.
+    Entity(Box<Entity>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Blocks,
+    Entities,
+}
+
+/// Reads a DXF file one item at a time without building a `Drawing`.
+///
+/// Only the `HEADER`, `BLOCKS`, and `ENTITIES` sections are interpreted; every other section is skipped as it's
+/// encountered, so memory use is bounded by the largest single item instead of the size of the file.  Entities are
+/// returned exactly as read, i.e., missing handles are not assigned.
+///
+/// ``` rust
+/// # fn ex() -> dxf::DxfResult<()> {
+/// use dxf::DrawingReader;
+/// use dxf::entities::*;
+///
+/// for entity in DrawingReader::open_file("path/to/file.dxf")?.entities() {
+///     if let EntityType::Line(ref line) = entity?.specific {
+///         // do something with the line
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct DrawingReader {
+    iter: CodePairPutBack,
+    section: Section,
+    pending_entity: Option<Entity>,
+    queued_events: VecDeque<DrawingEvent>,
+    entities_only: bool,
+    is_done: bool,
+}
+
+// public implementation
+impl DrawingReader {
+    /// Creates a `DrawingReader` from anything that implements the `Read` trait.
+    pub fn new<T>(reader: T) -> DxfResult<DrawingReader>
+    where
+        T: Read + 'static,
+    {
+        DrawingReader::new_with_encoding(reader, encoding_rs::WINDOWS_1252)
+    }
+    /// Creates a `DrawingReader` from anything that implements the `Read` trait using the specified text encoding.
+    ///
+    /// DXB files are loaded in full and their contents are then returned one at a time.
+    pub fn new_with_encoding<T>(reader: T, encoding: &'static Encoding) -> DxfResult<DrawingReader>
+    where
+        T: Read + 'static,
+    {
+        let mut reader = BufReader::new(reader);
+        let first_line = read_line(&mut reader, true, encoding)?;
+        match &*first_line {
+            "AutoCAD DXB 1.0" => {
+                let drawing = DxbReader::new(reader).load()?;
+                let mut queued_events = VecDeque::new();
+                queued_events.push_back(DrawingEvent::Header(Box::new(drawing.header.clone())));
+                for e in drawing.entities() {
+                    queued_events.push_back(DrawingEvent::Entity(Box::new(e.clone())));
+                }
+
+                // everything has already been read so the code pair stream is never consulted
+                let iter = new_streaming_code_pair_iter(std::io::empty(), encoding, first_line)?;
+                let mut reader =
+                    DrawingReader::from_code_pair_iter(CodePairPutBack::from_code_pair_iter(iter));
+                reader.queued_events = queued_events;
+                reader.is_done = true;
+                Ok(reader)
+            }
+            _ => {
+                let iter = new_streaming_code_pair_iter(reader, encoding, first_line)?;
+                Ok(DrawingReader::from_code_pair_iter(
+                    CodePairPutBack::from_code_pair_iter(iter),
+                ))
+            }
+        }
+    }
+    /// Creates a `DrawingReader` for the specified file.
+    pub fn open_file(path: impl AsRef<Path>) -> DxfResult<DrawingReader> {
+        DrawingReader::open_file_with_encoding(path, encoding_rs::WINDOWS_1252)
+    }
+    /// Creates a `DrawingReader` for the specified file using the specified text encoding.
+    pub fn open_file_with_encoding(
+        path: impl AsRef<Path>,
+        encoding: &'static Encoding,
+    ) -> DxfResult<DrawingReader> {
+        let file = File::open(&path)?;
+        DrawingReader::new_with_encoding(file, encoding)
+    }
+    /// Returns an iterator over only the entities in the `ENTITIES` section.  The `BLOCKS` section is skipped
+    /// without being interpreted.
+    pub fn entities(mut self) -> impl Iterator<Item = DxfResult<Entity>> {
+        self.entities_only = true;
+        self.filter_map(|event| match event {
+            Ok(DrawingEvent::Entity(e)) => Some(Ok(*e)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+}
+
+// internal visibility only
+impl DrawingReader {
+    pub(crate) fn from_code_pair_iter(iter: CodePairPutBack) -> DrawingReader {
+        DrawingReader {
+            iter,
+            section: Section::None,
+            pending_entity: None,
+            queued_events: VecDeque::new(),
+            entities_only: false,
+            is_done: false,
+        }
+    }
+    fn next_event(&mut self) -> DxfResult<Option<DrawingEvent>> {
+        loop {
+            match self.section {
+                Section::None => match self.iter.next() {
+                    Some(Ok(CodePair {
+                        code: 0,
+                        value: CodePairValue::Str(ref s),
+                        ..
+                    })) if s == "EOF" => return Ok(None),
+                    Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                        if pair.assert_string()? != "SECTION" {
+                            return Err(DxfError::UnexpectedCodePair(
+                                pair,
+                                String::from("expected 0/SECTION"),
+                            ));
+                        }
+
+                        let name = match self.iter.next() {
+                            Some(Ok(CodePair {
+                                code: 2,
+                                value: CodePairValue::Str(s),
+                                ..
+                            })) => s,
+                            Some(Ok(pair)) => {
+                                return Err(DxfError::UnexpectedCodePair(
+                                    pair,
+                                    String::from("expected 2/<section-name>"),
+                                ))
+                            }
+                            Some(Err(e)) => return Err(e),
+                            None => return Err(DxfError::UnexpectedEndOfInput),
+                        };
+                        match &*name {
+                            "HEADER" => {
+                                // always read so the text encoding can be updated
+                                let header = Header::read(&mut self.iter)?;
+                                self.expect_end_of_section()?;
+                                if !self.entities_only {
+                                    return Ok(Some(DrawingEvent::Header(Box::new(header))));
+                                }
+                            }
+                            "BLOCKS" if !self.entities_only => self.section = Section::Blocks,
+                            "ENTITIES" => self.section = Section::Entities,
+                            _ => self.skip_section()?,
+                        }
+                    }
+                    Some(Ok(pair)) => {
+                        return Err(DxfError::UnexpectedCodePair(
+                            pair,
+                            String::from("expected 0/SECTION or 0/EOF"),
+                        ))
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(None), // ideally should have been 0/EOF
+                },
+                Section::Blocks => match self.iter.next() {
+                    Some(Ok(pair @ CodePair { code: 0, .. })) => match &*pair.assert_string()? {
+                        "BLOCK" => {
+                            let block = Block::read(&mut self.iter)?;
+                            return Ok(Some(DrawingEvent::Block(Box::new(block))));
+                        }
+                        "ENDSEC" => self.section = Section::None,
+                        _ => return Err(DxfError::UnexpectedCodePair(pair, String::new())),
+                    },
+                    Some(Ok(pair)) => {
+                        return Err(DxfError::UnexpectedCodePair(pair, String::new()))
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(DxfError::UnexpectedEndOfInput),
+                },
+                Section::Entities => match self.next_entity()? {
+                    Some(e) => return Ok(Some(DrawingEvent::Entity(Box::new(e)))),
+                    None => {
+                        self.expect_end_of_section()?;
+                        self.section = Section::None;
+                    }
+                },
+            }
+        }
+    }
+    fn next_entity(&mut self) -> DxfResult<Option<Entity>> {
+        let mut entities = put_back(EntityIter::new(&mut self.iter));
+        if let Some(e) = self.pending_entity.take() {
+            entities.put_back(e);
+        }
+
+        let entity = next_combined_entity(&mut entities)?;
+        let (pending_entity, entities) = entities.into_parts();
+        if let Some(e) = entities.error {
+            return Err(e);
+        }
+
+        self.pending_entity = pending_entity;
+        Ok(entity)
+    }
+    fn expect_end_of_section(&mut self) -> DxfResult<()> {
+        match self.iter.next() {
+            Some(Ok(CodePair {
+                code: 0,
+                value: CodePairValue::Str(ref s),
+                ..
+            })) if s == "ENDSEC" => Ok(()),
+            Some(Ok(pair)) => Err(DxfError::UnexpectedCodePair(
+                pair,
+                String::from("expected 0/ENDSEC"),
+            )),
+            Some(Err(e)) => Err(e),
+            None => Err(DxfError::UnexpectedEndOfInput),
+        }
+    }
+    fn skip_section(&mut self) -> DxfResult<()> {
+        loop {
+            match self.iter.next() {
+                Some(Ok(pair)) => {
+                    if pair.code == 0 && pair.assert_string()? == "ENDSEC" {
+                        return Ok(());
+                    }
+                }
+                Some(Err(e)) => return Err(e),
+                None => return Err(DxfError::UnexpectedEndOfInput),
+            }
+        }
+    }
+}
+
+impl Iterator for DrawingReader {
+    type Item = DxfResult<DrawingEvent>;
+
+    fn next(&mut self) -> Option<DxfResult<DrawingEvent>> {
+        if let Some(event) = self.queued_events.pop_front() {
+            return Some(Ok(event));
+        }
+
+        if self.is_done {
+            return None;
+        }
+
+        match self.next_event() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.is_done = true;
+                None
+            }
+            Err(e) => {
+                // the position in the stream is unknown after an error so stop reading
+                self.is_done = true;
+                Some(Err(e.with_context(self.iter.error_context())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::helper_functions::tests::*;
+    use crate::*;
+    use std::io::Cursor;
+
+    fn reader_from_lines(lines: Vec<&str>) -> DrawingReader {
+        let contents = lines.join("\r\n");
+        DrawingReader::new(Cursor::new(contents.into_bytes())).unwrap()
+    }
+
+    fn reader_from_drawing(drawing: &Drawing) -> DrawingReader {
+        let contents = to_test_string(drawing);
+        DrawingReader::new(Cursor::new(contents.into_bytes())).unwrap()
+    }
+
+    #[test]
+    fn stream_entities_only() {
+        let mut reader = reader_from_lines(vec![
+            "0",
+            "SECTION",
+            "2",
+            "HEADER",
+            "9",
+            "$ACADVER",
+            "1",
+            "AC1015",
+            "0",
+            "ENDSEC",
+            "0",
+            "SECTION",
+            "2",
+            "TABLES",
+            "0",
+            "TABLE",
+            "2",
+            "LAYER",
+            "0",
+            "LAYER",
+            "2",
+            "some-layer",
+            "0",
+            "ENDTAB",
+            "0",
+            "ENDSEC",
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "LINE",
+            "10",
+            "1.0",
+            "0",
+            "POLYLINE",
+            "66",
+            "1",
+            "0",
+            "VERTEX",
+            "10",
+            "2.0",
+            "0",
+            "VERTEX",
+            "10",
+            "3.0",
+            "0",
+            "SEQEND",
+            "0",
+            "CIRCLE",
+            "40",
+            "4.0",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ])
+        .entities();
+        match reader.next() {
+            Some(Ok(Entity {
+                specific: EntityType::Line(ref line),
+                ..
+            })) => assert_eq!(Point::new(1.0, 0.0, 0.0), line.p1),
+            e => panic!("expected a line, got {:?}", e),
+        }
+        match reader.next() {
+            Some(Ok(Entity {
+                specific: EntityType::Polyline(ref poly),
+                ..
+            })) => {
+                let vertices = poly.vertices().collect::<Vec<_>>();
+                assert_eq!(2, vertices.len());
+                assert_eq!(Point::new(2.0, 0.0, 0.0), vertices[0].location);
+                assert_eq!(Point::new(3.0, 0.0, 0.0), vertices[1].location);
+            }
+            e => panic!("expected a polyline, got {:?}", e),
+        }
+        match reader.next() {
+            Some(Ok(Entity {
+                specific: EntityType::Circle(ref circle),
+                ..
+            })) => assert_eq!(4.0, circle.radius),
+            e => panic!("expected a circle, got {:?}", e),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn stream_header_blocks_and_entities_as_events() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        let mut block = Block {
+            name: String::from("some-block"),
+            ..Default::default()
+        };
+        block
+            .entities
+            .push(Entity::new(EntityType::Line(Default::default())));
+        drawing.add_block(block);
+        drawing.add_entity(Entity::new(EntityType::Circle(Default::default())));
+
+        let events = reader_from_drawing(&drawing)
+            .collect::<DxfResult<Vec<_>>>()
+            .unwrap();
+        match events[0] {
+            DrawingEvent::Header(ref header) => assert_eq!(AcadVersion::R2000, header.version),
+            ref e => panic!("expected a header, got {:?}", e),
+        }
+        let blocks = events
+            .iter()
+            .filter_map(|e| match e {
+                DrawingEvent::Block(b) => Some(b),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let block = blocks.iter().find(|b| b.name == "some-block").unwrap();
+        assert_eq!(1, block.entities.len());
+        match events.last() {
+            Some(DrawingEvent::Entity(ref e)) => match e.specific {
+                EntityType::Circle(_) => (),
+                _ => panic!("expected a circle, got {:?}", e),
+            },
+            e => panic!("expected an entity, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn stream_entity_read_error_is_reported() {
+        let mut reader = reader_from_lines(vec![
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "LINE",
+            "10",
+            "not-a-number",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ])
+        .entities();
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn stream_entities_skips_unsupported_sections() {
+        let mut reader = reader_from_lines(vec![
+            "0",
+            "SECTION",
+            "2",
+            "ACDSDATA",
+            "0",
+            "ACDSSCHEMA",
+            "0",
+            "ENDSEC",
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "POINT",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ])
+        .entities();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::code_pair_writer::CodePairWriter;
+use crate::entities::*;
+use crate::enums::*;
+use crate::{CodePair, Drawing, DxfError, DxfResult, Handle};
+
+use std::io::Write;
+
+/// Writes a DXF file incrementally so that entities don't all have to be held in memory at once.
+///
+/// Everything that precedes the `ENTITIES` section is written when the `DrawingWriter` is created, entities are
+/// written one at a time by `write_entity()`, and the remaining sections are written by `finish()`.  Since the tables
+/// have already been written by the time an entity is, any layers, line types, etc. that the entities reference must
+/// already be present in the `Drawing`.
+///
+/// The header's `$HANDSEED` value is also written up front, so it reserves room for the handles of the entities that
+/// will follow.
+///
+/// ``` rust
+/// # fn ex() -> dxf::DxfResult<()> {
+/// use dxf::{Drawing, DrawingWriter};
+/// use dxf::entities::*;
+///
+/// let file = std::fs::File::create("path/to/file.dxf")?;
+/// let mut writer = DrawingWriter::new(std::io::BufWriter::new(file), Drawing::new(), 1_000_000)?;
+/// for i in 0..1_000 {
+///     let line = Line::new(dxf::Point::new(i as f64, 0.0, 0.0), dxf::Point::new(i as f64, 1.0, 0.0));
+///     writer.write_entity(Entity::new(EntityType::Line(line)))?;
+/// }
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DrawingWriter<T: Write> {
+    writer: T,
+    drawing: Drawing,
+    as_ascii: bool,
+    write_handles: bool,
+    handle_seed: Handle,
+    pairs: Vec<CodePair>,
+}
+
+// public implementation
+impl<T: Write> DrawingWriter<T> {
+    /// Creates a `DrawingWriter` that writes ASCII DXF and immediately writes all sections of `drawing` that precede
+    /// the entities, followed by any entities already contained in `drawing`.  The written `$HANDSEED` leaves room for
+    /// `reserved_handles` more handles.
+    pub fn new(writer: T, drawing: Drawing, reserved_handles: u64) -> DxfResult<Self> {
+        DrawingWriter::new_internal(writer, drawing, reserved_handles, true)
+    }
+    /// Creates a `DrawingWriter` that writes binary DXF.  See `new()` for details.
+    pub fn new_binary(writer: T, drawing: Drawing, reserved_handles: u64) -> DxfResult<Self> {
+        DrawingWriter::new_internal(writer, drawing, reserved_handles, false)
+    }
+    /// Writes the specified entity, assigning its handles first.  Returns the handle of the entity.
+    pub fn write_entity(&mut self, mut entity: Entity) -> DxfResult<Handle> {
+        if self.write_handles {
+            let needed = Drawing::entity_handle_count(&entity);
+            if self.drawing.header.next_available_handle.0 + needed > self.handle_seed.0 {
+                return Err(DxfError::ReservedHandlesExhausted);
+            }
+
+            self.drawing.set_entity_handles(&mut entity);
+        }
+
+        entity.add_code_pairs(
+            &mut self.pairs,
+            self.drawing.header.version,
+            self.write_handles,
+        );
+        self.flush_pairs()?;
+        Ok(entity.common.handle)
+    }
+    /// Ends the `ENTITIES` section and writes the remaining sections of the drawing.  Returns the underlying writer.
+    pub fn finish(mut self) -> DxfResult<T> {
+        self.pairs.push(CodePair::new_str(0, "ENDSEC"));
+        self.drawing.add_pairs_after_entities(&mut self.pairs)?;
+        self.flush_pairs()?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// private implementation
+impl<T: Write> DrawingWriter<T> {
+    fn new_internal(
+        writer: T,
+        drawing: Drawing,
+        reserved_handles: u64,
+        as_ascii: bool,
+    ) -> DxfResult<Self> {
+        let write_handles = drawing.write_handles();
+        let next_handle = drawing.header.next_available_handle;
+        let handle_seed = Handle(next_handle.0 + reserved_handles);
+        let mut drawing_writer = DrawingWriter {
+            writer,
+            drawing,
+            as_ascii,
+            write_handles,
+            handle_seed,
+            pairs: vec![],
+        };
+
+        // the header is written before any entities, so it reports the seed that includes the reserved handles
+        drawing_writer.drawing.header.next_available_handle = handle_seed;
+        drawing_writer.write_prelude()?;
+        drawing_writer
+            .drawing
+            .add_pairs_before_entities(&mut drawing_writer.pairs, write_handles);
+        drawing_writer.drawing.header.next_available_handle = next_handle;
+
+        drawing_writer.pairs.push(CodePair::new_str(0, "SECTION"));
+        drawing_writer.pairs.push(CodePair::new_str(2, "ENTITIES"));
+        for e in drawing_writer.drawing.entities() {
+            e.add_code_pairs(
+                &mut drawing_writer.pairs,
+                drawing_writer.drawing.header.version,
+                write_handles,
+            );
+        }
+
+        drawing_writer.flush_pairs()?;
+        Ok(drawing_writer)
+    }
+    fn code_pair_writer(&mut self) -> CodePairWriter<'_, T> {
+        let text_as_ascii = self.drawing.header.version <= AcadVersion::R2004;
+        CodePairWriter::new(
+            &mut self.writer,
+            self.as_ascii,
+            text_as_ascii,
+            self.drawing.header.version,
+        )
+    }
+    fn write_prelude(&mut self) -> DxfResult<()> {
+        self.code_pair_writer().write_prelude()
+    }
+    fn flush_pairs(&mut self) -> DxfResult<()> {
+        let pairs = std::mem::take(&mut self.pairs);
+        let mut code_pair_writer = self.code_pair_writer();
+        for pair in &pairs {
+            code_pair_writer.write_code_pair(pair)?;
+        }
+
+        // reuse the allocation for the next batch
+        self.pairs = pairs;
+        self.pairs.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::enums::*;
+    use crate::*;
+    use std::io::Cursor;
+
+    fn r2000_drawing() -> Drawing {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        drawing
+    }
+
+    fn line(x: f64) -> Entity {
+        Entity::new(EntityType::Line(Line::new(
+            Point::new(x, 0.0, 0.0),
+            Point::new(x, 1.0, 0.0),
+        )))
+    }
+
+    #[test]
+    fn write_matches_save_without_streamed_entities() {
+        let mut drawing = r2000_drawing();
+        drawing.add_entity(line(1.0));
+
+        let mut expected = vec![];
+        drawing.save(&mut expected).unwrap();
+
+        let writer = DrawingWriter::new(vec![], drawing, 0).unwrap();
+        let actual = writer.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(expected).unwrap(),
+            String::from_utf8(actual).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_streamed_entities_with_handles() {
+        let mut writer = DrawingWriter::new(vec![], r2000_drawing(), 10).unwrap();
+        let first = writer.write_entity(line(1.0)).unwrap();
+        let second = writer.write_entity(line(2.0)).unwrap();
+        assert!(!first.is_empty());
+        assert_ne!(first, second);
+        let bytes = writer.finish().unwrap();
+
+        let drawing = Drawing::load(&mut Cursor::new(bytes)).unwrap();
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(2, entities.len());
+        assert_eq!(first, entities[0].common.handle);
+        assert_eq!(second, entities[1].common.handle);
+        assert!(drawing.header.next_available_handle.0 > second.0);
+    }
+
+    #[test]
+    fn write_streamed_entities_as_binary() {
+        let mut writer = DrawingWriter::new_binary(vec![], r2000_drawing(), 10).unwrap();
+        writer.write_entity(line(1.0)).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let drawing = Drawing::load(&mut Cursor::new(bytes)).unwrap();
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Line(ref line) => assert_eq!(Point::new(1.0, 0.0, 0.0), line.p1),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn write_entity_fails_when_reserved_handles_are_exhausted() {
+        let mut writer = DrawingWriter::new(vec![], r2000_drawing(), 1).unwrap();
+        writer.write_entity(line(1.0)).unwrap();
+        match writer.write_entity(line(2.0)) {
+            Err(DxfError::ReservedHandlesExhausted) => (),
+            r => panic!("expected the reserved handles to be exhausted, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn failed_write_entity_does_not_take_handles() {
+        // a polyline needs a handle for itself and one for its SEQEND
+        let mut writer = DrawingWriter::new(vec![], r2000_drawing(), 1).unwrap();
+        let polyline = Entity::new(EntityType::Polyline(Polyline::default()));
+        match writer.write_entity(polyline) {
+            Err(DxfError::ReservedHandlesExhausted) => (),
+            r => panic!("expected the reserved handles to be exhausted, got {:?}", r),
+        }
+        writer.write_entity(line(1.0)).unwrap();
+    }
+}
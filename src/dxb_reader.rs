@@ -298,7 +298,7 @@ impl<T: Read> DxbReader<T> {
     fn read_f(&mut self) -> DxfResult<f64> {
         let value = read_f64(&mut self.reader);
         self.advance_offset(8);
-        Ok(value.or::<f64>(Ok(0.0)).unwrap())
+        Ok(value.unwrap_or(0.0))
     }
     fn read_n(&mut self) -> DxfResult<f64> {
         if self.is_integer_mode {
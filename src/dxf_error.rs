@@ -3,7 +3,7 @@ use std::fmt;
 use std::io;
 use std::num;
 
-use crate::CodePair;
+use crate::{CodePair, Handle};
 
 #[derive(Debug)]
 pub enum DxfError {
@@ -23,6 +23,90 @@ pub enum DxfError {
     InvalidBinaryFile,
     MalformedString,
     WrongItemType,
+    ReservedHandlesExhausted,
+    /// The spline's degree, knots, control points, or weights are inconsistent, or it can't be constructed.
+    InvalidSpline(String),
+    /// Another error with information about where in the file it happened.
+    WithContext(Box<DxfError>, Box<ErrorContext>),
+}
+
+/// Whether a position is a line number (ASCII files) or a byte offset (binary files).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilePosition {
+    Line(usize),
+    Byte(usize),
+}
+
+impl fmt::Display for FilePosition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FilePosition::Line(l) => write!(formatter, "line {}", l),
+            FilePosition::Byte(b) => write!(formatter, "byte {}", b),
+        }
+    }
+}
+
+/// Describes where an error happened.  Only the values that were known are set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorContext {
+    /// The section being read, e.g., `ENTITIES`.
+    pub section: Option<String>,
+    /// The type string of the item being read, e.g., `LINE`, or the name of the header variable.
+    pub item_type: Option<String>,
+    /// The handle of the item being read.
+    pub handle: Option<Handle>,
+    /// The group code of the offending code pair.
+    pub code: Option<i32>,
+    /// The offending value as it appeared in the file.
+    pub raw_value: Option<String>,
+    /// Where the offending code pair or item starts.
+    pub position: Option<FilePosition>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(ref section) = self.section {
+            parts.push(format!("section {}", section));
+        }
+        if let Some(ref item_type) = self.item_type {
+            parts.push(format!("item {}", item_type));
+        }
+        if let Some(handle) = self.handle {
+            parts.push(format!("handle {}", handle.as_string()));
+        }
+        if let Some(code) = self.code {
+            parts.push(format!("code {}", code));
+        }
+        if let Some(ref raw_value) = self.raw_value {
+            parts.push(format!("value '{}'", raw_value));
+        }
+        if let Some(position) = self.position {
+            parts.push(position.to_string());
+        }
+        write!(formatter, "{}", parts.join(", "))
+    }
+}
+
+impl DxfError {
+    /// Returns the outermost context attached to this error, if any.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match *self {
+            DxfError::WithContext(_, ref context) => Some(context),
+            _ => None,
+        }
+    }
+    /// Returns the underlying error without any attached context.
+    pub fn root_cause(&self) -> &DxfError {
+        match *self {
+            DxfError::WithContext(ref error, _) => error.root_cause(),
+            _ => self,
+        }
+    }
+    pub(crate) fn with_context(self, context: ErrorContext) -> DxfError {
+        DxfError::WithContext(Box::new(self), Box::new(context))
+    }
 }
 
 impl From<io::Error> for DxfError {
@@ -89,17 +173,26 @@ impl fmt::Display for DxfError {
             DxfError::InvalidBinaryFile => write!(formatter, "the binary file is invalid"),
             DxfError::MalformedString => write!(formatter, "the string is malformed"),
             DxfError::WrongItemType => write!(formatter, "the specified item type is not correct"),
+            DxfError::ReservedHandlesExhausted => write!(
+                formatter,
+                "more handles were needed than were reserved when the header was written"
+            ),
+            DxfError::InvalidSpline(ref s) => write!(formatter, "the spline is invalid: {}", s),
+            DxfError::WithContext(ref error, ref context) => {
+                write!(formatter, "{} ({})", error, context)
+            }
         }
     }
 }
 
 impl error::Error for DxfError {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             DxfError::IoError(ref e) => Some(e),
             DxfError::ImageError(ref e) => Some(e),
             DxfError::ParseFloatError(ref e, _) => Some(e),
             DxfError::ParseIntError(ref e, _) => Some(e),
+            DxfError::WithContext(ref e, _) => Some(e.as_ref()),
             _ => None,
         }
     }
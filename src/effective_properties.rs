@@ -0,0 +1,331 @@
+// other implementation is in `drawing.rs`
+
+use crate::entities::*;
+use crate::tables::Layer;
+use crate::{Color, Drawing, LineWeight, Transparency};
+
+/// The line type name that defers to the item's layer's line type.
+const BY_LAYER_LINE_TYPE: &str = "BYLAYER";
+
+/// The line type name that defers to the containing block's line type.
+pub(crate) const BY_BLOCK_LINE_TYPE: &str = "BYBLOCK";
+
+/// The line type that BYBLOCK entities are drawn with outside of a block.
+const CONTINUOUS_LINE_TYPE: &str = "CONTINUOUS";
+
+/// The name of the layer whose entities take on the layer of the `Insert` they are drawn through.
+pub(crate) const LAYER_ZERO: &str = "0";
+
+//------------------------------------------------------------------------------
+//                                                           EffectiveProperties
+//------------------------------------------------------------------------------
+/// The display properties of an entity after BYLAYER and BYBLOCK values have been resolved.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectiveProperties {
+    /// The layer the entity is drawn on.  Entities on layer `0` inside of a block are drawn on the layer of the
+    /// `Insert`.
+    pub layer: String,
+    pub color: Color,
+    pub line_type_name: String,
+    /// The entity's line type scale, multiplied by that of the `Insert` when the line type is BYBLOCK.  This is relative
+    /// to the drawing; the global `Header::line_type_scale` (`$LTSCALE`) is not included and must be applied by the
+    /// caller.
+    pub line_type_scale: f64,
+    pub line_weight: LineWeight,
+    pub transparency: Transparency,
+    /// Whether the entity is plotted, as determined by its layer.
+    pub is_plotted: bool,
+    /// Whether the entity is displayed.  This is `false` if the entity is invisible, its layer is off or frozen, or any
+    /// `Insert` it is drawn through is invisible or on a frozen layer.
+    pub is_visible: bool,
+}
+
+//------------------------------------------------------------------------------
+//                                                                 InsertContext
+//------------------------------------------------------------------------------
+/// Describes the chain of `Insert` entities that an entity in a block is drawn through, which is needed to resolve
+/// BYBLOCK properties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsertContext {
+    insert_properties: Option<EffectiveProperties>,
+    is_content_shown: bool,
+}
+
+impl Default for InsertContext {
+    fn default() -> Self {
+        InsertContext {
+            insert_properties: None,
+            is_content_shown: true,
+        }
+    }
+}
+
+impl InsertContext {
+    /// Creates the context of entities that are drawn directly in model or paper space.
+    pub fn top_level() -> Self {
+        InsertContext::default()
+    }
+    /// Creates the context of the entities in the block referenced by `insert`, where `insert` is drawn in this
+    /// context.
+    pub fn nested(&self, drawing: &Drawing, insert: &Entity) -> Self {
+        let insert_properties = EffectiveProperties::resolve(drawing, insert, self);
+
+        // an invisible insert or one on a frozen layer hides everything in its block, but an insert on a layer that's
+        // only turned off still shows the contents that aren't on layer `0`
+        let is_content_shown = self.is_content_shown
+            && insert.common.is_visible
+            && !find_layer(drawing, &insert_properties.layer)
+                .map(|layer| layer.is_frozen())
+                .unwrap_or(false);
+        InsertContext {
+            insert_properties: Some(insert_properties),
+            is_content_shown,
+        }
+    }
+    /// Gets the resolved properties of the innermost `Insert`, or `None` for top-level entities.
+    pub fn insert_properties(&self) -> Option<&EffectiveProperties> {
+        self.insert_properties.as_ref()
+    }
+}
+
+impl EffectiveProperties {
+    pub(crate) fn resolve(drawing: &Drawing, entity: &Entity, context: &InsertContext) -> Self {
+        let common = &entity.common;
+        let parent = context.insert_properties();
+        let layer_name = match parent {
+            Some(parent) if common.layer == LAYER_ZERO => parent.layer.clone(),
+            _ => common.layer.clone(),
+        };
+        let default_layer = Layer::default();
+        let layer = find_layer(drawing, &layer_name).unwrap_or(&default_layer);
+
+        let color = if common.color.is_by_layer() {
+            layer.color.clone()
+        } else if common.color.is_by_block() {
+            parent
+                .map(|p| p.color.clone())
+                .unwrap_or_else(|| Color::from_index(7))
+        } else {
+            common.color.clone()
+        };
+
+        // a BYBLOCK line type is also scaled by the `Insert`
+        let (line_type_name, line_type_scale) = if common
+            .line_type_name
+            .eq_ignore_ascii_case(BY_LAYER_LINE_TYPE)
+        {
+            (layer.line_type_name.clone(), common.line_type_scale)
+        } else if common
+            .line_type_name
+            .eq_ignore_ascii_case(BY_BLOCK_LINE_TYPE)
+        {
+            match parent {
+                Some(p) => (
+                    p.line_type_name.clone(),
+                    common.line_type_scale * p.line_type_scale,
+                ),
+                None => (String::from(CONTINUOUS_LINE_TYPE), common.line_type_scale),
+            }
+        } else {
+            (common.line_type_name.clone(), common.line_type_scale)
+        };
+
+        let line_weight = LineWeight::from_raw_value(common.lineweight_enum_value);
+        let line_weight = if line_weight.is_by_layer() {
+            layer.line_weight.clone()
+        } else if line_weight.is_by_block() {
+            parent
+                .map(|p| p.line_weight.clone())
+                .unwrap_or_else(LineWeight::standard)
+        } else {
+            line_weight
+        };
+
+        let transparency = if common.transparency.is_by_layer() {
+            layer.transparency()
+        } else if common.transparency.is_by_block() {
+            parent
+                .map(|p| p.transparency.clone())
+                .unwrap_or_else(Transparency::opaque)
+        } else {
+            common.transparency.clone()
+        };
+
+        EffectiveProperties {
+            color,
+            line_type_name,
+            line_type_scale,
+            line_weight,
+            transparency,
+            is_plotted: layer.is_layer_plotted,
+            is_visible: context.is_content_shown
+                && common.is_visible
+                && layer.is_layer_on
+                && !layer.is_frozen(),
+            layer: layer_name,
+        }
+    }
+}
+
+fn find_layer<'a>(drawing: &'a Drawing, name: &str) -> Option<&'a Layer> {
+    drawing
+        .layers()
+        .find(|layer| layer.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::tables::*;
+    use crate::*;
+
+    fn drawing_with_layers() -> Drawing {
+        let mut drawing = Drawing::new();
+        let mut red = Layer {
+            name: String::from("red"),
+            color: Color::from_index(1),
+            line_type_name: String::from("DASHED"),
+            line_weight: LineWeight::from_raw_value(50),
+            ..Default::default()
+        };
+        red.set_transparency(Transparency::from_alpha(0x40));
+        drawing.add_layer(red);
+        drawing.add_layer(Layer {
+            name: String::from("blue"),
+            color: Color::from_index(5),
+            ..Default::default()
+        });
+        drawing
+    }
+
+    fn entity_on(layer: &str) -> Entity {
+        let mut entity = Entity::new(EntityType::Line(Default::default()));
+        entity.common.layer = String::from(layer);
+        entity.common.lineweight_enum_value = LineWeight::by_layer().raw_value();
+        entity
+    }
+
+    #[test]
+    fn by_layer_values_come_from_the_layer() {
+        let drawing = drawing_with_layers();
+        let entity = entity_on("RED");
+        let props = drawing.effective_properties(&entity, &InsertContext::top_level());
+        assert_eq!(Some(1), props.color.index());
+        assert_eq!("DASHED", props.line_type_name);
+        assert_eq!(50, props.line_weight.raw_value());
+        assert_eq!(Some(0x40), props.transparency.alpha());
+        assert!(props.is_visible);
+        assert!(props.is_plotted);
+    }
+
+    #[test]
+    fn explicit_values_are_kept() {
+        let drawing = drawing_with_layers();
+        let mut entity = entity_on("red");
+        entity.common.color = Color::from_rgb(1, 2, 3);
+        entity.common.line_type_name = String::from("HIDDEN");
+        entity.common.transparency = Transparency::from_alpha(0x80);
+        let props = drawing.effective_properties(&entity, &InsertContext::top_level());
+        assert_eq!(Some((1, 2, 3)), props.color.true_color());
+        assert_eq!("HIDDEN", props.line_type_name);
+        assert_eq!(Some(0x80), props.transparency.alpha());
+    }
+
+    #[test]
+    fn by_block_values_come_from_nested_inserts() {
+        let drawing = drawing_with_layers();
+
+        // outer insert on "red" with BYLAYER color, inner insert on "blue" with BYBLOCK color
+        let outer = entity_on("red");
+        let mut inner = entity_on("blue");
+        inner.common.color = Color::by_block();
+        inner.common.line_type_name = String::from("BYBLOCK");
+        inner.common.line_type_scale = 2.0;
+
+        let mut entity = entity_on("blue");
+        entity.common.color = Color::by_block();
+        entity.common.line_type_name = String::from("ByBlock");
+        entity.common.line_type_scale = 3.0;
+        entity.common.lineweight_enum_value = LineWeight::by_block().raw_value();
+
+        let context = InsertContext::top_level()
+            .nested(&drawing, &outer)
+            .nested(&drawing, &inner);
+        let props = drawing.effective_properties(&entity, &context);
+        assert_eq!(Some(1), props.color.index());
+        assert_eq!("DASHED", props.line_type_name);
+        assert_eq!(6.0, props.line_type_scale);
+        assert_eq!("blue", props.layer);
+    }
+
+    #[test]
+    fn by_block_at_top_level() {
+        let drawing = drawing_with_layers();
+        let mut entity = entity_on("red");
+        entity.common.color = Color::by_block();
+        entity.common.line_type_name = String::from("BYBLOCK");
+        entity.common.lineweight_enum_value = LineWeight::by_block().raw_value();
+        let props = drawing.effective_properties(&entity, &InsertContext::top_level());
+        assert_eq!(Some(7), props.color.index());
+        assert_eq!("CONTINUOUS", props.line_type_name);
+        assert!(props.line_weight.is_standard());
+    }
+
+    #[test]
+    fn layer_zero_takes_the_insert_layer() {
+        let drawing = drawing_with_layers();
+        let insert = entity_on("red");
+        let entity = entity_on("0");
+        let context = InsertContext::top_level().nested(&drawing, &insert);
+        let props = drawing.effective_properties(&entity, &context);
+        assert_eq!("red", props.layer);
+        assert_eq!(Some(1), props.color.index());
+    }
+
+    #[test]
+    fn visibility_through_layers_and_inserts() {
+        let mut drawing = drawing_with_layers();
+        for layer in drawing.layers_mut() {
+            if layer.name == "red" {
+                layer.is_layer_on = false;
+            }
+            if layer.name == "blue" {
+                layer.set_is_frozen(true);
+            }
+        }
+
+        let top_level = InsertContext::top_level();
+        assert!(
+            !drawing
+                .effective_properties(&entity_on("red"), &top_level)
+                .is_visible
+        );
+        assert!(
+            !drawing
+                .effective_properties(&entity_on("blue"), &top_level)
+                .is_visible
+        );
+
+        // an insert on a layer that's off only hides the contents on layer 0
+        let context = top_level.nested(&drawing, &entity_on("red"));
+        assert!(
+            !drawing
+                .effective_properties(&entity_on("0"), &context)
+                .is_visible
+        );
+        assert!(
+            drawing
+                .effective_properties(&entity_on("other"), &context)
+                .is_visible
+        );
+
+        // an insert on a frozen layer hides everything
+        let context = top_level.nested(&drawing, &entity_on("blue"));
+        assert!(
+            !drawing
+                .effective_properties(&entity_on("other"), &context)
+                .is_visible
+        );
+    }
+}
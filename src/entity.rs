@@ -2,13 +2,13 @@
 
 use enum_primitive::FromPrimitive;
 
-use crate::{CodePair, Color, DxfError, DxfResult, Handle, Point, Vector};
+use crate::{CodePair, DxfError, DxfResult, Handle, Point, Transparency, Vector};
 
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::entities::*;
 use crate::enums::*;
 use crate::helper_functions::*;
-use crate::Drawing;
+use crate::{Drawing, Header};
 
 //------------------------------------------------------------------------------
 //                                                                           Arc
@@ -42,11 +42,16 @@ impl Circle {
 //                                                                 DimensionBase
 //------------------------------------------------------------------------------
 impl DimensionBase {
-    fn set_dimension_type(&mut self, val: i16) -> DxfResult<()> {
+    fn set_dimension_type(&mut self, pair: &CodePair) -> DxfResult<()> {
+        let val = pair.assert_i16()?;
+        // only take the lower 4 bits
+        self.dimension_type = match DimensionType::from_i16(val & 0x0F) {
+            Some(dimension_type) => dimension_type,
+            None => return Err(DxfError::UnexpectedEnumValue(pair.offset)),
+        };
         self.is_block_reference_referenced_by_this_block_only = (val & 32) == 32;
         self.is_ordinate_x_type = (val & 64) == 64;
         self.is_at_user_defined_location = (val & 128) == 128;
-        self.dimension_type = enum_from_number!(DimensionType, Aligned, from_i16, val & 0x0F); // only take the lower 4 bits
         Ok(())
     }
     pub(crate) fn dimension_type(&self) -> i16 {
@@ -147,7 +152,7 @@ impl MLeader {
             };
             return vec![dogleg_vertice]
                 .into_iter()
-                .chain(self.vertices.clone().into_iter()) // <-- Use self.vertices directly, not self.vertices()
+                .chain(self.vertices.clone()) // <-- Use self.vertices directly, not self.vertices()
                 .collect();
         }
         self.vertices.clone()
@@ -155,10 +160,7 @@ impl MLeader {
 
     fn calculate_dogleg_vertice(&self) -> Option<Point> {
         // Calculate from the first coordinate of the vertices
-        let first_vertex = match self.vertices.first() {
-            Some(first_vertex) => first_vertex,
-            None => return None, // No vertices available
-        };
+        let first_vertex = self.vertices.first()?;
         // Calculate the new vertex with cos and sin of the 3D coordinate
         Some(Point {
             x: first_vertex.x + self.dogleg_vector.x * self.dogleg_length,
@@ -257,6 +259,28 @@ impl Trace {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                 UnknownEntity
+//------------------------------------------------------------------------------
+/// Represents an entity type that isn't natively supported.  Everything other than the common entity
+/// data is kept verbatim in `code_pairs`, starting with the first unrecognized code or the first
+/// type-specific subclass marker, so the entity can be written back unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownEntity {
+    pub type_string: String,
+    pub code_pairs: Vec<CodePair>,
+}
+
+impl UnknownEntity {
+    pub fn new(type_string: &str, code_pairs: Vec<CodePair>) -> Self {
+        UnknownEntity {
+            type_string: String::from(type_string),
+            code_pairs,
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                        Vertex
 //------------------------------------------------------------------------------
@@ -269,6 +293,47 @@ impl Vertex {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                      Viewport
+//------------------------------------------------------------------------------
+impl Viewport {
+    pub fn new(center: Point, width: f64, height: f64) -> Self {
+        Viewport {
+            center,
+            width,
+            height,
+            view_height: height,
+            ..Default::default()
+        }
+    }
+    /// Returns the number of paper space units that represent one model space unit, e.g., `0.5` for a 1:2 view.  A
+    /// viewport with a zero height or view height has no meaningful scale and returns `1.0`.
+    pub fn scale(&self) -> f64 {
+        if self.height == 0.0 || self.view_height == 0.0 {
+            1.0
+        } else {
+            self.height / self.view_height
+        }
+    }
+    /// Zooms the model space view so that one model space unit is displayed as `scale` paper space units.  The view
+    /// center is preserved.  A zero `scale` is ignored.
+    pub fn set_scale(&mut self, scale: f64) {
+        if scale != 0.0 {
+            self.view_height = self.height / scale;
+        }
+    }
+    /// Returns the line type scale for model space entities displayed through this viewport.  When
+    /// `$PSLTSCALE` is set, the global line type scale is divided by the viewport scale so dashes have the same
+    /// size on paper in every viewport.
+    pub fn line_type_scale(&self, header: &Header) -> f64 {
+        if header.scale_line_types_in_paperspace {
+            header.line_type_scale / self.scale()
+        } else {
+            header.line_type_scale
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                    EntityType
 //------------------------------------------------------------------------------
@@ -411,6 +476,103 @@ impl EntityType {
                     return Ok(false);
                 }
             },
+            EntityType::ArcDimension(ref mut dim) => match pair.code {
+                13 => {
+                    dim.definition_point_2.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.definition_point_2.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.definition_point_2.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.definition_point_3.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.definition_point_3.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.definition_point_3.z = pair.assert_f64()?;
+                }
+                15 => {
+                    dim.arc_center.x = pair.assert_f64()?;
+                }
+                25 => {
+                    dim.arc_center.y = pair.assert_f64()?;
+                }
+                35 => {
+                    dim.arc_center.z = pair.assert_f64()?;
+                }
+                40 => {
+                    dim.arc_start_angle = pair.assert_f64()?;
+                }
+                41 => {
+                    dim.arc_end_angle = pair.assert_f64()?;
+                }
+                70 => {
+                    dim.is_partial = as_bool(pair.assert_i16()?);
+                }
+                71 => {
+                    dim.has_leader = as_bool(pair.assert_i16()?);
+                }
+                16 => {
+                    dim.leader_point_1.x = pair.assert_f64()?;
+                }
+                26 => {
+                    dim.leader_point_1.y = pair.assert_f64()?;
+                }
+                36 => {
+                    dim.leader_point_1.z = pair.assert_f64()?;
+                }
+                17 => {
+                    dim.leader_point_2.x = pair.assert_f64()?;
+                }
+                27 => {
+                    dim.leader_point_2.y = pair.assert_f64()?;
+                }
+                37 => {
+                    dim.leader_point_2.z = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
+            EntityType::JoggedRadialDimension(ref mut dim) => match pair.code {
+                13 => {
+                    dim.definition_point_2.x = pair.assert_f64()?;
+                }
+                23 => {
+                    dim.definition_point_2.y = pair.assert_f64()?;
+                }
+                33 => {
+                    dim.definition_point_2.z = pair.assert_f64()?;
+                }
+                14 => {
+                    dim.override_center.x = pair.assert_f64()?;
+                }
+                24 => {
+                    dim.override_center.y = pair.assert_f64()?;
+                }
+                34 => {
+                    dim.override_center.z = pair.assert_f64()?;
+                }
+                15 => {
+                    dim.jog_point.x = pair.assert_f64()?;
+                }
+                25 => {
+                    dim.jog_point.y = pair.assert_f64()?;
+                }
+                35 => {
+                    dim.jog_point.z = pair.assert_f64()?;
+                }
+                40 => {
+                    dim.jog_angle = pair.assert_f64()?;
+                }
+                _ => {
+                    return Ok(false);
+                }
+            },
             _ => {
                 return Err(DxfError::UnexpectedEnumValue(pair.offset));
             }
@@ -457,7 +619,7 @@ impl Entity {
                     }
 
                     match &*type_string {
-                        "DIMENSION" => {
+                        "DIMENSION" | "ARC_DIMENSION" | "LARGE_RADIAL_DIMENSION" => {
                             // dimensions require special handling
                             let mut common = EntityCommon::default();
                             let mut dimension_entity: Option<EntityType> = None;
@@ -531,9 +693,9 @@ impl Entity {
                                                             pair.assert_f64()?;
                                                     }
                                                     70 => {
-                                                        dimension_base.set_dimension_type(
-                                                            pair.assert_i16()?,
-                                                        )?;
+                                                        let result = dimension_base
+                                                            .set_dimension_type(&pair);
+                                                        iter.recover_value_error(result)?;
                                                     }
                                                     71 => {
                                                         dimension_base.attachment_point = enum_from_number!(
@@ -624,6 +786,20 @@ impl Entity {
                                                                     ),
                                                                 );
                                                             }
+                                                            "AcDbArcDimension" => {
+                                                                dimension_entity =
+                                                                    Some(EntityType::ArcDimension(
+                                                                        ArcDimension {
+                                                                            dimension_base:
+                                                                                dimension_base
+                                                                                    .clone(),
+                                                                            ..Default::default()
+                                                                        },
+                                                                    ));
+                                                            }
+                                                            "AcDbRadialDimensionLarge" => {
+                                                                dimension_entity = Some(EntityType::JoggedRadialDimension(JoggedRadialDimension { dimension_base: dimension_base.clone(), .. Default::default() }));
+                                                            }
                                                             _ => {} // unexpected dimension type
                                                         }
                                                     }
@@ -653,47 +829,30 @@ impl Entity {
                             }
                         }
                         _ => {
-                            match EntityType::from_type_string(&type_string) {
-                                Some(e) => {
-                                    let mut entity = Entity::new(e);
-                                    if !entity.apply_custom_reader(iter)? {
-                                        // no custom reader, use the auto-generated one
-                                        loop {
-                                            match iter.next() {
-                                                Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                                    // new entity or ENDSEC
-                                                    iter.put_back(Ok(pair));
-                                                    break;
-                                                }
-                                                Some(Ok(pair)) => {
-                                                    entity.apply_code_pair(&pair, iter)?
-                                                }
-                                                Some(Err(e)) => return Err(e),
-                                                None => return Err(DxfError::UnexpectedEndOfInput),
-                                            }
-                                        }
-
-                                        entity.post_parse()?;
-                                    }
-
-                                    return Ok(Some(entity));
-                                }
-                                None => {
-                                    // swallow unsupported entity
-                                    loop {
-                                        match iter.next() {
-                                            Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                                // found another entity or ENDSEC
-                                                iter.put_back(Ok(pair));
-                                                break;
-                                            }
-                                            Some(Ok(_)) => (), // part of the unsupported entity
-                                            Some(Err(e)) => return Err(e),
-                                            None => return Err(DxfError::UnexpectedEndOfInput),
+                            let specific = EntityType::from_type_string(&type_string)
+                                .unwrap_or_else(|| {
+                                    EntityType::Unknown(UnknownEntity::new(&type_string, vec![]))
+                                });
+                            let mut entity = Entity::new(specific);
+                            if !entity.apply_custom_reader(iter)? {
+                                // no custom reader, use the auto-generated one
+                                loop {
+                                    match iter.next() {
+                                        Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                                            // new entity or ENDSEC
+                                            iter.put_back(Ok(pair));
+                                            break;
                                         }
+                                        Some(Ok(pair)) => entity.apply_code_pair(&pair, iter)?,
+                                        Some(Err(e)) => return Err(e),
+                                        None => return Err(DxfError::UnexpectedEndOfInput),
                                     }
                                 }
+
+                                entity.post_parse()?;
                             }
+
+                            return Ok(Some(entity));
                         }
                     }
                 }
@@ -833,15 +992,39 @@ impl Entity {
             EntityType::AttributeDefinition(ref mut att) => {
                 Entity::apply_custom_reader_attributedefinition(&mut self.common, att, iter)
             }
+            EntityType::Hatch(ref mut hatch) => {
+                Entity::apply_custom_reader_hatch(&mut self.common, hatch, iter)
+            }
             EntityType::LwPolyline(ref mut poly) => {
                 Entity::apply_custom_reader_lwpolyline(&mut self.common, poly, iter)
             }
+            EntityType::Mesh(ref mut mesh) => {
+                Entity::apply_custom_reader_mesh(&mut self.common, mesh, iter)
+            }
+            EntityType::Table(ref mut table) => {
+                Entity::apply_custom_reader_table(&mut self.common, table, iter)
+            }
+            EntityType::ExtrudedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_extrudedsurface(&mut self.common, surface, iter)
+            }
+            EntityType::LoftedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_loftedsurface(&mut self.common, surface, iter)
+            }
+            EntityType::RevolvedSurface(ref mut surface) => {
+                Entity::apply_custom_reader_revolvedsurface(&mut self.common, surface, iter)
+            }
+            EntityType::SweptSurface(ref mut surface) => {
+                Entity::apply_custom_reader_sweptsurface(&mut self.common, surface, iter)
+            }
             EntityType::MText(ref mut mtext) => {
                 Entity::apply_custom_reader_mtext(&mut self.common, mtext, iter)
             }
             EntityType::MLeader(ref mut mleader) => {
                 Entity::apply_custom_reader_mleader(&mut self.common, mleader, iter)
             }
+            EntityType::Unknown(ref mut unknown) => {
+                Entity::apply_custom_reader_unknown(&mut self.common, unknown, iter)
+            }
             _ => Ok(false), // no custom reader
         }
     }
@@ -1391,19 +1574,26 @@ impl Entity {
                         );
                     }
                     420 => {
-                        mtext.background_color_rgb = pair.assert_i32()?;
+                        mtext
+                            .background_fill_color
+                            .set_true_color_raw_value(pair.assert_i32()?);
                     }
                     430 => {
-                        mtext.background_color_name = pair.assert_string()?;
+                        mtext
+                            .background_fill_color
+                            .set_color_name_raw_value(pair.assert_string()?);
                     }
                     45 => {
                         mtext.fill_box_scale = pair.assert_f64()?;
                     }
                     63 => {
-                        mtext.background_fill_color = Color::from_raw_value(pair.assert_i16()?);
+                        mtext
+                            .background_fill_color
+                            .set_raw_value(pair.assert_i16()?);
                     }
                     441 => {
-                        mtext.background_fill_color_transparency = pair.assert_i32()?;
+                        mtext.background_fill_color_transparency =
+                            Transparency::from_raw_value(pair.assert_i32()?);
                     }
                     75 => {
                         mtext.column_type = pair.assert_i16()?;
@@ -1582,7 +1772,9 @@ impl Entity {
                     171 => {
                         mleader.text_attachment = pair.assert_i16()?;
                     }
-                    _ => common.apply_individual_pair(&pair, iter)?,
+                    _ => {
+                        common.apply_individual_pair(&pair, iter)?;
+                    }
                 },
                 ReadingState::Leader => {
                     match pair.code {
@@ -1655,6 +1847,24 @@ impl Entity {
             }
         }
     }
+    fn apply_custom_reader_unknown(
+        common: &mut EntityCommon,
+        unknown: &mut UnknownEntity,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        loop {
+            let pair = next_pair!(iter);
+            if unknown.code_pairs.is_empty()
+                && !is_specific_subclass_marker(&pair)
+                && (pair.code == 100 // `AcDbEntity` is written with the common data
+                    || common.apply_individual_pair(&pair, iter)?)
+            {
+                continue;
+            }
+
+            unknown.code_pairs.push(pair);
+        }
+    }
     pub(crate) fn add_code_pairs(
         &self,
         pairs: &mut Vec<CodePair>,
@@ -1691,15 +1901,47 @@ impl Entity {
             EntityType::OrdinateDimension(ref dim) => {
                 Entity::add_custom_code_pairs_ordinatedimension(pairs, dim, version);
             }
+            EntityType::ArcDimension(ref dim) => {
+                Entity::add_custom_code_pairs_arcdimension(pairs, dim, version);
+            }
+            EntityType::JoggedRadialDimension(ref dim) => {
+                Entity::add_custom_code_pairs_joggedradialdimension(pairs, dim, version);
+            }
+            EntityType::Hatch(ref hatch) => {
+                Entity::add_custom_code_pairs_hatch(pairs, hatch, version);
+            }
             EntityType::Polyline(ref poly) => {
                 Entity::add_custom_code_pairs_polyline(pairs, poly, version);
             }
             EntityType::Vertex(ref v) => {
                 Entity::add_custom_code_pairs_vertex(pairs, v, version);
             }
+            EntityType::Mesh(ref mesh) => {
+                Entity::add_custom_code_pairs_mesh(pairs, mesh, version);
+            }
+            EntityType::Table(ref table) => {
+                Entity::add_custom_code_pairs_table(pairs, table, version);
+            }
+            EntityType::ExtrudedSurface(ref surface) => {
+                Entity::add_custom_code_pairs_extrudedsurface(pairs, surface, version);
+            }
+            EntityType::LoftedSurface(ref surface) => {
+                Entity::add_custom_code_pairs_loftedsurface(pairs, surface, version);
+            }
+            EntityType::RevolvedSurface(ref surface) => {
+                Entity::add_custom_code_pairs_revolvedsurface(pairs, surface, version);
+            }
+            EntityType::SweptSurface(ref surface) => {
+                Entity::add_custom_code_pairs_sweptsurface(pairs, surface, version);
+            }
             EntityType::MLeader(ref leader) => {
                 Entity::add_custom_code_pairs_mleader(pairs, leader, version);
             }
+            EntityType::Unknown(ref unknown) => {
+                for pair in &unknown.code_pairs {
+                    pairs.push(pair.clone());
+                }
+            }
             _ => return false, // no custom code pairs
         }
 
@@ -1792,6 +2034,53 @@ impl Entity {
         pairs.push(CodePair::new_f64(34, dim.definition_point_3.z));
         true
     }
+    fn add_custom_code_pairs_arcdimension(
+        pairs: &mut Vec<CodePair>,
+        dim: &ArcDimension,
+        version: AcadVersion,
+    ) -> bool {
+        dim.dimension_base.add_code_pairs(pairs, version);
+        pairs.push(CodePair::new_str(100, "AcDbArcDimension"));
+        pairs.push(CodePair::new_f64(13, dim.definition_point_2.x));
+        pairs.push(CodePair::new_f64(23, dim.definition_point_2.y));
+        pairs.push(CodePair::new_f64(33, dim.definition_point_2.z));
+        pairs.push(CodePair::new_f64(14, dim.definition_point_3.x));
+        pairs.push(CodePair::new_f64(24, dim.definition_point_3.y));
+        pairs.push(CodePair::new_f64(34, dim.definition_point_3.z));
+        pairs.push(CodePair::new_f64(15, dim.arc_center.x));
+        pairs.push(CodePair::new_f64(25, dim.arc_center.y));
+        pairs.push(CodePair::new_f64(35, dim.arc_center.z));
+        pairs.push(CodePair::new_f64(40, dim.arc_start_angle));
+        pairs.push(CodePair::new_f64(41, dim.arc_end_angle));
+        pairs.push(CodePair::new_i16(70, as_i16(dim.is_partial)));
+        pairs.push(CodePair::new_i16(71, as_i16(dim.has_leader)));
+        pairs.push(CodePair::new_f64(16, dim.leader_point_1.x));
+        pairs.push(CodePair::new_f64(26, dim.leader_point_1.y));
+        pairs.push(CodePair::new_f64(36, dim.leader_point_1.z));
+        pairs.push(CodePair::new_f64(17, dim.leader_point_2.x));
+        pairs.push(CodePair::new_f64(27, dim.leader_point_2.y));
+        pairs.push(CodePair::new_f64(37, dim.leader_point_2.z));
+        true
+    }
+    fn add_custom_code_pairs_joggedradialdimension(
+        pairs: &mut Vec<CodePair>,
+        dim: &JoggedRadialDimension,
+        version: AcadVersion,
+    ) -> bool {
+        dim.dimension_base.add_code_pairs(pairs, version);
+        pairs.push(CodePair::new_str(100, "AcDbRadialDimensionLarge"));
+        pairs.push(CodePair::new_f64(13, dim.definition_point_2.x));
+        pairs.push(CodePair::new_f64(23, dim.definition_point_2.y));
+        pairs.push(CodePair::new_f64(33, dim.definition_point_2.z));
+        pairs.push(CodePair::new_f64(14, dim.override_center.x));
+        pairs.push(CodePair::new_f64(24, dim.override_center.y));
+        pairs.push(CodePair::new_f64(34, dim.override_center.z));
+        pairs.push(CodePair::new_f64(15, dim.jog_point.x));
+        pairs.push(CodePair::new_f64(25, dim.jog_point.y));
+        pairs.push(CodePair::new_f64(35, dim.jog_point.z));
+        pairs.push(CodePair::new_f64(40, dim.jog_angle));
+        true
+    }
     fn add_custom_code_pairs_polyline(
         pairs: &mut Vec<CodePair>,
         poly: &Polyline,
@@ -2232,11 +2521,13 @@ impl Entity {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use crate::entities::*;
     use crate::enums::*;
     use crate::helper_functions::tests::*;
     use crate::objects::*;
+    use crate::tables::*;
     use crate::*;
     use float_cmp::approx_eq;
 
@@ -2251,6 +2542,30 @@ mod tests {
         entities[0].clone()
     }
 
+    fn read_entity_with_version(
+        version: AcadVersion,
+        entity_type: &str,
+        body: Vec<CodePair>,
+    ) -> Entity {
+        let mut pairs = vec![
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "HEADER"),
+            CodePair::new_str(9, "$ACADVER"),
+            CodePair::new_string(1, &version.to_string()),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "ENTITIES"),
+            CodePair::new_str(0, entity_type),
+        ];
+        pairs.extend(body);
+        pairs.push(CodePair::new_str(0, "ENDSEC"));
+        pairs.push(CodePair::new_str(0, "EOF"));
+        let drawing = drawing_from_pairs(pairs);
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        entities[0].clone()
+    }
+
     #[test]
     fn read_empty_entities_section() {
         let drawing = drawing_from_pairs(vec![
@@ -2273,8 +2588,18 @@ mod tests {
             CodePair::new_str(0, "ENDSEC"),
             CodePair::new_str(0, "EOF"),
         ]);
-        let entities = drawing.entities();
-        assert_eq!(0, entities.count());
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_ENTITY", unknown.type_string);
+                assert_eq!(
+                    vec![CodePair::new_str(1, "unsupported string")],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown entity"),
+        }
     }
 
     #[test]
@@ -2290,58 +2615,333 @@ mod tests {
             CodePair::new_str(0, "EOF"),
         ]);
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(2, entities.len());
+        assert_eq!(3, entities.len());
         match entities[0].specific {
             EntityType::Line(_) => (),
             _ => panic!("expected a line"),
         }
         match entities[1].specific {
+            EntityType::Unknown(_) => (),
+            _ => panic!("expected an unknown entity"),
+        }
+        match entities[2].specific {
             EntityType::Circle(_) => (),
             _ => panic!("expected a circle"),
         }
     }
 
-    #[test]
-    fn read_entity_with_no_values() {
-        let drawing = drawing_from_pairs(vec![
-            CodePair::new_str(0, "SECTION"),
-            CodePair::new_str(2, "ENTITIES"),
-            CodePair::new_str(0, "LINE"),
-            CodePair::new_str(0, "ENDSEC"),
-            CodePair::new_str(0, "EOF"),
+    fn malformed_entities_text(section: &str) -> String {
+        let mut lines = vec!["0", "SECTION", "2", section];
+        if section == "BLOCKS" {
+            lines.extend(vec!["0", "BLOCK", "2", "some-block"]);
+        }
+        lines.extend(vec![
+            "0",
+            "LINE",
+            "5",
+            "ABC",
+            "10",
+            "not-a-number",
+            "20",
+            "2.0",
+            "0",
+            "CIRCLE",
+            "40",
+            "3.0",
         ]);
+        if section == "BLOCKS" {
+            lines.extend(vec!["0", "ENDBLK"]);
+        }
+        lines.extend(vec!["0", "ENDSEC", "0", "EOF"]);
+        lines.join("\n")
+    }
+
+    #[test]
+    fn read_malformed_entity_is_an_error() {
+        let text = malformed_entities_text("ENTITIES");
+        let error = match Drawing::load(&mut text.as_bytes()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        let context = error.context().unwrap();
+        assert_eq!(Some(String::from("ENTITIES")), context.section);
+        assert_eq!(Some(String::from("LINE")), context.item_type);
+        assert_eq!(Some(Handle(0xABC)), context.handle);
+        assert_eq!(Some(FilePosition::Line(5)), context.position);
+
+        // the inner context describes the offending code pair
+        let inner = match error {
+            DxfError::WithContext(ref inner, _) => inner,
+            _ => panic!("expected an error with context"),
+        };
+        let context = inner.context().unwrap();
+        assert_eq!(Some(10), context.code);
+        assert_eq!(Some(String::from("not-a-number")), context.raw_value);
+        assert_eq!(Some(FilePosition::Line(10)), context.position);
+        match error.root_cause() {
+            DxfError::ParseFloatError(_, 10) => (),
+            e => panic!("expected a float parse error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn read_malformed_entity_error_source_chain() {
+        let text = malformed_entities_text("ENTITIES");
+        let error = match Drawing::load(&mut text.as_bytes()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        let mut depth = 0;
+        let mut current: &dyn std::error::Error = &error;
+        while let Some(source) = current.source() {
+            depth += 1;
+            current = source;
+        }
+
+        // entity context -> code pair context -> float error -> std's ParseFloatError
+        assert_eq!(3, depth);
+        assert!(current.is::<std::num::ParseFloatError>());
+    }
+
+    #[test]
+    fn read_malformed_entity_in_block_is_an_error() {
+        let text = malformed_entities_text("BLOCKS");
+        let error = match Drawing::load(&mut text.as_bytes()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        let context = error.context().unwrap();
+        assert_eq!(Some(String::from("BLOCKS")), context.section);
+        assert_eq!(Some(String::from("LINE")), context.item_type);
+    }
+
+    fn load_leniently(text: &str) -> (Drawing, Vec<Diagnostic>) {
+        let options = LoadOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        Drawing::load_with_options(&mut text.as_bytes(), &options).unwrap()
+    }
+
+    #[test]
+    fn read_malformed_value_leniently() {
+        let text = malformed_entities_text("ENTITIES");
+        let (drawing, diagnostics) = load_leniently(&text);
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
+        assert_eq!(2, entities.len());
         match entities[0].specific {
-            EntityType::Line(_) => (),
+            EntityType::Line(ref line) => assert_eq!(Point::new(0.0, 2.0, 0.0), line.p1),
             _ => panic!("expected a line"),
         }
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(DiagnosticSeverity::Warning, diagnostics[0].severity);
+        assert_eq!("ENTITIES", diagnostics[0].section);
+        assert_eq!("LINE", diagnostics[0].item_type);
+        assert_eq!(Handle(0xABC), diagnostics[0].handle);
+        assert_eq!(FilePosition::Line(10), diagnostics[0].position);
     }
 
     #[test]
-    fn read_common_entity_fields() {
-        let ent = read_entity("LINE", vec![CodePair::new_str(8, "layer")]);
-        assert_eq!("layer", ent.common.layer);
+    fn read_unreadable_entity_leniently() {
+        let text = vec![
+            "0",
+            "SECTION",
+            "2",
+            "ENTITIES",
+            "0",
+            "LWPOLYLINE",
+            "5",
+            "ABC",
+            "20",
+            "2.0",
+            "0",
+            "CIRCLE",
+            "40",
+            "3.0",
+            "0",
+            "ENDSEC",
+            "0",
+            "EOF",
+        ]
+        .join("\n");
+        let (drawing, diagnostics) = load_leniently(&text);
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Circle(ref circle) => assert_eq!(3.0, circle.radius),
+            _ => panic!("expected a circle"),
+        }
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(DiagnosticSeverity::Error, diagnostics[0].severity);
+        assert_eq!("LWPOLYLINE", diagnostics[0].item_type);
+        assert_eq!(Handle(0xABC), diagnostics[0].handle);
+        assert_eq!(FilePosition::Line(5), diagnostics[0].position);
     }
 
     #[test]
-    fn read_line() {
+    fn read_unsupported_entity_with_common_data() {
         let ent = read_entity(
-            "LINE",
+            "ACME_WIDGET",
             vec![
-                CodePair::new_f64(10, 1.1), // p1
-                CodePair::new_f64(20, 2.2),
-                CodePair::new_f64(30, 3.3),
-                CodePair::new_f64(11, 4.4), // p2
-                CodePair::new_f64(21, 5.5),
-                CodePair::new_f64(31, 6.6),
+                CodePair::new_str(5, "ABC"),
+                CodePair::new_str(100, "AcDbEntity"),
+                CodePair::new_str(8, "layer"),
+                CodePair::new_str(100, "AcmeDbWidget"),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_str(8, "not the layer"),
             ],
         );
+        assert_eq!(Handle(0xABC), ent.common.handle);
+        assert_eq!("layer", ent.common.layer);
         match ent.specific {
-            EntityType::Line(ref line) => {
-                assert_eq!(Point::new(1.1, 2.2, 3.3), line.p1);
-                assert_eq!(Point::new(4.4, 5.5, 6.6), line.p2);
-            }
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("ACME_WIDGET", unknown.type_string);
+                assert_eq!(
+                    vec![
+                        CodePair::new_str(100, "AcmeDbWidget"),
+                        CodePair::new_f64(10, 1.0),
+                        CodePair::new_str(8, "not the layer"),
+                    ],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+    }
+
+    #[test]
+    fn write_unsupported_entity() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        let mut ent = Entity::new(EntityType::Unknown(UnknownEntity::new(
+            "ACME_WIDGET",
+            vec![
+                CodePair::new_str(100, "AcmeDbWidget"),
+                CodePair::new_f64(10, 1.0),
+            ],
+        )));
+        ent.common.layer = String::from("layer");
+        drawing.add_entity(ent);
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(100, "AcmeDbWidget"),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_str(0, "ENDSEC"),
+            ],
+        );
+        assert_contains_pairs(&drawing, vec![CodePair::new_str(0, "ACME_WIDGET")]);
+    }
+
+    #[test]
+    fn round_trip_unsupported_entity_in_r12() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R12;
+        drawing.add_entity(Entity::new(EntityType::Unknown(UnknownEntity::new(
+            "ACME_WIDGET",
+            vec![CodePair::new_f64(10, 1.0), CodePair::new_str(1, "widget")],
+        ))));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Unknown(ref unknown) => {
+                assert_eq!("ACME_WIDGET", unknown.type_string);
+                assert_eq!(
+                    vec![CodePair::new_f64(10, 1.0), CodePair::new_str(1, "widget")],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown entity"),
+        }
+    }
+
+    #[test]
+    fn read_entity_with_no_values() {
+        let drawing = drawing_from_pairs(vec![
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "ENTITIES"),
+            CodePair::new_str(0, "LINE"),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "EOF"),
+        ]);
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn read_common_entity_fields() {
+        let ent = read_entity("LINE", vec![CodePair::new_str(8, "layer")]);
+        assert_eq!("layer", ent.common.layer);
+    }
+
+    #[test]
+    fn read_entity_true_color() {
+        let ent = read_entity(
+            "LINE",
+            vec![
+                CodePair::new_i16(62, 1),
+                CodePair::new_i32(420, 0xFE0102),
+                CodePair::new_str(430, "RAL CLASSIC$RAL 3020"),
+            ],
+        );
+        assert_eq!(Some(1), ent.common.color.index());
+        assert_eq!(Some((0xFE, 0x01, 0x02)), ent.common.color.true_color());
+        assert_eq!(
+            Some(("RAL CLASSIC", "RAL 3020")),
+            ent.common.color.color_book()
+        );
+    }
+
+    #[test]
+    fn read_entity_transparency() {
+        let ent = read_entity("LINE", vec![CodePair::new_i32(440, 0x0200_007F)]);
+        assert_eq!(Some(0x7F), ent.common.transparency.alpha());
+
+        let ent = read_entity("LINE", vec![CodePair::new_i32(440, 0x0100_0000)]);
+        assert!(ent.common.transparency.is_by_block());
+    }
+
+    #[test]
+    fn write_entity_true_color() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        let mut ent = Entity::new(EntityType::Line(Default::default()));
+        ent.common.color = Color::from_rgb(0xFE, 0x01, 0x02);
+        drawing.add_entity(ent);
+        assert_contains_pairs(&drawing, vec![CodePair::new_i16(62, 1)]);
+        assert_contains_pairs(&drawing, vec![CodePair::new_i32(420, 0xFE0102)]);
+
+        // older versions fall back to the closest index
+        drawing.header.version = AcadVersion::R2000;
+        assert_contains_pairs(&drawing, vec![CodePair::new_i16(62, 1)]);
+        assert_not_contains_pairs(&drawing, vec![CodePair::new_i32(420, 0xFE0102)]);
+    }
+
+    #[test]
+    fn read_line() {
+        let ent = read_entity(
+            "LINE",
+            vec![
+                CodePair::new_f64(10, 1.1), // p1
+                CodePair::new_f64(20, 2.2),
+                CodePair::new_f64(30, 3.3),
+                CodePair::new_f64(11, 4.4), // p2
+                CodePair::new_f64(21, 5.5),
+                CodePair::new_f64(31, 6.6),
+            ],
+        );
+        match ent.specific {
+            EntityType::Line(ref line) => {
+                assert_eq!(Point::new(1.1, 2.2, 3.3), line.p1);
+                assert_eq!(Point::new(4.4, 5.5, 6.6), line.p2);
+            }
             _ => panic!("expected a line"),
         }
     }
@@ -2586,6 +3186,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_viewport() {
+        let ent = read_entity(
+            "VIEWPORT",
+            vec![
+                CodePair::new_str(100, "AcDbViewport"),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(40, 3.0),
+                CodePair::new_f64(41, 4.0),
+                CodePair::new_i16(68, 2),
+                CodePair::new_i16(69, 3),
+                CodePair::new_f64(45, 8.0),
+                CodePair::new_str(331, "A1"),
+                CodePair::new_str(331, "A2"),
+                CodePair::new_i32(90, 0x0001_0201),
+                CodePair::new_str(340, "B1"),
+                CodePair::new_i16(281, 2),
+                CodePair::new_i16(63, 7),
+                CodePair::new_i32(421, 0x00FF_8000),
+            ],
+        );
+        match ent.specific {
+            EntityType::Viewport(ref viewport) => {
+                assert_eq!(Point::new(1.0, 2.0, 0.0), viewport.center);
+                assert_eq!(3.0, viewport.width);
+                assert_eq!(4.0, viewport.height);
+                assert_eq!(2, viewport.status);
+                assert_eq!(3, viewport.id);
+                assert_eq!(0.5, viewport.scale());
+                assert_eq!(
+                    vec![Handle(0xA1), Handle(0xA2)],
+                    viewport.__frozen_layers_handle
+                );
+                assert!(viewport.is_perspective_mode_on());
+                assert!(viewport.is_grid_mode_on());
+                assert!(viewport.is_non_rectangular_clipping_on());
+                assert!(!viewport.is_snap_mode_on());
+                assert_eq!(Handle(0xB1), viewport.__clipping_boundary_handle);
+                assert_eq!(ViewRenderMode::HiddenLine, viewport.render_mode);
+                assert_eq!(
+                    Some((0xFF, 0x80, 0x00)),
+                    viewport.ambient_light_color.true_color()
+                );
+            }
+            _ => panic!("expected a VIEWPORT"),
+        }
+    }
+
+    #[test]
+    fn write_viewport() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2000;
+        let layer = drawing
+            .add_layer(Layer {
+                name: String::from("frozen"),
+                ..Default::default()
+            })
+            .clone();
+        let mut viewport = Viewport::new(Point::new(1.0, 2.0, 0.0), 3.0, 4.0);
+        viewport.add_frozen_layers(&layer);
+        viewport.set_is_zoom_locked(true);
+        let mut ent = Entity::new(EntityType::Viewport(viewport));
+        ent.common.is_in_paper_space = true;
+        let viewport = drawing.add_entity(ent).clone();
+        match viewport.specific {
+            EntityType::Viewport(ref viewport) => {
+                let frozen = viewport.frozen_layers(&drawing);
+                assert_eq!(1, frozen.len());
+                assert_eq!("frozen", frozen[0].name);
+            }
+            _ => panic!("expected a VIEWPORT"),
+        }
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(100, "AcDbViewport"),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_f64(40, 3.0),
+                CodePair::new_f64(41, 4.0),
+                CodePair::new_i16(68, 1),
+                CodePair::new_i16(69, 1),
+            ],
+        );
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_i16(72, 1000),
+                CodePair::new_string(331, &layer.handle.as_string()),
+                CodePair::new_i32(90, 32864 | 16384),
+            ],
+        );
+    }
+
+    #[test]
+    fn viewport_line_type_scale() {
+        let mut header = Header {
+            line_type_scale: 2.0,
+            ..Default::default()
+        };
+        let mut viewport = Viewport::new(Point::origin(), 10.0, 5.0);
+        viewport.set_scale(0.25);
+        assert_eq!(20.0, viewport.view_height);
+        assert_eq!(8.0, viewport.line_type_scale(&header));
+
+        header.scale_line_types_in_paperspace = false;
+        assert_eq!(2.0, viewport.line_type_scale(&header));
+    }
+
+    #[test]
+    fn viewport_scale_with_zero_heights() {
+        let mut viewport = Viewport::new(Point::origin(), 10.0, 5.0);
+        viewport.set_scale(0.0);
+        assert_eq!(5.0, viewport.view_height);
+
+        viewport.view_height = 0.0;
+        assert_eq!(1.0, viewport.scale());
+
+        let viewport = Viewport::new(Point::origin(), 10.0, 0.0);
+        assert_eq!(1.0, viewport.scale());
+        assert_eq!(1.0, viewport.line_type_scale(&Header::default()));
+    }
+
     #[test]
     fn read_entity_with_custom_reader_mtext() {
         let ent = read_entity(
@@ -2842,8 +3567,8 @@ mod tests {
         // Assert
         match entity.specific {
             EntityType::MLeader(ref mleader) => {
-                assert_eq!(mleader.enable_dogleg, true);
-                assert_eq!(mleader.enable_dogleg, true);
+                assert!(mleader.enable_dogleg);
+                assert!(mleader.enable_dogleg);
                 assert_eq!(mleader.dogleg_length, 3000.366019534463);
                 assert_eq!(
                     mleader.dogleg_vector,
@@ -3041,7 +3766,7 @@ mod tests {
         // Assert
         match entity.specific {
             EntityType::MLeader(ref mleader) => {
-                assert_eq!(mleader.enable_dogleg, true);
+                assert!(mleader.enable_dogleg);
                 assert_eq!(mleader.dogleg_length, 500.0000000000002);
                 assert_eq!(
                     mleader.dogleg_vector,
@@ -3994,49 +4719,1296 @@ mod tests {
     }
 
     #[test]
-    fn read_dimension() {
+    fn read_hatch_with_polyline_boundary_path() {
         let ent = read_entity(
-            "DIMENSION",
+            "HATCH",
             vec![
-                CodePair::new_str(1, "text"),
-                CodePair::new_str(100, "AcDbOrdinateDimension"),
-                CodePair::new_f64(13, 1.1), // definition_point_2
-                CodePair::new_f64(23, 2.2),
-                CodePair::new_f64(33, 3.3),
-                CodePair::new_f64(14, 4.4), // definition_point_3
-                CodePair::new_f64(24, 5.5),
-                CodePair::new_f64(34, 6.6),
+                CodePair::new_str(100, "AcDbHatch"),
+                CodePair::new_f64(10, 0.0), // elevation point
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(30, 1.5),
+                CodePair::new_str(2, "SOLID"),
+                CodePair::new_i16(70, 1), // solid fill
+                CodePair::new_i16(71, 1), // associative
+                CodePair::new_i32(91, 1), // boundary path count
+                CodePair::new_i32(92, 3), // external polyline
+                CodePair::new_i16(72, 1), // has bulge
+                CodePair::new_i16(73, 1), // is closed
+                CodePair::new_i32(93, 2), // vertex count
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(42, 0.5),
+                CodePair::new_f64(10, 3.0),
+                CodePair::new_f64(20, 4.0),
+                CodePair::new_f64(42, 0.0),
+                CodePair::new_i32(97, 1), // source boundary object count
+                CodePair::new_str(330, "ABC"),
+                CodePair::new_i16(75, 1),    // hatch style
+                CodePair::new_i16(76, 1),    // pattern type
+                CodePair::new_f64(47, 0.25), // pixel size
+                CodePair::new_i32(98, 1),    // seed point count
+                CodePair::new_f64(10, 5.0),
+                CodePair::new_f64(20, 6.0),
             ],
         );
         match ent.specific {
-            EntityType::OrdinateDimension(ref dim) => {
-                assert_eq!("text", dim.dimension_base.text);
-                assert_eq!(Point::new(1.1, 2.2, 3.3), dim.definition_point_2);
-                assert_eq!(Point::new(4.4, 5.5, 6.6), dim.definition_point_3);
+            EntityType::Hatch(ref hatch) => {
+                assert_eq!(Point::new(0.0, 0.0, 1.5), hatch.elevation_point);
+                assert!(hatch.is_solid_fill);
+                assert!(hatch.is_associative);
+                assert_eq!(1, hatch.boundary_paths.len());
+                let path = &hatch.boundary_paths[0];
+                assert!(path.is_external());
+                assert!(path.is_polyline());
+                assert!(path.is_closed);
+                assert_eq!(
+                    vec![
+                        HatchPolylineVertex::new(1.0, 2.0, 0.5),
+                        HatchPolylineVertex::new(3.0, 4.0, 0.0),
+                    ],
+                    path.vertices
+                );
+                assert_eq!(vec![Handle(0xABC)], path.source_boundary_handles);
+                assert_eq!(HatchStyle::OutermostAreaOnly, hatch.hatch_style);
+                assert_eq!(HatchPatternType::Predefined, hatch.pattern_type);
+                assert!(approx_eq!(f64, 0.25, hatch.pixel_size));
+                assert_eq!(vec![Point::new(5.0, 6.0, 0.0)], hatch.seed_points);
             }
-            _ => panic!("expected an ordinate dimension"),
+            _ => panic!("expected a HATCH"),
         }
     }
 
     #[test]
-    fn read_entity_after_unsupported_dimension() {
+    fn read_hatch_with_edge_boundary_path() {
+        // the spline fit data is only present in R2010+
+        let ent = read_entity_with_version(
+            AcadVersion::R2010,
+            "HATCH",
+            vec![
+                CodePair::new_i16(70, 1),
+                CodePair::new_i32(91, 2), // boundary path count
+                // path 1
+                CodePair::new_i32(92, 1),
+                CodePair::new_i32(93, 3), // edge count
+                CodePair::new_i16(72, 1), // line
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(11, 3.0),
+                CodePair::new_f64(21, 4.0),
+                CodePair::new_i16(72, 2), // circular arc
+                CodePair::new_f64(10, 5.0),
+                CodePair::new_f64(20, 6.0),
+                CodePair::new_f64(40, 7.0),
+                CodePair::new_f64(50, 8.0),
+                CodePair::new_f64(51, 9.0),
+                CodePair::new_i16(73, 0),
+                CodePair::new_i16(72, 3), // elliptic arc
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 1.0),
+                CodePair::new_f64(11, 2.0),
+                CodePair::new_f64(21, 0.0),
+                CodePair::new_f64(40, 0.5),
+                CodePair::new_f64(50, 0.0),
+                CodePair::new_f64(51, 180.0),
+                CodePair::new_i16(73, 1),
+                CodePair::new_i32(97, 0), // source boundary object count
+                // path 2
+                CodePair::new_i32(92, 0),
+                CodePair::new_i32(93, 1), // edge count
+                CodePair::new_i16(72, 4), // spline
+                CodePair::new_i32(94, 2),
+                CodePair::new_i16(73, 1), // rational
+                CodePair::new_i16(74, 0),
+                CodePair::new_i32(95, 6),
+                CodePair::new_i32(96, 3),
+                CodePair::new_f64(40, 0.0),
+                CodePair::new_f64(40, 0.0),
+                CodePair::new_f64(40, 0.0),
+                CodePair::new_f64(40, 1.0),
+                CodePair::new_f64(40, 1.0),
+                CodePair::new_f64(40, 1.0),
+                CodePair::new_f64(10, 0.0),
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 1.0),
+                CodePair::new_f64(10, 2.0),
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(42, 1.0),
+                CodePair::new_f64(42, 2.0),
+                CodePair::new_f64(42, 1.0),
+                CodePair::new_i32(97, 2), // fit point count
+                CodePair::new_f64(11, 0.0),
+                CodePair::new_f64(21, 0.0),
+                CodePair::new_f64(11, 2.0),
+                CodePair::new_f64(21, 0.0),
+                CodePair::new_f64(12, 1.0), // start tangent
+                CodePair::new_f64(22, 1.0),
+                CodePair::new_i32(97, 1), // source boundary object count
+                CodePair::new_str(330, "DEF"),
+                CodePair::new_i16(75, 0),
+                CodePair::new_i16(76, 1),
+            ],
+        );
+        match ent.specific {
+            EntityType::Hatch(ref hatch) => {
+                assert_eq!(2, hatch.boundary_paths.len());
+                let path = &hatch.boundary_paths[0];
+                assert!(!path.is_polyline());
+                assert_eq!(
+                    vec![
+                        HatchEdge::Line {
+                            start: Point::new(1.0, 2.0, 0.0),
+                            end: Point::new(3.0, 4.0, 0.0),
+                        },
+                        HatchEdge::CircularArc {
+                            center: Point::new(5.0, 6.0, 0.0),
+                            radius: 7.0,
+                            start_angle: 8.0,
+                            end_angle: 9.0,
+                            is_counter_clockwise: false,
+                        },
+                        HatchEdge::EllipticArc {
+                            center: Point::new(1.0, 1.0, 0.0),
+                            major_axis: Vector::new(2.0, 0.0, 0.0),
+                            minor_axis_ratio: 0.5,
+                            start_angle: 0.0,
+                            end_angle: 180.0,
+                            is_counter_clockwise: true,
+                        },
+                    ],
+                    path.edges
+                );
+                assert!(path.source_boundary_handles.is_empty());
+                let path = &hatch.boundary_paths[1];
+                assert_eq!(
+                    vec![HatchEdge::Spline {
+                        degree: 2,
+                        is_periodic: false,
+                        knot_values: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                        control_points: vec![
+                            Point::new(0.0, 0.0, 0.0),
+                            Point::new(1.0, 1.0, 0.0),
+                            Point::new(2.0, 0.0, 0.0),
+                        ],
+                        weights: vec![1.0, 2.0, 1.0],
+                        fit_points: vec![Point::new(0.0, 0.0, 0.0), Point::new(2.0, 0.0, 0.0)],
+                        start_tangent: Vector::new(1.0, 1.0, 0.0),
+                        end_tangent: Vector::zero(),
+                    }],
+                    path.edges
+                );
+                assert_eq!(vec![Handle(0xDEF)], path.source_boundary_handles);
+                assert_eq!(HatchPatternType::Predefined, hatch.pattern_type);
+            }
+            _ => panic!("expected a HATCH"),
+        }
+    }
+
+    #[test]
+    fn read_hatch_with_pattern_and_gradient() {
+        let ent = read_entity(
+            "HATCH",
+            vec![
+                CodePair::new_str(2, "ANSI31"),
+                CodePair::new_i16(70, 0),
+                CodePair::new_i32(91, 0),
+                CodePair::new_i16(75, 0),
+                CodePair::new_i16(76, 1),
+                CodePair::new_f64(52, 45.0),
+                CodePair::new_f64(41, 2.0),
+                CodePair::new_i16(77, 1),
+                CodePair::new_i16(78, 2), // pattern line count
+                CodePair::new_f64(53, 45.0),
+                CodePair::new_f64(43, 0.0),
+                CodePair::new_f64(44, 0.0),
+                CodePair::new_f64(45, -0.1),
+                CodePair::new_f64(46, 0.1),
+                CodePair::new_i16(79, 0),
+                CodePair::new_f64(53, 90.0),
+                CodePair::new_f64(43, 1.0),
+                CodePair::new_f64(44, 2.0),
+                CodePair::new_f64(45, 0.0),
+                CodePair::new_f64(46, 0.5),
+                CodePair::new_i16(79, 2),
+                CodePair::new_f64(49, 0.25),
+                CodePair::new_f64(49, -0.125),
+                CodePair::new_i32(98, 0),
+                CodePair::new_i32(450, 1),
+                CodePair::new_i32(451, 0),
+                CodePair::new_f64(460, 1.5),
+                CodePair::new_f64(461, 0.5),
+                CodePair::new_i32(452, 1),
+                CodePair::new_f64(462, 0.75),
+                CodePair::new_i32(453, 2),
+                CodePair::new_f64(463, 0.0),
+                CodePair::new_i16(63, 5),
+                CodePair::new_i32(421, 0x0000FF),
+                CodePair::new_f64(463, 1.0),
+                CodePair::new_i32(421, 0xFFFF00),
+                CodePair::new_str(470, "LINEAR"),
+            ],
+        );
+        match ent.specific {
+            EntityType::Hatch(ref hatch) => {
+                assert_eq!("ANSI31", hatch.pattern_name);
+                assert!(!hatch.is_solid_fill);
+                assert!(approx_eq!(f64, 45.0, hatch.pattern_angle));
+                assert!(approx_eq!(f64, 2.0, hatch.pattern_scale));
+                assert!(hatch.is_pattern_double);
+                assert_eq!(2, hatch.pattern_definition_lines.len());
+                assert_eq!(
+                    HatchPatternDefinitionLine {
+                        angle: 90.0,
+                        base_point: Point::new(1.0, 2.0, 0.0),
+                        offset: Vector::new(0.0, 0.5, 0.0),
+                        dash_lengths: vec![0.25, -0.125],
+                    },
+                    hatch.pattern_definition_lines[1]
+                );
+                assert!(hatch.is_gradient);
+                assert!(hatch.is_single_color_gradient);
+                assert!(approx_eq!(f64, 1.5, hatch.gradient_angle));
+                assert!(approx_eq!(f64, 0.5, hatch.gradient_shift));
+                assert!(approx_eq!(f64, 0.75, hatch.gradient_tint));
+                assert_eq!(2, hatch.gradient_colors.len());
+                assert_eq!(Some(5), hatch.gradient_colors[0].color.index());
+                assert_eq!(
+                    Some((0, 0, 255)),
+                    hatch.gradient_colors[0].color.true_color()
+                );
+                assert!(approx_eq!(f64, 1.0, hatch.gradient_colors[1].value));
+                assert_eq!(
+                    Some((255, 255, 0)),
+                    hatch.gradient_colors[1].color.true_color()
+                );
+                assert_eq!("LINEAR", hatch.gradient_name);
+            }
+            _ => panic!("expected a HATCH"),
+        }
+    }
+
+    #[test]
+    fn read_entity_after_hatch() {
         let drawing = from_section(
             "ENTITIES",
             vec![
-                CodePair::new_str(0, "DIMENSION"),
-                CodePair::new_str(1, "text"),
-                CodePair::new_str(100, "AcDbSomeUnsupportedDimensionType"),
-                CodePair::new_f64(10, 1.1),
-                CodePair::new_f64(20, 2.2),
-                CodePair::new_f64(30, 3.3),
+                CodePair::new_str(0, "HATCH"),
+                CodePair::new_i32(91, 1),
+                CodePair::new_i32(92, 0),
+                CodePair::new_i32(93, 1),
+                CodePair::new_i16(72, 1),
+                CodePair::new_f64(10, 0.0),
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(11, 1.0),
+                CodePair::new_f64(21, 1.0),
+                CodePair::new_i32(97, 0),
                 CodePair::new_str(0, "LINE"),
             ],
         );
         let entities = drawing.entities().collect::<Vec<_>>();
-        assert_eq!(1, entities.len());
+        assert_eq!(2, entities.len());
         match entities[0].specific {
-            EntityType::Line(_) => {}
-            _ => panic!("expected a line"),
+            EntityType::Hatch(ref hatch) => assert_eq!(1, hatch.boundary_paths[0].edges.len()),
+            _ => panic!("expected a HATCH"),
+        }
+        match entities[1].specific {
+            EntityType::Line(_) => (),
+            _ => panic!("expected a LINE"),
+        }
+    }
+
+    #[test]
+    fn write_hatch() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let mut hatch = Hatch::default();
+        let mut path = HatchBoundaryPath::from_vertices(
+            vec![
+                HatchPolylineVertex::new(1.0, 2.0, 0.0),
+                HatchPolylineVertex::new(3.0, 4.0, 0.5),
+            ],
+            true,
+        );
+        path.source_boundary_handles.push(Handle(0xABC));
+        hatch.boundary_paths.push(path);
+        hatch.seed_points.push(Point::new(5.0, 6.0, 0.0));
+        drawing.add_entity(Entity::new(EntityType::Hatch(hatch)));
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(2, "SOLID"),
+                CodePair::new_i16(70, 1),
+                CodePair::new_i16(71, 0),
+                CodePair::new_i32(91, 1), // boundary path count
+                CodePair::new_i32(92, 3), // external polyline
+                CodePair::new_i16(72, 1), // has bulge
+                CodePair::new_i16(73, 1), // is closed
+                CodePair::new_i32(93, 2), // vertex count
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(42, 0.0),
+                CodePair::new_f64(10, 3.0),
+                CodePair::new_f64(20, 4.0),
+                CodePair::new_f64(42, 0.5),
+                CodePair::new_i32(97, 1),
+                CodePair::new_str(330, "ABC"),
+                CodePair::new_i16(75, 0),
+                CodePair::new_i16(76, 1),
+                CodePair::new_f64(47, 0.0), // no pattern data for solid fills
+                CodePair::new_i32(98, 1),
+                CodePair::new_f64(10, 5.0),
+                CodePair::new_f64(20, 6.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn write_hatch_not_supported_before_r13() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R12;
+        drawing.add_entity(Entity::new(EntityType::Hatch(Hatch::default())));
+        assert_not_contains_pairs(&drawing, vec![CodePair::new_str(0, "HATCH")]);
+    }
+
+    #[test]
+    fn round_trip_hatch() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let hatch = Hatch {
+            pattern_name: String::from("USER"),
+            is_solid_fill: false,
+            pattern_type: HatchPatternType::UserDefined,
+            pattern_angle: 30.0,
+            pattern_definition_lines: vec![HatchPatternDefinitionLine {
+                angle: 30.0,
+                offset: Vector::new(0.0, 1.0, 0.0),
+                dash_lengths: vec![0.5, -0.25],
+                ..Default::default()
+            }],
+            boundary_paths: vec![HatchBoundaryPath::from_edges(vec![
+                HatchEdge::Line {
+                    start: Point::new(0.0, 0.0, 0.0),
+                    end: Point::new(2.0, 0.0, 0.0),
+                },
+                HatchEdge::Spline {
+                    degree: 2,
+                    is_periodic: false,
+                    knot_values: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                    control_points: vec![
+                        Point::new(2.0, 0.0, 0.0),
+                        Point::new(1.0, 2.0, 0.0),
+                        Point::new(0.0, 0.0, 0.0),
+                    ],
+                    weights: vec![],
+                    fit_points: vec![],
+                    start_tangent: Vector::zero(),
+                    end_tangent: Vector::zero(),
+                },
+            ])],
+            is_gradient: true,
+            gradient_colors: vec![HatchGradientColor {
+                value: 0.0,
+                color: Color::from_index(1),
+            }],
+            gradient_name: String::from("LINEAR"),
+            ..Default::default()
+        };
+        drawing.add_entity(Entity::new(EntityType::Hatch(hatch.clone())));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Hatch(ref h) => assert_eq!(&hatch, h),
+            _ => panic!("expected a HATCH"),
+        }
+    }
+
+    #[test]
+    fn round_trip_hatch_spline_edge_without_fit_points() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let hatch = Hatch {
+            boundary_paths: vec![HatchBoundaryPath::from_edges(vec![
+                HatchEdge::Spline {
+                    degree: 2,
+                    is_periodic: false,
+                    knot_values: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                    control_points: vec![
+                        Point::new(0.0, 0.0, 0.0),
+                        Point::new(1.0, 2.0, 0.0),
+                        Point::new(2.0, 0.0, 0.0),
+                    ],
+                    weights: vec![],
+                    fit_points: vec![],
+                    start_tangent: Vector::zero(),
+                    end_tangent: Vector::zero(),
+                },
+                HatchEdge::Line {
+                    start: Point::new(2.0, 0.0, 0.0),
+                    end: Point::new(0.0, 0.0, 0.0),
+                },
+            ])],
+            ..Default::default()
+        };
+        drawing.add_entity(Entity::new(EntityType::Hatch(hatch.clone())));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Hatch(ref h) => assert_eq!(&hatch, h),
+            _ => panic!("expected a HATCH"),
+        }
+    }
+
+    #[test]
+    fn read_mesh() {
+        let ent = read_entity(
+            "MESH",
+            vec![
+                CodePair::new_i32(92, 0), // common graphics data size
+                CodePair::new_str(100, "AcDbSubDMesh"),
+                CodePair::new_i16(71, 2),
+                CodePair::new_i16(72, 1),
+                CodePair::new_i32(91, 3),
+                CodePair::new_i32(92, 4), // vertex count
+                CodePair::new_f64(10, 0.0),
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 0.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 1.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_f64(10, 0.0),
+                CodePair::new_f64(20, 1.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_i32(93, 9), // face list size
+                CodePair::new_i32(90, 3),
+                CodePair::new_i32(90, 0),
+                CodePair::new_i32(90, 1),
+                CodePair::new_i32(90, 2),
+                CodePair::new_i32(90, 4),
+                CodePair::new_i32(90, 0),
+                CodePair::new_i32(90, 1),
+                CodePair::new_i32(90, 2),
+                CodePair::new_i32(90, 3),
+                CodePair::new_i32(94, 1), // edge count
+                CodePair::new_i32(90, 0),
+                CodePair::new_i32(90, 1),
+                CodePair::new_i32(95, 1), // crease count
+                CodePair::new_f64(140, -1.0),
+                CodePair::new_i32(90, 1), // override count
+                CodePair::new_i32(91, 7),
+                CodePair::new_i32(92, 1),
+                CodePair::new_i32(90, 0),
+                CodePair::new_i16(62, 1),
+                CodePair::new_str(1001, "APP"),
+            ],
+        );
+        assert_eq!(1, ent.common.x_data.len());
+        match ent.specific {
+            EntityType::Mesh(ref mesh) => {
+                assert_eq!(2, mesh.version);
+                assert!(mesh.is_blend_crease);
+                assert_eq!(3, mesh.subdivision_level);
+                assert_eq!(4, mesh.vertices.len());
+                assert_eq!(Point::new(1.0, 1.0, 0.0), mesh.vertices[2]);
+                assert_eq!(vec![vec![0, 1, 2], vec![0, 1, 2, 3]], mesh.faces);
+                assert_eq!(vec![MeshEdge::new(0, 1)], mesh.edges);
+                assert_eq!(vec![-1.0], mesh.edge_creases);
+                assert_eq!(
+                    vec![MeshSubentityOverride {
+                        subentity_marker: 7,
+                        properties: vec![MeshOverrideProperty {
+                            property_type: 0,
+                            values: vec![CodePair::new_i16(62, 1)],
+                        }],
+                    }],
+                    mesh.overrides
+                );
+            }
+            _ => panic!("expected a MESH"),
+        }
+    }
+
+    #[test]
+    fn round_trip_mesh() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let mut mesh = Mesh::from_faces(
+            vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(2.0, 0.0, 0.0),
+                Point::new(3.0, 1.0, 0.0),
+                Point::new(1.0, 2.0, 0.0),
+                Point::new(-1.0, 1.0, 0.0),
+                Point::new(2.0, -1.0, 0.0),
+                Point::new(4.0, -1.0, 0.0),
+            ],
+            vec![vec![0, 1, 2, 3, 4], vec![0, 5, 1], vec![1, 5, 6, 2]],
+        );
+        mesh.is_blend_crease = true;
+        mesh.edge_creases[0] = -1.0;
+        mesh.edge_creases[6] = 2.5;
+        mesh.subdivision_level = 3;
+        mesh.overrides = vec![
+            MeshSubentityOverride {
+                subentity_marker: 1,
+                properties: vec![
+                    MeshOverrideProperty {
+                        property_type: 0,
+                        values: vec![CodePair::new_i16(62, 1)],
+                    },
+                    MeshOverrideProperty {
+                        property_type: 1,
+                        values: vec![CodePair::new_str(347, "1A")],
+                    },
+                ],
+            },
+            MeshSubentityOverride {
+                subentity_marker: 4,
+                properties: vec![MeshOverrideProperty {
+                    property_type: 2,
+                    values: vec![CodePair::new_i32(440, 0x0200_0080)],
+                }],
+            },
+        ];
+        drawing.add_entity(Entity::new(EntityType::Mesh(mesh)));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Mesh(ref mesh) => {
+                assert!(mesh.is_blend_crease);
+                assert_eq!(3, mesh.subdivision_level);
+                assert_eq!(7, mesh.vertices.len());
+                assert_eq!(Point::new(4.0, -1.0, 0.0), mesh.vertices[6]);
+                assert_eq!(
+                    vec![vec![0, 1, 2, 3, 4], vec![0, 5, 1], vec![1, 5, 6, 2]],
+                    mesh.faces
+                );
+                assert_eq!(9, mesh.edges.len());
+                assert_eq!(MeshEdge::new(5, 6), mesh.edges[7]);
+                assert_eq!(9, mesh.edge_creases.len());
+                assert_eq!(-1.0, mesh.edge_creases[0]);
+                assert_eq!(2.5, mesh.edge_creases[6]);
+                assert_eq!(2, mesh.overrides.len());
+                assert_eq!(1, mesh.overrides[0].subentity_marker);
+                assert_eq!(
+                    vec![CodePair::new_str(347, "1A")],
+                    mesh.overrides[0].properties[1].values
+                );
+                assert_eq!(4, mesh.overrides[1].subentity_marker);
+                assert_eq!(2, mesh.overrides[1].properties[0].property_type);
+                assert_eq!(
+                    vec![CodePair::new_i32(440, 0x0200_0080)],
+                    mesh.overrides[1].properties[0].values
+                );
+            }
+            _ => panic!("expected a MESH"),
+        }
+    }
+
+    #[test]
+    fn mesh_triangles() {
+        let mesh = Mesh::from_faces(
+            vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            vec![vec![0, 1, 2, 3], vec![0, 1], vec![0, 1, 9]],
+        );
+        assert_eq!(vec![[0, 1, 2], [0, 2, 3]], mesh.triangles());
+        let points = mesh.triangle_points();
+        assert_eq!(2, points.len());
+        assert_eq!(Point::new(0.0, 1.0, 0.0), points[1][2]);
+    }
+
+    #[test]
+    fn read_table() {
+        let ent = read_entity(
+            "ACAD_TABLE",
+            vec![
+                CodePair::new_i32(92, 0), // common graphics data size
+                CodePair::new_str(100, "AcDbBlockReference"),
+                CodePair::new_str(2, "*T1"),
+                CodePair::new_f64(10, 1.0),
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_str(100, "AcDbTable"),
+                CodePair::new_i16(280, 0),
+                CodePair::new_str(342, "A1"),
+                CodePair::new_str(343, "A2"),
+                CodePair::new_f64(11, 1.0),
+                CodePair::new_f64(21, 0.0),
+                CodePair::new_f64(31, 0.0),
+                CodePair::new_i32(90, 22),
+                CodePair::new_i32(91, 2), // row count
+                CodePair::new_i32(92, 2), // column count
+                CodePair::new_i16(62, 3), // table-wide override, not the entity color
+                CodePair::new_f64(141, 0.5),
+                CodePair::new_f64(141, 0.25),
+                CodePair::new_f64(142, 3.0),
+                CodePair::new_f64(142, 1.0),
+                // title cell merged across both columns
+                CodePair::new_i16(171, 1),
+                CodePair::new_i16(172, 0),
+                CodePair::new_i16(173, 1),
+                CodePair::new_i16(174, 0),
+                CodePair::new_i32(175, 2),
+                CodePair::new_i32(176, 1),
+                CodePair::new_i32(91, 0),
+                CodePair::new_i16(178, 0),
+                CodePair::new_f64(145, 0.0),
+                CodePair::new_str(2, "Bill of "),
+                CodePair::new_str(1, "Materials"),
+                CodePair::new_str(7, "Standard"),
+                CodePair::new_i16(171, 1),
+                CodePair::new_i16(173, 1),
+                // block cell
+                CodePair::new_i16(171, 2),
+                CodePair::new_str(340, "B1"),
+                CodePair::new_f64(144, 0.5),
+                CodePair::new_i16(179, 1),
+                CodePair::new_str(331, "C1"),
+                CodePair::new_str(300, "PART-1"),
+                // quantity cell with a typed value
+                CodePair::new_i16(171, 1),
+                CodePair::new_str(1, "12"),
+                CodePair::new_i16(64, 1), // per-cell override, not modelled
+                CodePair::new_str(301, "CELL_VALUE"),
+                CodePair::new_i32(93, 0),
+                CodePair::new_i32(90, 1),
+                CodePair::new_i32(91, 12),
+                CodePair::new_i32(94, 0),
+                CodePair::new_str(300, ""),
+                CodePair::new_str(302, "12"),
+                CodePair::new_str(304, "ACVALUE_END"),
+            ],
+        );
+        assert_eq!(Color::by_layer(), ent.common.color);
+        match ent.specific {
+            EntityType::Table(ref table) => {
+                assert_eq!("*T1", table.block_name);
+                assert_eq!(Point::new(1.0, 2.0, 0.0), table.insertion_point);
+                assert_eq!(Handle(0xA1), table.__table_style_handle);
+                assert_eq!(Handle(0xA2), table.__block_record_handle);
+                assert_eq!(2, table.row_count());
+                assert_eq!(2, table.column_count());
+                assert_eq!(4, table.cells.len());
+                assert_eq!("Bill of Materials", table.cell(0, 0).unwrap().text.as_str());
+                assert_eq!(
+                    vec![TableCellRange {
+                        top_row: 0,
+                        left_column: 0,
+                        bottom_row: 0,
+                        right_column: 1,
+                    }],
+                    table.merged_ranges()
+                );
+                let block = table.cell(1, 0).unwrap();
+                assert_eq!(TableCellType::Block, block.cell_type);
+                assert_eq!(Handle(0xB1), block.block_handle);
+                assert_eq!(0.5, block.block_scale);
+                assert_eq!(
+                    vec![TableCellAttribute {
+                        attribute_definition_handle: Handle(0xC1),
+                        value: String::from("PART-1"),
+                    }],
+                    block.attribute_values
+                );
+                let quantity = table.cell(1, 1).unwrap();
+                assert_eq!("12", quantity.text);
+                let value = quantity.value.as_ref().unwrap();
+                assert_eq!(Some(DataTableValue::Integer(12)), value.value);
+                assert_eq!("12", value.formatted_value);
+            }
+            _ => panic!("expected an ACAD_TABLE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_table() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let mut table = Table::new(
+            Point::new(1.0, 2.0, 0.0),
+            vec![1.0, 0.5, 0.5, 0.5],
+            vec![4.0, 2.0, 2.0],
+        );
+        table.override_flags = 0x01;
+        table.border_color_override_flags = 0x02;
+        table.merge_cells(&TableCellRange {
+            top_row: 0,
+            left_column: 0,
+            bottom_row: 0,
+            right_column: 2,
+        });
+        table.merge_cells(&TableCellRange {
+            top_row: 2,
+            left_column: 1,
+            bottom_row: 3,
+            right_column: 2,
+        });
+        table.cell_mut(0, 0).unwrap().text = "x".repeat(600);
+        *table.cell_mut(1, 0).unwrap() = TableCell {
+            override_flags: 0x0003_0005,
+            virtual_edge_flags: 3,
+            rotation: 90.0,
+            text_style_name: String::from("cell-style"),
+            ..TableCell::from_text("Bolt")
+        };
+        *table.cell_mut(1, 1).unwrap() = TableCell {
+            block_scale: 0.5,
+            attribute_values: vec![TableCellAttribute {
+                attribute_definition_handle: Handle(0x50),
+                value: String::from("M8"),
+            }],
+            ..TableCell::from_block(Handle(0x42))
+        };
+        let values = [
+            (1, 2, DataTableValue::Integer(-42)),
+            (2, 0, DataTableValue::Double(1.5)),
+            (3, 0, DataTableValue::Str("y".repeat(600))),
+            (2, 1, DataTableValue::Point2D(Point::new(1.0, 2.0, 0.0))),
+            (2, 2, DataTableValue::Point3D(Point::new(1.0, 2.0, 3.0))),
+            (3, 1, DataTableValue::Handle(Handle(0x1F))),
+        ];
+        for (row, column, value) in &values {
+            table.cell_mut(*row, *column).unwrap().value = Some(TableCellValue {
+                value: Some(value.clone()),
+                unit_type: 2,
+                formatted_value: String::from("formatted"),
+                ..Default::default()
+            });
+        }
+        drawing.add_entity(Entity::new(EntityType::Table(table)));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Table(ref table) => {
+                assert_eq!(0x01, table.override_flags);
+                assert_eq!(0x02, table.border_color_override_flags);
+                assert_eq!(
+                    vec![
+                        TableCellRange {
+                            top_row: 0,
+                            left_column: 0,
+                            bottom_row: 0,
+                            right_column: 2,
+                        },
+                        TableCellRange {
+                            top_row: 2,
+                            left_column: 1,
+                            bottom_row: 3,
+                            right_column: 2,
+                        },
+                    ],
+                    table.merged_ranges()
+                );
+                assert!(table.cell(3, 2).unwrap().is_merged);
+                assert!(!table.cell(1, 0).unwrap().is_merged);
+                assert_eq!("x".repeat(600), table.cell(0, 0).unwrap().text);
+
+                let text_cell = table.cell(1, 0).unwrap();
+                assert_eq!("Bolt", text_cell.text);
+                assert_eq!(0x0003_0005, text_cell.override_flags);
+                assert_eq!(3, text_cell.virtual_edge_flags);
+                assert_eq!(90.0, text_cell.rotation);
+                assert_eq!("cell-style", text_cell.text_style_name);
+
+                let block_cell = table.cell(1, 1).unwrap();
+                assert_eq!(TableCellType::Block, block_cell.cell_type);
+                assert_eq!(Handle(0x42), block_cell.block_handle);
+                assert_eq!(0.5, block_cell.block_scale);
+                assert_eq!(1, block_cell.attribute_values.len());
+                assert_eq!("M8", block_cell.attribute_values[0].value);
+
+                for (row, column, value) in &values {
+                    let cell_value = table.cell(*row, *column).unwrap().value.as_ref().unwrap();
+                    assert_eq!(Some(value), cell_value.value.as_ref());
+                    assert_eq!(2, cell_value.unit_type);
+                    assert_eq!("formatted", cell_value.formatted_value);
+                }
+            }
+            _ => panic!("expected an ACAD_TABLE"),
+        }
+    }
+
+    #[test]
+    fn table_is_linked_to_its_style() {
+        let mut drawing = Drawing::new();
+        let style = drawing
+            .add_object(Object::new(ObjectType::TableStyle(TableStyle {
+                description: String::from("bom"),
+                ..Default::default()
+            })))
+            .clone();
+        let mut table = Table::new(Point::origin(), vec![1.0], vec![1.0]);
+        table.set_table_style(&style).unwrap();
+        match table.table_style(&drawing).unwrap().specific {
+            ObjectType::TableStyle(ref s) => assert_eq!("bom", s.description),
+            _ => panic!("expected a TABLESTYLE"),
+        }
+    }
+
+    #[test]
+    fn read_extruded_surface() {
+        let mut pairs = vec![
+            CodePair::new_str(100, "AcDbModelerGeometry"),
+            CodePair::new_i16(70, 1),
+            CodePair::new_str(1, "acis-data"),
+            CodePair::new_str(100, "AcDbSurface"),
+            CodePair::new_i16(71, 4),
+            CodePair::new_i16(72, 5),
+            CodePair::new_str(100, "AcDbExtrudedSurface"),
+            CodePair::new_i32(90, 33),
+            CodePair::new_f64(10, 0.0), // sweep_vector
+            CodePair::new_f64(20, 0.0),
+            CodePair::new_f64(30, 2.5),
+        ];
+        for value in TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0)).values() {
+            pairs.push(CodePair::new_f64(40, value));
+        }
+        pairs.push(CodePair::new_f64(48, 2.0)); // scale_factor
+        pairs.push(CodePair::new_i16(70, 2)); // alignment
+        pairs.push(CodePair::new_bool(293, true)); // is_banked
+        let ent = read_entity("EXTRUDEDSURFACE", pairs);
+        match ent.specific {
+            EntityType::ExtrudedSurface(ref surface) => {
+                assert_eq!(1, surface.format_version_number);
+                assert_eq!(vec!["acis-data"], surface.custom_data);
+                assert_eq!(4, surface.u_isoline_count);
+                assert_eq!(5, surface.v_isoline_count);
+                assert_eq!(33, surface.class_id);
+                assert_eq!(Vector::new(0.0, 0.0, 2.5), surface.sweep_vector);
+                assert_eq!(
+                    TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0)),
+                    surface.extruded_entity_transform
+                );
+                assert_eq!(2.0, surface.sweep_options.scale_factor);
+                assert_eq!(
+                    SweepAlignment::TranslateSweepEntityToPath,
+                    surface.sweep_options.alignment
+                );
+                assert!(surface.sweep_options.is_banked);
+            }
+            _ => panic!("expected an EXTRUDEDSURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_extruded_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::ExtrudedSurface(ExtrudedSurface {
+            custom_data: vec![String::from("extruded")],
+            u_isoline_count: 3,
+            v_isoline_count: 4,
+            class_id: 33,
+            sweep_vector: Vector::new(0.0, 0.0, 4.0),
+            extruded_entity_transform: TransformationMatrix::scale(2.0, 2.0, 2.0),
+            sweep_options: SweepOptions {
+                twist_angle: 0.5,
+                alignment: SweepAlignment::AlignSweepEntityToPath,
+                is_banked: true,
+                reference_vector: Vector::y_axis(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::ExtrudedSurface(ref surface) => {
+                assert_eq!(vec!["extruded"], surface.custom_data);
+                assert_eq!(3, surface.u_isoline_count);
+                assert_eq!(4, surface.v_isoline_count);
+                assert_eq!(33, surface.class_id);
+                assert_eq!(Vector::new(0.0, 0.0, 4.0), surface.sweep_vector);
+                assert_eq!(
+                    TransformationMatrix::scale(2.0, 2.0, 2.0),
+                    surface.extruded_entity_transform
+                );
+                assert_eq!(0.5, surface.sweep_options.twist_angle);
+                assert_eq!(
+                    SweepAlignment::AlignSweepEntityToPath,
+                    surface.sweep_options.alignment
+                );
+                assert!(surface.sweep_options.is_banked);
+                assert_eq!(Vector::y_axis(), surface.sweep_options.reference_vector);
+            }
+            _ => panic!("expected an EXTRUDEDSURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_lofted_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::LoftedSurface(LoftedSurface {
+            loft_entity_transform: TransformationMatrix::scale(1.0, 2.0, 3.0),
+            plane_normal_lofting_type: 1,
+            start_draft_angle: 0.125,
+            end_draft_angle: 0.25,
+            end_draft_magnitude: 2.5,
+            has_no_twist: false,
+            is_ruled: true,
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::LoftedSurface(ref surface) => {
+                assert_eq!(
+                    TransformationMatrix::scale(1.0, 2.0, 3.0),
+                    surface.loft_entity_transform
+                );
+                assert_eq!(1, surface.plane_normal_lofting_type);
+                assert_eq!(0.125, surface.start_draft_angle);
+                assert_eq!(0.25, surface.end_draft_angle);
+                assert_eq!(2.5, surface.end_draft_magnitude);
+                assert!(!surface.has_no_twist);
+                assert!(surface.is_ruled);
+            }
+            _ => panic!("expected a LOFTEDSURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_nurb_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::NurbSurface(NurbSurface {
+            custom_data: vec![String::from("nurb")],
+            u_isoline_count: 8,
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::NurbSurface(ref surface) => {
+                assert_eq!(vec!["nurb"], surface.custom_data);
+                assert_eq!(8, surface.u_isoline_count);
+                assert_eq!(6, surface.v_isoline_count);
+            }
+            _ => panic!("expected a NURBSURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_plane_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::PlaneSurface(PlaneSurface {
+            custom_data: vec![String::from("plane-1"), String::from("plane-2")],
+            custom_data2: vec![String::from("plane-3")],
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::PlaneSurface(ref surface) => {
+                assert_eq!(vec!["plane-1", "plane-2"], surface.custom_data);
+                assert_eq!(vec!["plane-3"], surface.custom_data2);
+            }
+            _ => panic!("expected a PLANESURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_revolved_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::RevolvedSurface(RevolvedSurface {
+            revolve_entity_id: 12,
+            revolve_entity_data: vec![0x01, 0x02, 0x03],
+            axis_point: Point::new(1.0, 2.0, 0.0),
+            axis_direction: Vector::y_axis(),
+            revolve_angle: std::f64::consts::PI,
+            revolved_entity_transform: TransformationMatrix::scale(3.0, 3.0, 3.0),
+            draft_end_distance: 2.0,
+            is_close_to_axis: true,
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::RevolvedSurface(ref surface) => {
+                assert_eq!(12, surface.revolve_entity_id);
+                assert_eq!(vec![0x01, 0x02, 0x03], surface.revolve_entity_data);
+                assert_eq!(Point::new(1.0, 2.0, 0.0), surface.axis_point);
+                assert_eq!(Vector::y_axis(), surface.axis_direction);
+                assert_eq!(std::f64::consts::PI, surface.revolve_angle);
+                assert_eq!(
+                    TransformationMatrix::scale(3.0, 3.0, 3.0),
+                    surface.revolved_entity_transform
+                );
+                assert_eq!(2.0, surface.draft_end_distance);
+                assert!(surface.is_close_to_axis);
+                assert!(!surface.is_solid);
+            }
+            _ => panic!("expected a REVOLVEDSURFACE"),
+        }
+    }
+
+    #[test]
+    fn round_trip_swept_surface() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2013;
+        drawing.add_entity(Entity::new(EntityType::SweptSurface(SweptSurface {
+            sweep_entity_id: 1,
+            // long enough to be split across several 310 pairs
+            sweep_entity_data: (0..300).map(|i| i as u8).collect(),
+            path_entity_id: 2,
+            path_entity_data: vec![0xAB; 5],
+            path_transform: TransformationMatrix::scale(2.0, 2.0, 2.0),
+            sweep_options: SweepOptions {
+                is_solid: true,
+                alignment: SweepAlignment::TranslatePathToSweepEntity,
+                reference_vector: Vector::x_axis(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })));
+
+        let drawing = drawing_from_pairs(drawing.code_pairs().unwrap());
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::SweptSurface(ref surface) => {
+                assert_eq!(1, surface.sweep_entity_id);
+                assert_eq!(
+                    (0..300).map(|i| i as u8).collect::<Vec<_>>(),
+                    surface.sweep_entity_data
+                );
+                assert_eq!(2, surface.path_entity_id);
+                assert_eq!(vec![0xAB; 5], surface.path_entity_data);
+                assert_eq!(TransformationMatrix::identity(), surface.profile_transform);
+                assert_eq!(
+                    TransformationMatrix::scale(2.0, 2.0, 2.0),
+                    surface.path_transform
+                );
+                assert!(surface.sweep_options.is_solid);
+                assert_eq!(
+                    SweepAlignment::TranslatePathToSweepEntity,
+                    surface.sweep_options.alignment
+                );
+                assert_eq!(Vector::x_axis(), surface.sweep_options.reference_vector);
+            }
+            _ => panic!("expected a SWEPTSURFACE"),
+        }
+    }
+
+    #[test]
+    fn surfaces_are_not_written_before_r2007() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        drawing.add_entity(Entity::new(EntityType::PlaneSurface(Default::default())));
+        drawing.add_entity(Entity::new(EntityType::SweptSurface(Default::default())));
+        assert_not_contains_pairs(&drawing, vec![CodePair::new_str(0, "PLANESURFACE")]);
+        assert_not_contains_pairs(&drawing, vec![CodePair::new_str(0, "SWEPTSURFACE")]);
+    }
+
+    #[test]
+    fn read_dimension() {
+        let ent = read_entity(
+            "DIMENSION",
+            vec![
+                CodePair::new_str(1, "text"),
+                CodePair::new_str(100, "AcDbOrdinateDimension"),
+                CodePair::new_f64(13, 1.1), // definition_point_2
+                CodePair::new_f64(23, 2.2),
+                CodePair::new_f64(33, 3.3),
+                CodePair::new_f64(14, 4.4), // definition_point_3
+                CodePair::new_f64(24, 5.5),
+                CodePair::new_f64(34, 6.6),
+            ],
+        );
+        match ent.specific {
+            EntityType::OrdinateDimension(ref dim) => {
+                assert_eq!("text", dim.dimension_base.text);
+                assert_eq!(Point::new(1.1, 2.2, 3.3), dim.definition_point_2);
+                assert_eq!(Point::new(4.4, 5.5, 6.6), dim.definition_point_3);
+            }
+            _ => panic!("expected an ordinate dimension"),
+        }
+    }
+
+    #[test]
+    fn read_entity_after_unsupported_dimension() {
+        let drawing = from_section(
+            "ENTITIES",
+            vec![
+                CodePair::new_str(0, "DIMENSION"),
+                CodePair::new_str(1, "text"),
+                CodePair::new_str(100, "AcDbSomeUnsupportedDimensionType"),
+                CodePair::new_f64(10, 1.1),
+                CodePair::new_f64(20, 2.2),
+                CodePair::new_f64(30, 3.3),
+                CodePair::new_str(0, "LINE"),
+            ],
+        );
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Line(_) => {}
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn read_arc_dimension() {
+        let ent = read_entity(
+            "ARC_DIMENSION",
+            vec![
+                CodePair::new_str(100, "AcDbDimension"),
+                CodePair::new_i16(70, 8),
+                CodePair::new_str(100, "AcDbArcDimension"),
+                CodePair::new_f64(13, 1.0), // definition_point_2
+                CodePair::new_f64(23, 0.0),
+                CodePair::new_f64(33, 0.0),
+                CodePair::new_f64(14, 0.0), // definition_point_3
+                CodePair::new_f64(24, 1.0),
+                CodePair::new_f64(34, 0.0),
+                CodePair::new_f64(15, 0.0), // arc_center
+                CodePair::new_f64(25, 0.0),
+                CodePair::new_f64(35, 0.0),
+                CodePair::new_f64(40, 0.0),
+                CodePair::new_f64(41, 1.5),
+                CodePair::new_i16(70, 0),
+                CodePair::new_i16(71, 1),
+                CodePair::new_f64(16, 2.0), // leader_point_1
+                CodePair::new_f64(26, 2.0),
+                CodePair::new_f64(36, 0.0),
+            ],
+        );
+        match ent.specific {
+            EntityType::ArcDimension(ref dim) => {
+                assert_eq!(DimensionType::ArcLength, dim.dimension_base.dimension_type);
+                assert_eq!(Point::new(1.0, 0.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(0.0, 1.0, 0.0), dim.definition_point_3);
+                assert_eq!(Point::origin(), dim.arc_center);
+                assert_eq!(1.5, dim.arc_end_angle);
+                assert!(!dim.is_partial);
+                assert!(dim.has_leader);
+                assert_eq!(Point::new(2.0, 2.0, 0.0), dim.leader_point_1);
+            }
+            _ => panic!("expected an arc dimension"),
+        }
+    }
+
+    #[test]
+    fn read_jogged_radial_dimension_from_dimension_type_string() {
+        let ent = read_entity(
+            "DIMENSION",
+            vec![
+                CodePair::new_f64(10, 1.0), // definition_point_1
+                CodePair::new_f64(20, 2.0),
+                CodePair::new_f64(30, 0.0),
+                CodePair::new_str(100, "AcDbRadialDimensionLarge"),
+                CodePair::new_f64(13, 4.0), // definition_point_2
+                CodePair::new_f64(23, 2.0),
+                CodePair::new_f64(33, 0.0),
+                CodePair::new_f64(14, 1.0), // override_center
+                CodePair::new_f64(24, 5.0),
+                CodePair::new_f64(34, 0.0),
+                CodePair::new_f64(15, 2.0), // jog_point
+                CodePair::new_f64(25, 4.0),
+                CodePair::new_f64(35, 0.0),
+                CodePair::new_f64(40, 0.75),
+            ],
+        );
+        match ent.specific {
+            EntityType::JoggedRadialDimension(ref dim) => {
+                assert_eq!(
+                    Point::new(1.0, 2.0, 0.0),
+                    dim.dimension_base.definition_point_1
+                );
+                assert_eq!(Point::new(4.0, 2.0, 0.0), dim.definition_point_2);
+                assert_eq!(Point::new(1.0, 5.0, 0.0), dim.override_center);
+                assert_eq!(Point::new(2.0, 4.0, 0.0), dim.jog_point);
+                assert_eq!(0.75, dim.jog_angle);
+            }
+            _ => panic!("expected a jogged radial dimension"),
+        }
+    }
+
+    #[test]
+    fn round_trip_arc_and_jogged_radial_dimensions() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2010;
+        let arc = ArcDimension {
+            dimension_base: DimensionBase {
+                dimension_type: DimensionType::ArcLength,
+                ..Default::default()
+            },
+            definition_point_2: Point::new(1.0, 0.0, 0.0),
+            definition_point_3: Point::new(0.0, 1.0, 0.0),
+            arc_end_angle: std::f64::consts::FRAC_PI_2,
+            is_partial: true,
+            has_leader: true,
+            leader_point_1: Point::new(2.0, 2.0, 0.0),
+            leader_point_2: Point::new(3.0, 2.0, 0.0),
+            ..Default::default()
+        };
+        let jogged = JoggedRadialDimension {
+            dimension_base: DimensionBase {
+                dimension_type: DimensionType::JoggedRadius,
+                ..Default::default()
+            },
+            definition_point_2: Point::new(4.0, 2.0, 0.0),
+            override_center: Point::new(1.0, 5.0, 0.0),
+            jog_point: Point::new(2.0, 4.0, 0.0),
+            jog_angle: std::f64::consts::FRAC_PI_4,
+        };
+        drawing.add_entity(Entity::new(EntityType::ArcDimension(arc.clone())));
+        drawing.add_entity(Entity::new(EntityType::JoggedRadialDimension(
+            jogged.clone(),
+        )));
+
+        let pairs = drawing.code_pairs().unwrap();
+        assert!(pairs.contains(&CodePair::new_str(0, "ARC_DIMENSION")));
+        assert!(pairs.contains(&CodePair::new_str(0, "LARGE_RADIAL_DIMENSION")));
+        let drawing = drawing_from_pairs(pairs);
+
+        let entities = drawing.entities().collect::<Vec<_>>();
+        assert_eq!(2, entities.len());
+        match entities[0].specific {
+            EntityType::ArcDimension(ref dim) => assert_eq!(&arc, dim),
+            _ => panic!("expected an arc dimension"),
+        }
+        match entities[1].specific {
+            EntityType::JoggedRadialDimension(ref dim) => assert_eq!(&jogged, dim),
+            _ => panic!("expected a jogged radial dimension"),
         }
     }
 
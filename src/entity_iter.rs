@@ -1,32 +1,72 @@
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::drawing::AUTO_REPLACE_HANDLE;
 use crate::entities::*;
-use crate::DxfResult;
+use crate::{DiagnosticSeverity, DxfError, DxfResult};
 
 use itertools::{put_back, PutBack};
 
+/// Reads entities until the end of the section or block.  A read error ends the iteration and is held in `error`,
+/// unless the reader is lenient, in which case the broken entity is skipped and a diagnostic is recorded.
 pub(crate) struct EntityIter<'a> {
     pub iter: &'a mut CodePairPutBack,
+    pub error: Option<DxfError>,
 }
 
 impl<'a> Iterator for EntityIter<'a> {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Entity> {
-        match Entity::read(self.iter) {
-            Ok(Some(e)) => Some(e),
-            Ok(None) | Err(_) => None,
+        if self.error.is_some() {
+            return None;
+        }
+
+        loop {
+            match Entity::read(self.iter) {
+                Ok(e) => return e,
+                Err(e) if self.iter.is_lenient() => {
+                    let message = format!("the entity was dropped: {}", e);
+                    self.iter.add_diagnostic(DiagnosticSeverity::Error, message);
+                    if let Err(e) = self.iter.skip_to_next_item() {
+                        self.error = Some(e);
+                        return None;
+                    }
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
         }
     }
 }
 
 impl<'a> EntityIter<'a> {
+    pub(crate) fn new(iter: &'a mut CodePairPutBack) -> Self {
+        EntityIter { iter, error: None }
+    }
     pub(crate) fn read_entities_into_vec(&mut self, entities: &mut Vec<Entity>) -> DxfResult<()> {
-        collect_entities(self, entities)
+        collect_entities(self, entities)?;
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 }
 
 pub(crate) fn collect_entities<I>(iter: &mut I, entities: &mut Vec<Entity>) -> DxfResult<()>
+where
+    I: Iterator<Item = Entity>,
+{
+    let mut iter = put_back(iter);
+    while let Some(entity) = next_combined_entity(&mut iter)? {
+        entities.push(entity);
+    }
+
+    Ok(())
+}
+
+/// Reads the next entity, folding any trailing `ATTRIB`, `VERTEX`, `SEQEND`, and `MTEXT` entities into their owner.
+pub(crate) fn next_combined_entity<I>(iter: &mut PutBack<I>) -> DxfResult<Option<Entity>>
 where
     I: Iterator<Item = Entity>,
 {
@@ -67,105 +107,102 @@ where
         Ok(m_text)
     }
 
-    let mut iter = put_back(iter);
-    loop {
-        match iter.next() {
-            Some(Entity {
-                ref common,
-                specific: EntityType::Attribute(ref att),
-            }) => {
-                let mut att = att.clone(); // 27 fields
-                match mtext(&mut iter) {
-                    Ok(Some(m_text)) => att.m_text = m_text,
-                    Ok(None) => (),
-                    Err(e) => return Err(e),
-                }
+    let entity = match iter.next() {
+        Some(Entity {
+            ref common,
+            specific: EntityType::Attribute(ref att),
+        }) => {
+            let mut att = att.clone(); // 27 fields
+            match mtext(iter) {
+                Ok(Some(m_text)) => att.m_text = m_text,
+                Ok(None) => (),
+                Err(e) => return Err(e),
+            }
 
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Attribute(att),
-                });
+            Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Attribute(att),
+            }
+        }
+        Some(Entity {
+            ref common,
+            specific: EntityType::AttributeDefinition(ref att),
+        }) => {
+            let mut att = att.clone(); // 27 fields
+            match mtext(iter) {
+                Ok(Some(m_text)) => att.m_text = m_text,
+                Ok(None) => (),
+                Err(e) => return Err(e),
             }
-            Some(Entity {
-                ref common,
-                specific: EntityType::AttributeDefinition(ref att),
-            }) => {
-                let mut att = att.clone(); // 27 fields
-                match mtext(&mut iter) {
-                    Ok(Some(m_text)) => att.m_text = m_text,
-                    Ok(None) => (),
-                    Err(e) => return Err(e),
-                }
 
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::AttributeDefinition(att),
-                });
+            Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::AttributeDefinition(att),
             }
-            Some(Entity {
-                ref common,
-                specific: EntityType::Insert(ref ins),
-            }) if ins.__has_attributes => {
-                let mut ins = ins.clone(); // 12 fields
-                loop {
-                    match iter.next() {
-                        Some(Entity {
-                            specific: EntityType::Attribute(att),
-                            ..
-                        }) => ins
-                            .__attributes_and_handles
-                            .push((att, AUTO_REPLACE_HANDLE)),
-                        Some(ent) => {
-                            // stop gathering on any non-ATTRIBUTE
-                            iter.put_back(ent);
-                            break;
-                        }
-                        None => break,
+        }
+        Some(Entity {
+            ref common,
+            specific: EntityType::Insert(ref ins),
+        }) if ins.__has_attributes => {
+            let mut ins = ins.clone(); // 12 fields
+            loop {
+                match iter.next() {
+                    Some(Entity {
+                        specific: EntityType::Attribute(att),
+                        ..
+                    }) => ins
+                        .__attributes_and_handles
+                        .push((att, AUTO_REPLACE_HANDLE)),
+                    Some(ent) => {
+                        // stop gathering on any non-ATTRIBUTE
+                        iter.put_back(ent);
+                        break;
                     }
+                    None => break,
                 }
+            }
 
-                swallow_seqend(&mut iter)?;
+            swallow_seqend(iter)?;
 
-                // and finally keep the INSERT
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Insert(ins),
-                });
+            // and finally keep the INSERT
+            Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Insert(ins),
             }
-            Some(Entity {
-                common,
-                specific: EntityType::Polyline(poly),
-            }) => {
-                let mut poly = poly.clone(); // 13 fields
-                loop {
-                    match iter.next() {
-                        Some(Entity {
-                            specific: EntityType::Vertex(vertex),
-                            ..
-                        }) => poly
-                            .__vertices_and_handles
-                            .push((vertex, AUTO_REPLACE_HANDLE)),
-                        Some(ent) => {
-                            // stop gathering on any non-VERTEX
-                            iter.put_back(ent);
-                            break;
-                        }
-                        None => break,
+        }
+        Some(Entity {
+            common,
+            specific: EntityType::Polyline(poly),
+        }) => {
+            let mut poly = poly.clone(); // 13 fields
+            loop {
+                match iter.next() {
+                    Some(Entity {
+                        specific: EntityType::Vertex(vertex),
+                        ..
+                    }) => poly
+                        .__vertices_and_handles
+                        .push((vertex, AUTO_REPLACE_HANDLE)),
+                    Some(ent) => {
+                        // stop gathering on any non-VERTEX
+                        iter.put_back(ent);
+                        break;
                     }
+                    None => break,
                 }
+            }
 
-                swallow_seqend(&mut iter)?;
+            swallow_seqend(iter)?;
 
-                // and finally keep the POLYLINE
-                entities.push(Entity {
-                    common: common.clone(), // 18 fields
-                    specific: EntityType::Polyline(poly),
-                });
+            // and finally keep the POLYLINE
+            Entity {
+                common: common.clone(), // 18 fields
+                specific: EntityType::Polyline(poly),
             }
-            Some(entity) => entities.push(entity),
-            None => break,
         }
-    }
+        Some(entity) => entity,
+        None => return Ok(None),
+    };
 
-    Ok(())
+    Ok(Some(entity))
 }
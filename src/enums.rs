@@ -341,6 +341,8 @@ pub enum DimensionType {
     Radius = 4,
     AngularThreePoint = 5,
     Ordinate = 6,
+    ArcLength = 8,
+    JoggedRadius = 9,
 }
 }
 
@@ -473,6 +475,26 @@ pub enum GeoDataVersion
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum HatchPatternType {
+    UserDefined = 0,
+    Predefined = 1,
+    Custom = 2,
+}
+}
+
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum HatchStyle {
+    OddParity = 0,
+    OutermostAreaOnly = 1,
+    EntireArea = 2,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -1077,6 +1099,17 @@ pub enum StandardScale
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SweepAlignment {
+    NoAlignment = 0,
+    AlignSweepEntityToPath = 1,
+    TranslateSweepEntityToPath = 2,
+    TranslatePathToSweepEntity = 3,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -1087,6 +1120,15 @@ pub enum SubentityType
 }
 }
 
+enum_from_primitive! {
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableCellType {
+    Text = 1,
+    Block = 2,
+}
+}
+
 enum_from_primitive! {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
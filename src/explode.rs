@@ -0,0 +1,506 @@
+// other implementation is in `entity.rs`
+
+use crate::effective_properties::{BY_BLOCK_LINE_TYPE, LAYER_ZERO};
+use crate::entities::*;
+use crate::{
+    Block, Drawing, Handle, LineWeight, ObjectCoordinateSystem, TransformationMatrix, Vector,
+};
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// If the entity is an `Insert`, returns the entities of the referenced block placed in world coordinates along
+    /// with its visible attributes as `Text`, otherwise returns `None`.  See `Insert::explode()` for details.
+    ///
+    /// Unlike `Insert::explode()`, the block's entities take on the properties of this entity: those on layer `0` are
+    /// moved to this entity's layer and BYBLOCK colors, line types, line weights, and transparencies are replaced by
+    /// this entity's values.
+    pub fn explode(&self, drawing: &Drawing) -> Option<Vec<Entity>> {
+        self.explode_with_depth(drawing, false)
+    }
+    /// Like `Entity::explode()`, but nested `Insert` entities are also exploded so that only the leaf geometry
+    /// remains.
+    pub fn explode_recursive(&self, drawing: &Drawing) -> Option<Vec<Entity>> {
+        self.explode_with_depth(drawing, true)
+    }
+}
+
+// private implementation
+impl Entity {
+    fn explode_with_depth(&self, drawing: &Drawing, is_recursive: bool) -> Option<Vec<Entity>> {
+        match self.specific {
+            EntityType::Insert(ref insert) => {
+                let mut exploder = Exploder {
+                    drawing,
+                    is_recursive,
+                    entities: vec![],
+                };
+                exploder.insert(
+                    insert,
+                    Some(&self.common),
+                    &TransformationMatrix::identity(),
+                    0,
+                );
+                Some(exploder.entities)
+            }
+            _ => None,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Insert
+//------------------------------------------------------------------------------
+impl Insert {
+    /// Returns the entities of the referenced block placed in world coordinates, followed by the visible attributes
+    /// converted to `Text`.
+    ///
+    /// The block's base point is moved to the insert's location after applying the scale factors and rotation, and
+    /// the result is placed in the object coordinate system given by the extrusion direction.  A row/column array
+    /// produces one copy of the block per cell.  Constant attribute definitions in the block are converted to `Text`
+    /// and the others are dropped since their values come from the insert's attributes.  Nested `Insert` entities are
+    /// transformed but not exploded and no properties are resolved since they depend on the `Insert` entity; see
+    /// `Entity::explode()`.
+    ///
+    /// The returned entities have no handles.  An empty list is returned if the block can't be found in `drawing` and
+    /// there are no attributes.
+    pub fn explode(&self, drawing: &Drawing) -> Vec<Entity> {
+        self.explode_with_depth(drawing, false)
+    }
+    /// Like `Insert::explode()`, but nested `Insert` entities are also exploded so that only the leaf geometry
+    /// remains.  Blocks nested more than 32 levels deep (e.g., a block that inserts itself) are ignored.
+    pub fn explode_recursive(&self, drawing: &Drawing) -> Vec<Entity> {
+        self.explode_with_depth(drawing, true)
+    }
+    /// Returns the transformation from the coordinates of the referenced block to world coordinates for each cell of
+    /// the row/column array, in row-major order.
+    pub fn block_transformations(&self, block: &Block) -> Vec<TransformationMatrix> {
+        let ocs = ObjectCoordinateSystem::new(&self.extrusion_direction).to_wcs_transformation();
+        let placement =
+            ocs * TransformationMatrix::translation(&Vector::new(
+                self.location.x,
+                self.location.y,
+                self.location.z,
+            )) * TransformationMatrix::rotation_about_axis(
+                &Vector::z_axis(),
+                self.rotation.to_radians(),
+            );
+        let block_to_origin = TransformationMatrix::scale(
+            self.x_scale_factor,
+            self.y_scale_factor,
+            self.z_scale_factor,
+        ) * TransformationMatrix::translation(&Vector::new(
+            -block.base_point.x,
+            -block.base_point.y,
+            -block.base_point.z,
+        ));
+
+        let mut transformations = vec![];
+        for row in 0..self.row_count.max(1) {
+            for column in 0..self.column_count.max(1) {
+                // the array spacing is along the rotated axes, but isn't scaled
+                let cell_offset = TransformationMatrix::translation(&Vector::new(
+                    f64::from(column) * self.column_spacing,
+                    f64::from(row) * self.row_spacing,
+                    0.0,
+                ));
+                transformations.push(placement * cell_offset * block_to_origin);
+            }
+        }
+
+        transformations
+    }
+}
+
+// private implementation
+impl Insert {
+    fn explode_with_depth(&self, drawing: &Drawing, is_recursive: bool) -> Vec<Entity> {
+        let mut exploder = Exploder {
+            drawing,
+            is_recursive,
+            entities: vec![],
+        };
+        exploder.insert(self, None, &TransformationMatrix::identity(), 0);
+        exploder.entities
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                      Exploder
+//------------------------------------------------------------------------------
+struct Exploder<'a> {
+    drawing: &'a Drawing,
+    is_recursive: bool,
+    entities: Vec<Entity>,
+}
+
+impl Exploder<'_> {
+    /// Adds the contents of `insert`, whose own coordinates are mapped to world coordinates by `outer`.
+    fn insert(
+        &mut self,
+        insert: &Insert,
+        insert_common: Option<&EntityCommon>,
+        outer: &TransformationMatrix,
+        depth: usize,
+    ) {
+        if let Some(block) = Block::find_nested(self.drawing, &insert.name, depth + 1) {
+            for cell in insert.block_transformations(block) {
+                let matrix = *outer * cell;
+                for entity in &block.entities {
+                    let mut entity = match entity.specific {
+                        EntityType::AttributeDefinition(ref att_def) if att_def.is_constant() => {
+                            Entity {
+                                common: entity.common.clone(),
+                                specific: EntityType::Text(attribute_definition_text(att_def)),
+                            }
+                        }
+                        EntityType::AttributeDefinition(_) => continue,
+                        _ => entity.clone(),
+                    };
+                    if let Some(insert_common) = insert_common {
+                        inherit_block_properties(&mut entity.common, insert_common);
+                    }
+
+                    match entity.specific {
+                        EntityType::Insert(ref nested) if self.is_recursive => {
+                            self.insert(nested, Some(&entity.common), &matrix, depth + 1);
+                        }
+                        _ => self.add(entity, &matrix),
+                    }
+                }
+            }
+        }
+
+        // attributes are already placed in the insert's own coordinates
+        for att in insert.attributes().filter(|att| !att.is_invisible()) {
+            let mut common = insert_common.cloned().unwrap_or_default();
+            common.x_data.clear();
+            common.extension_data_groups.clear();
+            self.add(
+                Entity {
+                    common,
+                    specific: EntityType::Text(attribute_text(att)),
+                },
+                outer,
+            );
+        }
+    }
+    fn add(&mut self, mut entity: Entity, matrix: &TransformationMatrix) {
+        entity.common.handle = Handle::empty();
+        entity.common.__owner_handle = Handle::empty();
+        entity.transform(matrix);
+        self.entities.push(entity);
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+/// Replaces the properties of an entity in a block that defer to the `Insert` it's drawn through.
+fn inherit_block_properties(common: &mut EntityCommon, insert_common: &EntityCommon) {
+    if common.layer == LAYER_ZERO {
+        common.layer = insert_common.layer.clone();
+    }
+    if common.color.is_by_block() {
+        common.color = insert_common.color.clone();
+    }
+    if common
+        .line_type_name
+        .eq_ignore_ascii_case(BY_BLOCK_LINE_TYPE)
+    {
+        common.line_type_name = insert_common.line_type_name.clone();
+        common.line_type_scale *= insert_common.line_type_scale;
+    }
+    if LineWeight::from_raw_value(common.lineweight_enum_value).is_by_block() {
+        common.lineweight_enum_value = insert_common.lineweight_enum_value;
+    }
+    if common.transparency.is_by_block() {
+        common.transparency = insert_common.transparency.clone();
+    }
+    common.is_visible &= insert_common.is_visible;
+}
+
+fn attribute_text(att: &Attribute) -> Text {
+    Text {
+        thickness: att.thickness,
+        location: att.location.clone(),
+        text_height: att.text_height,
+        value: att.value.clone(),
+        rotation: att.rotation,
+        relative_x_scale_factor: att.relative_x_scale_factor,
+        oblique_angle: att.oblique_angle,
+        text_style_name: att.text_style_name.clone(),
+        text_generation_flags: att.text_generation_flags,
+        horizontal_text_justification: att.horizontal_text_justification,
+        second_alignment_point: att.second_alignment_point.clone(),
+        normal: att.normal.clone(),
+        vertical_text_justification: att.vertical_text_justification,
+    }
+}
+
+fn attribute_definition_text(att_def: &AttributeDefinition) -> Text {
+    Text {
+        thickness: att_def.thickness,
+        location: att_def.location.clone(),
+        text_height: att_def.text_height,
+        value: att_def.value.clone(),
+        rotation: att_def.rotation,
+        relative_x_scale_factor: att_def.relative_x_scale_factor,
+        oblique_angle: att_def.oblique_angle,
+        text_style_name: att_def.text_style_name.clone(),
+        text_generation_flags: att_def.text_generation_flags,
+        horizontal_text_justification: att_def.horizontal_text_justification,
+        second_alignment_point: att_def.second_alignment_point.clone(),
+        normal: att_def.normal.clone(),
+        vertical_text_justification: att_def.vertical_text_justification,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_point(expected: (f64, f64, f64), actual: &Point) {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(
+            close(expected.0, actual.x)
+                && close(expected.1, actual.y)
+                && close(expected.2, actual.z),
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn line_points(entity: &Entity) -> (&Point, &Point) {
+        match entity.specific {
+            EntityType::Line(ref line) => (&line.p1, &line.p2),
+            _ => panic!("expected a line"),
+        }
+    }
+
+    fn drawing_with_block(name: &str, base_point: Point, entities: Vec<Entity>) -> Drawing {
+        let mut drawing = Drawing::new();
+        drawing.add_block(Block {
+            name: String::from(name),
+            base_point,
+            entities,
+            ..Default::default()
+        });
+        drawing
+    }
+
+    fn line(p1: (f64, f64), p2: (f64, f64)) -> Entity {
+        Entity::new(EntityType::Line(Line::new(
+            Point::new(p1.0, p1.1, 0.0),
+            Point::new(p2.0, p2.1, 0.0),
+        )))
+    }
+
+    #[test]
+    fn explode_scaled_and_rotated_insert() {
+        let drawing = drawing_with_block(
+            "b",
+            Point::new(1.0, 1.0, 0.0),
+            vec![line((1.0, 1.0), (2.0, 1.0))],
+        );
+        let insert = Insert {
+            name: String::from("B"),
+            location: Point::new(10.0, 10.0, 0.0),
+            x_scale_factor: 2.0,
+            rotation: 90.0,
+            ..Default::default()
+        };
+        let entities = insert.explode(&drawing);
+        assert_eq!(1, entities.len());
+        let (p1, p2) = line_points(&entities[0]);
+        assert_point((10.0, 10.0, 0.0), p1);
+        assert_point((10.0, 12.0, 0.0), p2);
+    }
+
+    #[test]
+    fn explode_with_extrusion_direction() {
+        let drawing = drawing_with_block("b", Point::origin(), vec![line((0.0, 0.0), (1.0, 0.0))]);
+        let insert = Insert {
+            name: String::from("b"),
+            location: Point::new(0.0, 0.0, 5.0),
+            extrusion_direction: Vector::new(0.0, 0.0, -1.0),
+            ..Default::default()
+        };
+
+        // the OCS of -Z has its X axis along -X
+        let entities = insert.explode(&drawing);
+        let (p1, p2) = line_points(&entities[0]);
+        assert_point((0.0, 0.0, -5.0), p1);
+        assert_point((-1.0, 0.0, -5.0), p2);
+    }
+
+    #[test]
+    fn explode_minsert() {
+        let drawing = drawing_with_block("b", Point::origin(), vec![line((0.0, 0.0), (1.0, 0.0))]);
+        let insert = Insert {
+            name: String::from("b"),
+            x_scale_factor: 3.0,
+            column_count: 2,
+            row_count: 3,
+            column_spacing: 5.0,
+            row_spacing: 10.0,
+            ..Default::default()
+        };
+        let entities = insert.explode(&drawing);
+        assert_eq!(6, entities.len());
+
+        // the spacing isn't scaled
+        let (p1, p2) = line_points(&entities[5]);
+        assert_point((5.0, 20.0, 0.0), p1);
+        assert_point((8.0, 20.0, 0.0), p2);
+    }
+
+    #[test]
+    fn explode_recursive_nested_blocks() {
+        let mut drawing =
+            drawing_with_block("inner", Point::origin(), vec![line((0.0, 0.0), (1.0, 0.0))]);
+        let nested = Entity::new(EntityType::Insert(Insert {
+            name: String::from("inner"),
+            location: Point::new(1.0, 0.0, 0.0),
+            rotation: 90.0,
+            ..Default::default()
+        }));
+        drawing.add_block(Block {
+            name: String::from("outer"),
+            entities: vec![nested],
+            ..Default::default()
+        });
+        let insert = Insert {
+            name: String::from("outer"),
+            location: Point::new(10.0, 0.0, 0.0),
+            x_scale_factor: 2.0,
+            ..Default::default()
+        };
+
+        // a single level keeps the nested insert
+        let entities = insert.explode(&drawing);
+        assert_eq!(1, entities.len());
+        match entities[0].specific {
+            EntityType::Insert(ref nested) => assert_point((12.0, 0.0, 0.0), &nested.location),
+            _ => panic!("expected an insert"),
+        }
+
+        // the non-uniform scale is applied after the nested rotation
+        let entities = insert.explode_recursive(&drawing);
+        assert_eq!(1, entities.len());
+        let (p1, p2) = line_points(&entities[0]);
+        assert_point((12.0, 0.0, 0.0), p1);
+        assert_point((12.0, 1.0, 0.0), p2);
+    }
+
+    #[test]
+    fn explode_self_referencing_block() {
+        let looping = Entity::new(EntityType::Insert(Insert {
+            name: String::from("loop"),
+            ..Default::default()
+        }));
+        let drawing = drawing_with_block(
+            "loop",
+            Point::origin(),
+            vec![line((0.0, 0.0), (1.0, 0.0)), looping],
+        );
+        let insert = Insert {
+            name: String::from("loop"),
+            ..Default::default()
+        };
+        assert_eq!(32, insert.explode_recursive(&drawing).len());
+    }
+
+    #[test]
+    fn explode_resolves_by_block_properties() {
+        let mut by_block = line((0.0, 0.0), (1.0, 0.0));
+        by_block.common.layer = String::from("0");
+        by_block.common.color = Color::by_block();
+        by_block.common.line_type_name = String::from("ByBlock");
+        by_block.common.lineweight_enum_value = LineWeight::by_block().raw_value();
+        by_block.common.transparency = Transparency::by_block();
+        let mut explicit = line((0.0, 0.0), (1.0, 0.0));
+        explicit.common.layer = String::from("other");
+        explicit.common.color = Color::from_index(3);
+        let drawing = drawing_with_block("b", Point::origin(), vec![by_block, explicit]);
+
+        let mut insert = Entity::new(EntityType::Insert(Insert {
+            name: String::from("b"),
+            ..Default::default()
+        }));
+        insert.common.layer = String::from("red");
+        insert.common.color = Color::from_index(1);
+        insert.common.line_type_name = String::from("DASHED");
+        insert.common.lineweight_enum_value = 50;
+        insert.common.transparency = Transparency::from_alpha(0x40);
+
+        let entities = insert.explode(&drawing).unwrap();
+        assert_eq!(2, entities.len());
+        assert_eq!("red", entities[0].common.layer);
+        assert_eq!(Some(1), entities[0].common.color.index());
+        assert_eq!("DASHED", entities[0].common.line_type_name);
+        assert_eq!(50, entities[0].common.lineweight_enum_value);
+        assert_eq!(Some(0x40), entities[0].common.transparency.alpha());
+        assert_eq!("other", entities[1].common.layer);
+        assert_eq!(Some(3), entities[1].common.color.index());
+
+        assert!(Entity::new(EntityType::Line(Line::default()))
+            .explode(&drawing)
+            .is_none());
+    }
+
+    #[test]
+    fn explode_attributes() {
+        let constant = Entity::new(EntityType::AttributeDefinition(AttributeDefinition {
+            value: String::from("constant"),
+            flags: 2,
+            ..Default::default()
+        }));
+        let variable = Entity::new(EntityType::AttributeDefinition(AttributeDefinition {
+            text_tag: String::from("TAG"),
+            ..Default::default()
+        }));
+        let mut drawing = drawing_with_block("b", Point::origin(), vec![constant, variable]);
+
+        let mut insert = Insert {
+            name: String::from("b"),
+            location: Point::new(5.0, 0.0, 0.0),
+            ..Default::default()
+        };
+        insert.add_attribute(
+            &mut drawing,
+            Attribute {
+                value: String::from("visible"),
+                location: Point::new(1.0, 2.0, 0.0),
+                ..Default::default()
+            },
+        );
+        insert.add_attribute(
+            &mut drawing,
+            Attribute {
+                value: String::from("hidden"),
+                flags: 1,
+                ..Default::default()
+            },
+        );
+
+        let entities = insert.explode(&drawing);
+        let texts = entities
+            .iter()
+            .map(|e| match e.specific {
+                EntityType::Text(ref text) => text,
+                _ => panic!("expected text"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(2, texts.len());
+        assert_eq!("constant", texts[0].value);
+        assert_point((5.0, 0.0, 0.0), &texts[0].location);
+
+        // attributes are already in place
+        assert_eq!("visible", texts[1].value);
+        assert_point((1.0, 2.0, 0.0), &texts[1].location);
+    }
+}
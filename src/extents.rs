@@ -0,0 +1,1042 @@
+// other implementation is in `entity.rs` and `block.rs`
+
+use crate::bounding_box::{offset_point, ExtentsBuilder};
+use crate::entities::*;
+use crate::enums::*;
+use crate::{
+    Block, BoundingBox, Drawing, HatchBoundaryPath, HatchEdge, ObjectCoordinateSystem, Point,
+    Vector,
+};
+
+/// The approximate ratio of a character's width to the text height.  The actual width depends on the font, which isn't
+/// available.
+const CHARACTER_WIDTH_FACTOR: f64 = 1.0;
+
+/// The distance between lines of `MText` relative to the text height when the line spacing factor is 1.
+const MTEXT_LINE_SPACING: f64 = 5.0 / 3.0;
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Returns the extents of the entity in world coordinates, or `None` if the entity is unbounded (e.g., `Ray` and
+    /// `XLine`) or has no geometry that can be measured (e.g., `Solid3D` and `Region` whose shape is stored as opaque
+    /// ACIS data).
+    ///
+    /// Arcs, circles, ellipses, and bulged polyline segments are measured exactly, splines use the hull of their
+    /// control points (which always contains the curve), and text is approximated from its height and character count
+    /// since font metrics aren't available.  The `drawing` is used to find the blocks referenced by `Insert` and
+    /// dimension entities.
+    pub fn bounding_box(&self, drawing: &Drawing) -> Option<BoundingBox> {
+        self.extents(drawing, 0)
+    }
+}
+
+// internal visibility only
+impl Entity {
+    pub(crate) fn extents(&self, drawing: &Drawing, depth: usize) -> Option<BoundingBox> {
+        let mut b = ExtentsBuilder::default();
+        match self.specific {
+            EntityType::Face3D(ref face) => {
+                b.points([
+                    &face.first_corner,
+                    &face.second_corner,
+                    &face.third_corner,
+                    &face.fourth_corner,
+                ]);
+            }
+            EntityType::Arc(ref arc) => {
+                circle_extents(
+                    &mut b,
+                    &arc.center,
+                    arc.radius,
+                    &arc.normal,
+                    arc.start_angle.to_radians(),
+                    arc.end_angle.to_radians(),
+                );
+                extrude(&mut b, &arc.normal, arc.thickness);
+            }
+            EntityType::ArcAlignedText(ref text) => {
+                // the text is somewhere along the arc, so the whole circle it could occupy is used
+                let radius = text.arc_radius + text.offset_from_arc.abs() + text.text_height;
+                circle_extents(
+                    &mut b,
+                    &text.center_point,
+                    radius,
+                    &text.extrusion_direction,
+                    0.0,
+                    0.0,
+                );
+            }
+            EntityType::AttributeDefinition(ref att) => {
+                text_extents(
+                    &mut b,
+                    &TextGeometry {
+                        location: &att.location,
+                        second_alignment_point: &att.second_alignment_point,
+                        height: att.text_height,
+                        value: &att.value,
+                        rotation: att.rotation,
+                        x_scale: att.relative_x_scale_factor,
+                        horizontal: att.horizontal_text_justification,
+                        vertical: att.vertical_text_justification,
+                        normal: &att.normal,
+                    },
+                );
+                extrude(&mut b, &att.normal, att.thickness);
+            }
+            EntityType::Attribute(ref att) => {
+                attribute_extents(&mut b, att);
+            }
+            EntityType::Circle(ref circle) => {
+                circle_extents(
+                    &mut b,
+                    &circle.center,
+                    circle.radius,
+                    &circle.normal,
+                    0.0,
+                    0.0,
+                );
+                extrude(&mut b, &circle.normal, circle.thickness);
+            }
+            EntityType::RotatedDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.points([
+                    &dim.insertion_point,
+                    &dim.definition_point_2,
+                    &dim.definition_point_3,
+                ]);
+            }
+            EntityType::RadialDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.point(&dim.definition_point_2);
+            }
+            EntityType::DiameterDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.point(&dim.definition_point_2);
+            }
+            EntityType::AngularThreePointDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.points([
+                    &dim.definition_point_2,
+                    &dim.definition_point_3,
+                    &dim.definition_point_4,
+                    &dim.definition_point_5,
+                ]);
+            }
+            EntityType::OrdinateDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.points([&dim.definition_point_2, &dim.definition_point_3]);
+            }
+            EntityType::ArcDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.points([
+                    &dim.definition_point_2,
+                    &dim.definition_point_3,
+                    &dim.arc_center,
+                ]);
+                if dim.has_leader {
+                    b.points([&dim.leader_point_1, &dim.leader_point_2]);
+                }
+            }
+            EntityType::JoggedRadialDimension(ref dim) => {
+                dimension_extents(&mut b, drawing, depth, &dim.dimension_base);
+                b.points([&dim.definition_point_2, &dim.jog_point]);
+            }
+            EntityType::Ellipse(ref ellipse) => {
+                let minor_axis = ellipse
+                    .normal
+                    .normalized()
+                    .cross(&ellipse.major_axis)
+                    .scaled(ellipse.minor_axis_ratio);
+                b.elliptic_arc(
+                    &ellipse.center,
+                    &ellipse.major_axis,
+                    &minor_axis,
+                    ellipse.start_parameter,
+                    ellipse.end_parameter,
+                );
+            }
+            EntityType::Hatch(ref hatch) => {
+                let ocs = ObjectCoordinateSystem::new(&hatch.extrusion_direction);
+                for path in &hatch.boundary_paths {
+                    hatch_path_extents(&mut b, &ocs, hatch.elevation_point.z, path);
+                }
+            }
+            EntityType::Helix(ref helix) => {
+                let ocs = ObjectCoordinateSystem::new(&helix.axis_vector);
+                let height = helix.number_of_turns * helix.turn_height;
+                let top = offset_point(&helix.axis_base_point, ocs.normal(), height);
+                for center in [&helix.axis_base_point, &top] {
+                    b.elliptic_arc(
+                        center,
+                        &ocs.x_axis().scaled(helix.radius),
+                        &ocs.y_axis().scaled(helix.radius),
+                        0.0,
+                        0.0,
+                    );
+                }
+                b.point(&helix.start_point);
+            }
+            EntityType::Image(ref image) => {
+                image_extents(
+                    &mut b,
+                    &image.location,
+                    &image.u_vector,
+                    &image.v_vector,
+                    &image.image_size,
+                );
+            }
+            EntityType::Insert(ref insert) => {
+                if let Some(bbox) = insert.extents(drawing, depth) {
+                    b.bounding_box(&bbox);
+                }
+            }
+            EntityType::Leader(ref leader) => {
+                b.points(&leader.vertices);
+            }
+            EntityType::Line(ref line) => {
+                b.points([&line.p1, &line.p2]);
+                extrude(&mut b, &line.extrusion_direction, line.thickness);
+            }
+            EntityType::LwPolyline(ref poly) => {
+                let ocs = ObjectCoordinateSystem::new(&poly.extrusion_direction);
+                let elevation = self.common.elevation;
+                let vertices = &poly.vertices;
+                for (i, v) in vertices.iter().enumerate() {
+                    let next = match vertices.get(i + 1) {
+                        Some(next) => next,
+                        None if poly.is_closed() => &vertices[0],
+                        None => {
+                            b.point(&ocs.to_wcs(&Point::new(v.x, v.y, elevation)));
+                            continue;
+                        }
+                    };
+                    b.ocs_bulge_segment(&ocs, (v.x, v.y), (next.x, next.y), v.bulge, elevation);
+                }
+                extrude(&mut b, &poly.extrusion_direction, poly.thickness);
+            }
+            EntityType::Mesh(ref mesh) => {
+                b.points(&mesh.vertices);
+            }
+            EntityType::MLine(ref mline) => {
+                b.points(&mline.vertices);
+            }
+            EntityType::MLeader(ref mleader) => {
+                b.points(&mleader.get_vertices());
+            }
+            EntityType::MText(ref mtext) => {
+                mtext_extents(&mut b, mtext);
+            }
+            EntityType::ModelPoint(ref point) => {
+                b.point(&point.location);
+                extrude(&mut b, &point.extrusion_direction, point.thickness);
+            }
+            EntityType::Polyline(ref poly) => {
+                polyline_extents(&mut b, poly);
+            }
+            EntityType::RText(ref text) => {
+                b.point(&text.insertion_point);
+            }
+            EntityType::Shape(ref shape) => {
+                let ocs = ObjectCoordinateSystem::new(&shape.extrusion_direction);
+                let width = shape.size * shape.relative_x_scale_factor;
+                rectangle_extents(
+                    &mut b,
+                    &ocs,
+                    &shape.location,
+                    shape.rotation_angle.to_radians(),
+                    (0.0, width),
+                    (0.0, shape.size),
+                );
+                extrude(&mut b, &shape.extrusion_direction, shape.thickness);
+            }
+            EntityType::Solid(ref solid) => {
+                let ocs = ObjectCoordinateSystem::new(&solid.extrusion_direction);
+                for p in [
+                    &solid.first_corner,
+                    &solid.second_corner,
+                    &solid.third_corner,
+                    &solid.fourth_corner,
+                ] {
+                    b.point(&ocs.to_wcs(p));
+                }
+                extrude(&mut b, &solid.extrusion_direction, solid.thickness);
+            }
+            EntityType::Spline(ref spline) => {
+                if spline.control_points.is_empty() {
+                    b.points(&spline.fit_points);
+                } else {
+                    b.points(&spline.control_points);
+                }
+            }
+            EntityType::Table(ref table) => {
+                b.points(&table.corners());
+            }
+            EntityType::Text(ref text) => {
+                text_extents(
+                    &mut b,
+                    &TextGeometry {
+                        location: &text.location,
+                        second_alignment_point: &text.second_alignment_point,
+                        height: text.text_height,
+                        value: &text.value,
+                        rotation: text.rotation,
+                        x_scale: text.relative_x_scale_factor,
+                        horizontal: text.horizontal_text_justification,
+                        vertical: text.vertical_text_justification,
+                        normal: &text.normal,
+                    },
+                );
+                extrude(&mut b, &text.normal, text.thickness);
+            }
+            EntityType::Tolerance(ref tolerance) => {
+                b.point(&tolerance.insertion_point);
+            }
+            EntityType::Trace(ref trace) => {
+                let ocs = ObjectCoordinateSystem::new(&trace.extrusion_direction);
+                for p in [
+                    &trace.first_corner,
+                    &trace.second_corner,
+                    &trace.third_corner,
+                    &trace.fourth_corner,
+                ] {
+                    b.point(&ocs.to_wcs(p));
+                }
+                extrude(&mut b, &trace.extrusion_direction, trace.thickness);
+            }
+            EntityType::Vertex(ref vertex) => {
+                b.point(&vertex.location);
+            }
+            EntityType::Viewport(ref viewport) => {
+                let (dx, dy) = (viewport.width / 2.0, viewport.height / 2.0);
+                let c = &viewport.center;
+                b.point(&Point::new(c.x - dx, c.y - dy, c.z));
+                b.point(&Point::new(c.x + dx, c.y + dy, c.z));
+            }
+            EntityType::Wipeout(ref wipeout) => {
+                image_extents(
+                    &mut b,
+                    &wipeout.location,
+                    &wipeout.u_vector,
+                    &wipeout.v_vector,
+                    &wipeout.image_size,
+                );
+            }
+            EntityType::Ray(_) | EntityType::XLine(_) => (), // unbounded
+            EntityType::Solid3D(_)
+            | EntityType::ProxyEntity(_)
+            | EntityType::Body(_)
+            | EntityType::ExtrudedSurface(_)
+            | EntityType::LoftedSurface(_)
+            | EntityType::NurbSurface(_)
+            | EntityType::PlaneSurface(_)
+            | EntityType::RevolvedSurface(_)
+            | EntityType::SweptSurface(_)
+            | EntityType::Light(_)
+            | EntityType::OleFrame(_)
+            | EntityType::Ole2Frame(_)
+            | EntityType::Region(_)
+            | EntityType::Section(_)
+            | EntityType::Seqend(_)
+            | EntityType::DgnUnderlay(_)
+            | EntityType::DwfUnderlay(_)
+            | EntityType::PdfUnderlay(_)
+            | EntityType::Unknown(_) => (), // no measurable geometry
+        }
+
+        b.build()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Insert
+//------------------------------------------------------------------------------
+impl Insert {
+    /// Returns the extents of the referenced block as placed by this insert in world coordinates, honoring the scale
+    /// factors, rotation, extrusion direction, and row/column array, along with any attributes.  Returns `None` if the
+    /// block can't be found in `drawing` and there are no attributes.
+    pub fn bounding_box(&self, drawing: &Drawing) -> Option<BoundingBox> {
+        self.extents(drawing, 0)
+    }
+}
+
+// internal visibility only
+impl Insert {
+    pub(crate) fn extents(&self, drawing: &Drawing, depth: usize) -> Option<BoundingBox> {
+        let mut b = ExtentsBuilder::default();
+        if let Some(block) = Block::find_nested(drawing, &self.name, depth + 1) {
+            if let Some(block_box) = block.extents(drawing, depth + 1) {
+                let ocs = ObjectCoordinateSystem::new(&self.extrusion_direction);
+                let (sin, cos) = self.rotation.to_radians().sin_cos();
+                let last_column = f64::from(self.column_count.max(1) - 1);
+                let last_row = f64::from(self.row_count.max(1) - 1);
+
+                // the array offsets are a translation, so the outermost cells bound all of the others
+                for column in [0.0, last_column] {
+                    for row in [0.0, last_row] {
+                        for corner in block_box.corners().iter() {
+                            let x = (corner.x - block.base_point.x) * self.x_scale_factor
+                                + column * self.column_spacing;
+                            let y = (corner.y - block.base_point.y) * self.y_scale_factor
+                                + row * self.row_spacing;
+                            let z = (corner.z - block.base_point.z) * self.z_scale_factor;
+                            b.point(&ocs.to_wcs(&Point::new(
+                                self.location.x + x * cos - y * sin,
+                                self.location.y + x * sin + y * cos,
+                                self.location.z + z,
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+
+        for att in self.attributes() {
+            attribute_extents(&mut b, att);
+        }
+
+        b.build()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Block
+//------------------------------------------------------------------------------
+impl Block {
+    /// Returns the extents of the block's entities in the block's own coordinates, i.e., before it is placed by an
+    /// `Insert`.  See `Entity::bounding_box()` for details.
+    pub fn bounding_box(&self, drawing: &Drawing) -> Option<BoundingBox> {
+        self.extents(drawing, 0)
+    }
+}
+
+// internal visibility only
+impl Block {
+    pub(crate) fn extents(&self, drawing: &Drawing, depth: usize) -> Option<BoundingBox> {
+        let mut b = ExtentsBuilder::default();
+        for entity in &self.entities {
+            if let Some(bbox) = entity.extents(drawing, depth) {
+                b.bounding_box(&bbox);
+            }
+        }
+
+        b.build()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+fn extrude(b: &mut ExtentsBuilder, normal: &Vector, thickness: f64) {
+    if thickness != 0.0 {
+        b.extrude(&normal.normalized().scaled(thickness));
+    }
+}
+
+/// Adds a circle or arc whose center is in the object coordinate system given by `normal`.  Angles are in radians;
+/// equal angles make a full circle.
+fn circle_extents(
+    b: &mut ExtentsBuilder,
+    center: &Point,
+    radius: f64,
+    normal: &Vector,
+    start: f64,
+    end: f64,
+) {
+    let ocs = ObjectCoordinateSystem::new(normal);
+    b.elliptic_arc(
+        &ocs.to_wcs(center),
+        &ocs.x_axis().scaled(radius),
+        &ocs.y_axis().scaled(radius),
+        start,
+        end,
+    );
+}
+
+/// Adds the rectangle spanning `x_range` and `y_range` relative to `origin`, rotated by `rotation` radians, all in the
+/// object coordinate system `ocs`.
+fn rectangle_extents(
+    b: &mut ExtentsBuilder,
+    ocs: &ObjectCoordinateSystem,
+    origin: &Point,
+    rotation: f64,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+) {
+    let (sin, cos) = rotation.sin_cos();
+    for x in [x_range.0, x_range.1] {
+        for y in [y_range.0, y_range.1] {
+            b.point(&ocs.to_wcs(&Point::new(
+                origin.x + x * cos - y * sin,
+                origin.y + x * sin + y * cos,
+                origin.z,
+            )));
+        }
+    }
+}
+
+/// The values shared by `Text`, `Attribute`, and `AttributeDefinition` that determine where the text is drawn.
+struct TextGeometry<'a> {
+    location: &'a Point,
+    second_alignment_point: &'a Point,
+    height: f64,
+    value: &'a str,
+    rotation: f64,
+    x_scale: f64,
+    horizontal: HorizontalTextJustification,
+    vertical: VerticalTextJustification,
+    normal: &'a Vector,
+}
+
+fn text_extents(b: &mut ExtentsBuilder, text: &TextGeometry) {
+    let ocs = ObjectCoordinateSystem::new(text.normal);
+    let height = text.height;
+    let width = text.value.chars().count() as f64 * height * CHARACTER_WIDTH_FACTOR * text.x_scale;
+    match text.horizontal {
+        HorizontalTextJustification::Aligned | HorizontalTextJustification::Fit => {
+            // the text is stretched between the two alignment points
+            let (start, end) = (text.location, text.second_alignment_point);
+            let (dx, dy) = (end.x - start.x, end.y - start.y);
+            let length = (dx * dx + dy * dy).sqrt();
+            rectangle_extents(b, &ocs, start, dy.atan2(dx), (0.0, length), (0.0, height));
+        }
+        _ => {
+            let x_range = match text.horizontal {
+                HorizontalTextJustification::Center | HorizontalTextJustification::Middle => {
+                    (-width / 2.0, width / 2.0)
+                }
+                HorizontalTextJustification::Right => (-width, 0.0),
+                _ => (0.0, width),
+            };
+            let y_range = match (text.horizontal, text.vertical) {
+                (HorizontalTextJustification::Middle, _)
+                | (_, VerticalTextJustification::Middle) => (-height / 2.0, height / 2.0),
+                (_, VerticalTextJustification::Top) => (-height, 0.0),
+                _ => (0.0, height),
+            };
+
+            // only left/baseline text is positioned by the first alignment point
+            let is_default_justification = text.horizontal == HorizontalTextJustification::Left
+                && text.vertical == VerticalTextJustification::Baseline;
+            let origin = if is_default_justification {
+                text.location
+            } else {
+                text.second_alignment_point
+            };
+            rectangle_extents(
+                b,
+                &ocs,
+                origin,
+                text.rotation.to_radians(),
+                x_range,
+                y_range,
+            );
+        }
+    }
+}
+
+fn attribute_extents(b: &mut ExtentsBuilder, att: &Attribute) {
+    text_extents(
+        b,
+        &TextGeometry {
+            location: &att.location,
+            second_alignment_point: &att.second_alignment_point,
+            height: att.text_height,
+            value: &att.value,
+            rotation: att.rotation,
+            x_scale: att.relative_x_scale_factor,
+            horizontal: att.horizontal_text_justification,
+            vertical: att.vertical_text_justification,
+            normal: &att.normal,
+        },
+    );
+    extrude(b, &att.normal, att.thickness);
+}
+
+fn mtext_extents(b: &mut ExtentsBuilder, mtext: &MText) {
+    let mut full_text = mtext.extended_text.concat();
+    full_text.push_str(&mtext.text);
+    let lines = full_text
+        .split("\\P")
+        .flat_map(|l| l.split('\n'))
+        .collect::<Vec<_>>();
+    let height = mtext.initial_text_height;
+    let longest_line = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let width = if mtext.reference_rectangle_width > 0.0 {
+        mtext.reference_rectangle_width
+    } else {
+        longest_line as f64 * height * CHARACTER_WIDTH_FACTOR
+    };
+    let line_spacing_factor = if mtext.line_spacing_factor > 0.0 {
+        mtext.line_spacing_factor
+    } else {
+        1.0
+    };
+    let total_height = height
+        + (lines.len().max(1) - 1) as f64 * height * MTEXT_LINE_SPACING * line_spacing_factor;
+
+    // the x-axis direction takes precedence over the rotation angle, which is in radians
+    let normal = mtext.extrusion_direction.normalized();
+    let x_direction = if mtext.rotation_angle != 0.0 && mtext.x_axis_direction == Vector::x_axis() {
+        let (sin, cos) = mtext.rotation_angle.sin_cos();
+        ObjectCoordinateSystem::new(&normal).vector_to_wcs(&Vector::new(cos, sin, 0.0))
+    } else {
+        mtext.x_axis_direction.normalized()
+    };
+    let y_direction = normal.cross(&x_direction).normalized();
+
+    let x_range = match mtext.attachment_point {
+        AttachmentPoint::TopLeft | AttachmentPoint::MiddleLeft | AttachmentPoint::BottomLeft => {
+            (0.0, width)
+        }
+        AttachmentPoint::TopCenter
+        | AttachmentPoint::MiddleCenter
+        | AttachmentPoint::BottomCenter => (-width / 2.0, width / 2.0),
+        AttachmentPoint::TopRight | AttachmentPoint::MiddleRight | AttachmentPoint::BottomRight => {
+            (-width, 0.0)
+        }
+    };
+    let y_range = match mtext.attachment_point {
+        AttachmentPoint::TopLeft | AttachmentPoint::TopCenter | AttachmentPoint::TopRight => {
+            (-total_height, 0.0)
+        }
+        AttachmentPoint::MiddleLeft
+        | AttachmentPoint::MiddleCenter
+        | AttachmentPoint::MiddleRight => (-total_height / 2.0, total_height / 2.0),
+        AttachmentPoint::BottomLeft
+        | AttachmentPoint::BottomCenter
+        | AttachmentPoint::BottomRight => (0.0, total_height),
+    };
+    for x in [x_range.0, x_range.1] {
+        for y in [y_range.0, y_range.1] {
+            let p = offset_point(&mtext.insertion_point, &x_direction, x);
+            b.point(&offset_point(&p, &y_direction, y));
+        }
+    }
+}
+
+fn polyline_extents(b: &mut ExtentsBuilder, poly: &Polyline) {
+    if poly.is_3d_polyline() || poly.is_3d_polygon_mesh() || poly.is_polyface_mesh() {
+        // vertices are in world coordinates
+        for vertex in poly.vertices() {
+            let is_face_record = vertex.is_polyface_mesh_vertex() && !vertex.is_3d_polygon_mesh();
+            if !is_face_record {
+                b.point(&vertex.location);
+            }
+        }
+    } else {
+        let ocs = ObjectCoordinateSystem::new(&poly.normal);
+        let elevation = poly.location.z;
+        let vertices = poly.vertices().collect::<Vec<_>>();
+        for (i, v) in vertices.iter().enumerate() {
+            let (x, y) = (v.location.x, v.location.y);
+            let next = match vertices.get(i + 1) {
+                Some(next) => next,
+                None if poly.is_closed() => &vertices[0],
+                None => {
+                    b.point(&ocs.to_wcs(&Point::new(x, y, elevation)));
+                    continue;
+                }
+            };
+            b.ocs_bulge_segment(
+                &ocs,
+                (x, y),
+                (next.location.x, next.location.y),
+                v.bulge,
+                elevation,
+            );
+        }
+        extrude(b, &poly.normal, poly.thickness);
+    }
+}
+
+fn dimension_extents(
+    b: &mut ExtentsBuilder,
+    drawing: &Drawing,
+    depth: usize,
+    dimension_base: &DimensionBase,
+) {
+    // the anonymous block holds the dimension's rendered geometry in world coordinates
+    if !dimension_base.block_name.is_empty() {
+        if let Some(block) = Block::find_nested(drawing, &dimension_base.block_name, depth + 1) {
+            if let Some(bbox) = block.extents(drawing, depth + 1) {
+                b.bounding_box(&bbox);
+            }
+        }
+    }
+
+    b.points([
+        &dimension_base.definition_point_1,
+        &dimension_base.text_mid_point,
+    ]);
+}
+
+fn image_extents(
+    b: &mut ExtentsBuilder,
+    location: &Point,
+    u_vector: &Vector,
+    v_vector: &Vector,
+    image_size: &Vector,
+) {
+    // the u and v vectors are the size of a single pixel
+    let right = offset_point(location, u_vector, image_size.x);
+    b.point(location);
+    b.point(&right);
+    b.point(&offset_point(location, v_vector, image_size.y));
+    b.point(&offset_point(&right, v_vector, image_size.y));
+}
+
+fn hatch_path_extents(
+    b: &mut ExtentsBuilder,
+    ocs: &ObjectCoordinateSystem,
+    elevation: f64,
+    path: &HatchBoundaryPath,
+) {
+    let vertices = &path.vertices;
+    for (i, v) in vertices.iter().enumerate() {
+        let next = match vertices.get(i + 1) {
+            Some(next) => next,
+            None if path.is_closed => &vertices[0],
+            None => {
+                b.point(&ocs.to_wcs(&Point::new(v.x, v.y, elevation)));
+                continue;
+            }
+        };
+        b.ocs_bulge_segment(ocs, (v.x, v.y), (next.x, next.y), v.bulge, elevation);
+    }
+
+    for edge in &path.edges {
+        match edge {
+            HatchEdge::Line { start, end } => {
+                b.point(&ocs.to_wcs(&Point::new(start.x, start.y, elevation)));
+                b.point(&ocs.to_wcs(&Point::new(end.x, end.y, elevation)));
+            }
+            HatchEdge::CircularArc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                let (start, end) = hatch_arc_range(*start_angle, *end_angle, *is_counter_clockwise);
+                b.elliptic_arc(
+                    &ocs.to_wcs(&Point::new(center.x, center.y, elevation)),
+                    &ocs.x_axis().scaled(*radius),
+                    &ocs.y_axis().scaled(*radius),
+                    start,
+                    end,
+                );
+            }
+            HatchEdge::EllipticArc {
+                center,
+                major_axis,
+                minor_axis_ratio,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                let (start, end) = hatch_arc_range(*start_angle, *end_angle, *is_counter_clockwise);
+                let u = ocs.vector_to_wcs(&Vector::new(major_axis.x, major_axis.y, 0.0));
+                let v = ocs.normal().cross(&u).scaled(*minor_axis_ratio);
+                b.elliptic_arc(
+                    &ocs.to_wcs(&Point::new(center.x, center.y, elevation)),
+                    &u,
+                    &v,
+                    start,
+                    end,
+                );
+            }
+            HatchEdge::Spline {
+                control_points,
+                fit_points,
+                ..
+            } => {
+                let points = if control_points.is_empty() {
+                    fit_points
+                } else {
+                    control_points
+                };
+                for p in points {
+                    b.point(&ocs.to_wcs(&Point::new(p.x, p.y, elevation)));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the counter-clockwise range, in radians, covered by a hatch arc edge whose angles are in degrees.  Clockwise
+/// edges store their angles mirrored.
+fn hatch_arc_range(start_angle: f64, end_angle: f64, is_counter_clockwise: bool) -> (f64, f64) {
+    if is_counter_clockwise {
+        (start_angle.to_radians(), end_angle.to_radians())
+    } else {
+        ((-end_angle).to_radians(), (-start_angle).to_radians())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_box(
+        expected_min: (f64, f64, f64),
+        expected_max: (f64, f64, f64),
+        bbox: Option<BoundingBox>,
+    ) {
+        let bbox = bbox.expect("expected a bounding box");
+        let close = |a: (f64, f64, f64), b: (f64, f64, f64)| {
+            (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9 && (a.2 - b.2).abs() < 1e-9
+        };
+        assert!(
+            close(expected_min, bbox.min.tuple()) && close(expected_max, bbox.max.tuple()),
+            "expected {:?} - {:?}, got {:?}",
+            expected_min,
+            expected_max,
+            bbox
+        );
+    }
+
+    fn extents_of(specific: EntityType) -> Option<BoundingBox> {
+        Entity::new(specific).bounding_box(&Drawing::new())
+    }
+
+    #[test]
+    fn line_extents() {
+        let line = Line::new(Point::new(3.0, 1.0, 0.0), Point::new(1.0, 2.0, 5.0));
+        assert_box(
+            (1.0, 1.0, 0.0),
+            (3.0, 2.0, 5.0),
+            extents_of(EntityType::Line(line)),
+        );
+    }
+
+    #[test]
+    fn line_with_thickness_extents() {
+        let mut line = Line::new(Point::new(0.0, 0.0, 0.0), Point::new(1.0, 0.0, 0.0));
+        line.thickness = 2.0;
+        assert_box(
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 2.0),
+            extents_of(EntityType::Line(line)),
+        );
+    }
+
+    #[test]
+    fn arc_extents() {
+        // from 0 to 90 degrees only covers the first quadrant
+        let arc = Arc::new(Point::new(1.0, 1.0, 0.0), 2.0, 0.0, 90.0);
+        assert_box(
+            (1.0, 1.0, 0.0),
+            (3.0, 3.0, 0.0),
+            extents_of(EntityType::Arc(arc)),
+        );
+
+        // from 45 to 135 degrees reaches the top of the circle but neither side
+        let arc = Arc::new(Point::origin(), 1.0, 45.0, 135.0);
+        let half = 0.5f64.sqrt();
+        assert_box(
+            (-half, half, 0.0),
+            (half, 1.0, 0.0),
+            extents_of(EntityType::Arc(arc)),
+        );
+    }
+
+    #[test]
+    fn circle_with_flipped_normal_extents() {
+        // with a normal of -Z the OCS x-axis points toward -X
+        let mut circle = Circle::new(Point::new(5.0, 0.0, 0.0), 1.0);
+        circle.normal = Vector::new(0.0, 0.0, -1.0);
+        assert_box(
+            (-6.0, -1.0, 0.0),
+            (-4.0, 1.0, 0.0),
+            extents_of(EntityType::Circle(circle)),
+        );
+    }
+
+    #[test]
+    fn rotated_ellipse_extents() {
+        let ellipse = Ellipse {
+            center: Point::origin(),
+            major_axis: Vector::new(0.0, 2.0, 0.0),
+            minor_axis_ratio: 0.5,
+            ..Default::default()
+        };
+        assert_box(
+            (-1.0, -2.0, 0.0),
+            (1.0, 2.0, 0.0),
+            extents_of(EntityType::Ellipse(ellipse)),
+        );
+    }
+
+    #[test]
+    fn lw_polyline_with_bulge_extents() {
+        let mut poly = LwPolyline::default();
+        poly.vertices.push(LwPolylineVertex {
+            x: 0.0,
+            y: 0.0,
+            bulge: -1.0,
+            ..Default::default()
+        });
+        poly.vertices.push(LwPolylineVertex {
+            x: 2.0,
+            y: 0.0,
+            ..Default::default()
+        });
+        assert_box(
+            (0.0, 0.0, 0.0),
+            (2.0, 1.0, 0.0),
+            extents_of(EntityType::LwPolyline(poly)),
+        );
+    }
+
+    #[test]
+    fn text_extents() {
+        let text = Text {
+            location: Point::new(1.0, 1.0, 0.0),
+            text_height: 2.0,
+            value: String::from("abc"),
+            ..Default::default()
+        };
+        assert_box(
+            (1.0, 1.0, 0.0),
+            (7.0, 3.0, 0.0),
+            extents_of(EntityType::Text(text)),
+        );
+    }
+
+    #[test]
+    fn unbounded_entities_have_no_extents() {
+        assert!(extents_of(EntityType::XLine(XLine::default())).is_none());
+    }
+
+    fn drawing_with_square_block() -> Drawing {
+        let mut drawing = Drawing::new();
+        let mut block = Block {
+            name: String::from("square"),
+            base_point: Point::new(1.0, 1.0, 0.0),
+            ..Default::default()
+        };
+        block.entities.push(Entity::new(EntityType::Line(Line::new(
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(2.0, 2.0, 0.0),
+        ))));
+        drawing.add_block(block);
+        drawing
+    }
+
+    #[test]
+    fn insert_extents() {
+        let drawing = drawing_with_square_block();
+        let insert = Insert {
+            name: String::from("square"),
+            location: Point::new(10.0, 10.0, 0.0),
+            x_scale_factor: 2.0,
+            rotation: 90.0,
+            ..Default::default()
+        };
+
+        // the block's (1,1)-(2,2) is moved to the origin, stretched along X, rotated, then moved
+        assert_box(
+            (9.0, 10.0, 0.0),
+            (10.0, 12.0, 0.0),
+            insert.bounding_box(&drawing),
+        );
+    }
+
+    #[test]
+    fn minsert_extents() {
+        let drawing = drawing_with_square_block();
+        let insert = Insert {
+            name: String::from("square"),
+            column_count: 3,
+            row_count: 2,
+            column_spacing: 5.0,
+            row_spacing: 10.0,
+            ..Default::default()
+        };
+        assert_box(
+            (0.0, 0.0, 0.0),
+            (11.0, 11.0, 0.0),
+            insert.bounding_box(&drawing),
+        );
+    }
+
+    #[test]
+    fn self_referencing_block_extents() {
+        let mut drawing = Drawing::new();
+        let mut block = Block {
+            name: String::from("loop"),
+            ..Default::default()
+        };
+        block.entities.push(Entity::new(EntityType::Insert(Insert {
+            name: String::from("loop"),
+            ..Default::default()
+        })));
+        block.entities.push(Entity::new(EntityType::Line(Line::new(
+            Point::origin(),
+            Point::new(1.0, 1.0, 0.0),
+        ))));
+        drawing.add_block(block);
+
+        let block = drawing.blocks().find(|b| b.name == "loop").unwrap();
+        assert_box(
+            (0.0, 0.0, 0.0),
+            (1.0, 1.0, 0.0),
+            block.bounding_box(&drawing),
+        );
+    }
+
+    #[test]
+    fn update_drawing_extents() {
+        let mut drawing = drawing_with_square_block();
+        drawing.add_entity(Entity::new(EntityType::Insert(Insert {
+            name: String::from("square"),
+            location: Point::new(-5.0, 0.0, 0.0),
+            ..Default::default()
+        })));
+        drawing.add_entity(Entity::new(EntityType::Circle(Circle::new(
+            Point::new(3.0, 3.0, 0.0),
+            1.0,
+        ))));
+        let mut paper_space_line = Entity::new(EntityType::Line(Line::new(
+            Point::new(100.0, 100.0, 0.0),
+            Point::new(200.0, 200.0, 0.0),
+        )));
+        paper_space_line.common.is_in_paper_space = true;
+        drawing.add_entity(paper_space_line);
+
+        drawing.update_extents();
+        assert_eq!(
+            Point::new(-5.0, 0.0, 0.0),
+            drawing.header.minimum_drawing_extents
+        );
+        assert_eq!(
+            Point::new(4.0, 4.0, 0.0),
+            drawing.header.maximum_drawing_extents
+        );
+        assert_eq!(
+            Point::new(100.0, 100.0, 0.0),
+            drawing.header.paperspace_minimum_drawing_extents
+        );
+        assert_eq!(
+            Point::new(200.0, 200.0, 0.0),
+            drawing.header.paperspace_maximum_drawing_extents
+        );
+    }
+
+    #[test]
+    fn update_empty_drawing_extents() {
+        let mut drawing = Drawing::new();
+        drawing.update_extents();
+        assert_eq!(
+            Point::new(1.0e20, 1.0e20, 1.0e20),
+            drawing.header.minimum_drawing_extents
+        );
+        assert_eq!(
+            Point::new(-1.0e20, -1.0e20, -1.0e20),
+            drawing.header.maximum_drawing_extents
+        );
+    }
+}
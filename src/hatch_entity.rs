@@ -0,0 +1,816 @@
+// other implementation is in `generated/entities.rs`
+
+use enum_primitive::FromPrimitive;
+
+use crate::{CodePair, Color, DxfError, DxfResult, Handle, Point, Vector};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::entities::*;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+const BOUNDARY_PATH_EXTERNAL: i32 = 1;
+const BOUNDARY_PATH_POLYLINE: i32 = 2;
+const BOUNDARY_PATH_DERIVED: i32 = 4;
+const BOUNDARY_PATH_TEXTBOX: i32 = 8;
+const BOUNDARY_PATH_OUTERMOST: i32 = 16;
+
+//------------------------------------------------------------------------------
+//                                                             HatchBoundaryPath
+//------------------------------------------------------------------------------
+/// Represents a single boundary path of a `Hatch`.  A path is either a polyline (when `is_polyline()`
+/// is `true`) described by `vertices`, or a collection of `edges`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HatchBoundaryPath {
+    pub flags: i32,
+    /// Only used for polyline paths.
+    pub is_closed: bool,
+    /// Only used for polyline paths.
+    pub vertices: Vec<HatchPolylineVertex>,
+    /// Only used for non-polyline paths.
+    pub edges: Vec<HatchEdge>,
+    /// Handles of the source boundary objects when the hatch is associative.
+    pub source_boundary_handles: Vec<Handle>,
+}
+
+impl HatchBoundaryPath {
+    /// Creates a new polyline boundary path.
+    pub fn from_vertices(vertices: Vec<HatchPolylineVertex>, is_closed: bool) -> Self {
+        HatchBoundaryPath {
+            flags: BOUNDARY_PATH_EXTERNAL | BOUNDARY_PATH_POLYLINE,
+            is_closed,
+            vertices,
+            ..Default::default()
+        }
+    }
+    /// Creates a new boundary path made of the specified edges.
+    pub fn from_edges(edges: Vec<HatchEdge>) -> Self {
+        HatchBoundaryPath {
+            flags: BOUNDARY_PATH_EXTERNAL,
+            edges,
+            ..Default::default()
+        }
+    }
+    pub fn is_external(&self) -> bool {
+        self.flags & BOUNDARY_PATH_EXTERNAL != 0
+    }
+    pub fn set_is_external(&mut self, val: bool) {
+        self.set_flag(BOUNDARY_PATH_EXTERNAL, val);
+    }
+    pub fn is_polyline(&self) -> bool {
+        self.flags & BOUNDARY_PATH_POLYLINE != 0
+    }
+    pub fn set_is_polyline(&mut self, val: bool) {
+        self.set_flag(BOUNDARY_PATH_POLYLINE, val);
+    }
+    pub fn is_derived(&self) -> bool {
+        self.flags & BOUNDARY_PATH_DERIVED != 0
+    }
+    pub fn set_is_derived(&mut self, val: bool) {
+        self.set_flag(BOUNDARY_PATH_DERIVED, val);
+    }
+    pub fn is_text_box(&self) -> bool {
+        self.flags & BOUNDARY_PATH_TEXTBOX != 0
+    }
+    pub fn set_is_text_box(&mut self, val: bool) {
+        self.set_flag(BOUNDARY_PATH_TEXTBOX, val);
+    }
+    pub fn is_outermost(&self) -> bool {
+        self.flags & BOUNDARY_PATH_OUTERMOST != 0
+    }
+    pub fn set_is_outermost(&mut self, val: bool) {
+        self.set_flag(BOUNDARY_PATH_OUTERMOST, val);
+    }
+    fn set_flag(&mut self, mask: i32, val: bool) {
+        if val {
+            self.flags |= mask;
+        } else {
+            self.flags &= !mask;
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                           HatchPolylineVertex
+//------------------------------------------------------------------------------
+/// Represents a single vertex of a polyline `HatchBoundaryPath`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HatchPolylineVertex {
+    pub x: f64,
+    pub y: f64,
+    pub bulge: f64,
+}
+
+impl HatchPolylineVertex {
+    pub fn new(x: f64, y: f64, bulge: f64) -> Self {
+        HatchPolylineVertex { x, y, bulge }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                     HatchEdge
+//------------------------------------------------------------------------------
+/// Represents a single edge of a non-polyline `HatchBoundaryPath`.  All coordinates are in the
+/// hatch's object coordinate system.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum HatchEdge {
+    Line {
+        start: Point,
+        end: Point,
+    },
+    /// Start and end angles are in degrees.
+    CircularArc {
+        center: Point,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_counter_clockwise: bool,
+    },
+    /// The major axis is relative to the center; start and end angles are in degrees.
+    EllipticArc {
+        center: Point,
+        major_axis: Vector,
+        minor_axis_ratio: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_counter_clockwise: bool,
+    },
+    /// The edge is rational when `weights` is non-empty.  Fit data is only written for R2010 and later.
+    Spline {
+        degree: i32,
+        is_periodic: bool,
+        knot_values: Vec<f64>,
+        control_points: Vec<Point>,
+        weights: Vec<f64>,
+        fit_points: Vec<Point>,
+        start_tangent: Vector,
+        end_tangent: Vector,
+    },
+}
+
+impl HatchEdge {
+    fn edge_type(&self) -> i16 {
+        match self {
+            HatchEdge::Line { .. } => 1,
+            HatchEdge::CircularArc { .. } => 2,
+            HatchEdge::EllipticArc { .. } => 3,
+            HatchEdge::Spline { .. } => 4,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                    HatchPatternDefinitionLine
+//------------------------------------------------------------------------------
+/// Represents a single line of a `Hatch` pattern definition.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HatchPatternDefinitionLine {
+    /// Line angle in degrees.
+    pub angle: f64,
+    pub base_point: Point,
+    pub offset: Vector,
+    /// Positive values are dashes, negative values are spaces, and zero is a dot.
+    pub dash_lengths: Vec<f64>,
+}
+
+//------------------------------------------------------------------------------
+//                                                            HatchGradientColor
+//------------------------------------------------------------------------------
+/// Represents a single color of a gradient-filled `Hatch`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HatchGradientColor {
+    pub value: f64,
+    pub color: Color,
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Reader
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn apply_custom_reader_hatch(
+        common: &mut EntityCommon,
+        hatch: &mut Hatch,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        loop {
+            let pair = next_pair!(iter);
+            match pair.code {
+                2 => {
+                    hatch.pattern_name = pair.assert_string()?;
+                }
+                10 => {
+                    hatch.elevation_point.x = pair.assert_f64()?;
+                }
+                20 => {
+                    hatch.elevation_point.y = pair.assert_f64()?;
+                }
+                30 => {
+                    hatch.elevation_point.z = pair.assert_f64()?;
+                }
+                41 => {
+                    hatch.pattern_scale = pair.assert_f64()?;
+                }
+                47 => {
+                    hatch.pixel_size = pair.assert_f64()?;
+                }
+                52 => {
+                    hatch.pattern_angle = pair.assert_f64()?;
+                }
+                63 => {
+                    vec_last!(hatch.gradient_colors)
+                        .color
+                        .set_raw_value(pair.assert_i16()?);
+                }
+                70 => {
+                    hatch.is_solid_fill = as_bool(pair.assert_i16()?);
+                }
+                71 => {
+                    hatch.is_associative = as_bool(pair.assert_i16()?);
+                }
+                75 => {
+                    hatch.hatch_style =
+                        enum_from_number!(HatchStyle, OddParity, from_i16, pair.assert_i16()?);
+                }
+                76 => {
+                    hatch.pattern_type = enum_from_number!(
+                        HatchPatternType,
+                        Predefined,
+                        from_i16,
+                        pair.assert_i16()?
+                    );
+                }
+                77 => {
+                    hatch.is_pattern_double = as_bool(pair.assert_i16()?);
+                }
+                78 => {
+                    for _ in 0..pair.assert_i16()? {
+                        let line = read_pattern_definition_line(iter)?;
+                        hatch.pattern_definition_lines.push(line);
+                    }
+                }
+                91 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let path = read_boundary_path(iter)?;
+                        hatch.boundary_paths.push(path);
+                    }
+                }
+                98 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let x = expect_pair(iter, 10)?.assert_f64()?;
+                        let y = expect_pair(iter, 20)?.assert_f64()?;
+                        hatch.seed_points.push(Point::new(x, y, 0.0));
+                    }
+                }
+                210 => {
+                    hatch.extrusion_direction.x = pair.assert_f64()?;
+                }
+                220 => {
+                    hatch.extrusion_direction.y = pair.assert_f64()?;
+                }
+                230 => {
+                    hatch.extrusion_direction.z = pair.assert_f64()?;
+                }
+                421 => {
+                    vec_last!(hatch.gradient_colors)
+                        .color
+                        .set_true_color_raw_value(pair.assert_i32()?);
+                }
+                450 => {
+                    hatch.is_gradient = pair.assert_i32()? != 0;
+                }
+                451 => (), // reserved
+                452 => {
+                    hatch.is_single_color_gradient = pair.assert_i32()? != 0;
+                }
+                453 => (), // number of gradient colors; each color starts with a 463 pair
+                460 => {
+                    hatch.gradient_angle = pair.assert_f64()?;
+                }
+                461 => {
+                    hatch.gradient_shift = pair.assert_f64()?;
+                }
+                462 => {
+                    hatch.gradient_tint = pair.assert_f64()?;
+                }
+                463 => {
+                    hatch.gradient_colors.push(HatchGradientColor {
+                        value: pair.assert_f64()?,
+                        ..Default::default()
+                    });
+                }
+                470 => {
+                    hatch.gradient_name = pair.assert_string()?;
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+}
+
+fn read_boundary_path(iter: &mut CodePairPutBack) -> DxfResult<HatchBoundaryPath> {
+    let mut path = HatchBoundaryPath {
+        flags: expect_pair(iter, 92)?.assert_i32()?,
+        ..Default::default()
+    };
+    loop {
+        let pair = next_pair_or_err(iter)?;
+        match pair.code {
+            72 if path.is_polyline() => (), // has bulge flag; bulges are always read when present
+            73 if path.is_polyline() => {
+                path.is_closed = as_bool(pair.assert_i16()?);
+            }
+            93 if path.is_polyline() => {
+                for _ in 0..pair.assert_i32()? {
+                    path.vertices.push(read_polyline_vertex(iter)?);
+                }
+            }
+            93 => {
+                for _ in 0..pair.assert_i32()? {
+                    path.edges.push(read_edge(iter)?);
+                }
+            }
+            97 => {
+                for _ in 0..pair.assert_i32()? {
+                    let handle = expect_pair(iter, 330)?.as_handle()?;
+                    path.source_boundary_handles.push(handle);
+                }
+                break;
+            }
+            _ => {
+                iter.put_back(Ok(pair));
+                break;
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+fn read_polyline_vertex(iter: &mut CodePairPutBack) -> DxfResult<HatchPolylineVertex> {
+    let x = expect_pair(iter, 10)?.assert_f64()?;
+    let y = expect_pair(iter, 20)?.assert_f64()?;
+    let mut vertex = HatchPolylineVertex::new(x, y, 0.0);
+    let pair = next_pair_or_err(iter)?;
+    if pair.code == 42 {
+        vertex.bulge = pair.assert_f64()?;
+    } else {
+        iter.put_back(Ok(pair));
+    }
+
+    Ok(vertex)
+}
+
+fn read_edge(iter: &mut CodePairPutBack) -> DxfResult<HatchEdge> {
+    let pair = expect_pair(iter, 72)?;
+    let mut edge = match pair.assert_i16()? {
+        1 => HatchEdge::Line {
+            start: Point::origin(),
+            end: Point::origin(),
+        },
+        2 => HatchEdge::CircularArc {
+            center: Point::origin(),
+            radius: 0.0,
+            start_angle: 0.0,
+            end_angle: 360.0,
+            is_counter_clockwise: true,
+        },
+        3 => HatchEdge::EllipticArc {
+            center: Point::origin(),
+            major_axis: Vector::x_axis(),
+            minor_axis_ratio: 1.0,
+            start_angle: 0.0,
+            end_angle: 360.0,
+            is_counter_clockwise: true,
+        },
+        4 => HatchEdge::Spline {
+            degree: 3,
+            is_periodic: false,
+            knot_values: vec![],
+            control_points: vec![],
+            weights: vec![],
+            fit_points: vec![],
+            start_tangent: Vector::zero(),
+            end_tangent: Vector::zero(),
+        },
+        _ => return Err(DxfError::UnexpectedEnumValue(pair.offset)),
+    };
+    let mut has_read_fit_points = false;
+    loop {
+        let pair = next_pair_or_err(iter)?;
+        let handled = match edge {
+            HatchEdge::Line {
+                ref mut start,
+                ref mut end,
+            } => match pair.code {
+                10 => {
+                    start.x = pair.assert_f64()?;
+                    true
+                }
+                20 => {
+                    start.y = pair.assert_f64()?;
+                    true
+                }
+                11 => {
+                    end.x = pair.assert_f64()?;
+                    true
+                }
+                21 => {
+                    end.y = pair.assert_f64()?;
+                    true
+                }
+                _ => false,
+            },
+            HatchEdge::CircularArc {
+                ref mut center,
+                ref mut radius,
+                ref mut start_angle,
+                ref mut end_angle,
+                ref mut is_counter_clockwise,
+            } => match pair.code {
+                10 => {
+                    center.x = pair.assert_f64()?;
+                    true
+                }
+                20 => {
+                    center.y = pair.assert_f64()?;
+                    true
+                }
+                40 => {
+                    *radius = pair.assert_f64()?;
+                    true
+                }
+                50 => {
+                    *start_angle = pair.assert_f64()?;
+                    true
+                }
+                51 => {
+                    *end_angle = pair.assert_f64()?;
+                    true
+                }
+                73 => {
+                    *is_counter_clockwise = as_bool(pair.assert_i16()?);
+                    true
+                }
+                _ => false,
+            },
+            HatchEdge::EllipticArc {
+                ref mut center,
+                ref mut major_axis,
+                ref mut minor_axis_ratio,
+                ref mut start_angle,
+                ref mut end_angle,
+                ref mut is_counter_clockwise,
+            } => match pair.code {
+                10 => {
+                    center.x = pair.assert_f64()?;
+                    true
+                }
+                20 => {
+                    center.y = pair.assert_f64()?;
+                    true
+                }
+                11 => {
+                    major_axis.x = pair.assert_f64()?;
+                    true
+                }
+                21 => {
+                    major_axis.y = pair.assert_f64()?;
+                    true
+                }
+                40 => {
+                    *minor_axis_ratio = pair.assert_f64()?;
+                    true
+                }
+                50 => {
+                    *start_angle = pair.assert_f64()?;
+                    true
+                }
+                51 => {
+                    *end_angle = pair.assert_f64()?;
+                    true
+                }
+                73 => {
+                    *is_counter_clockwise = as_bool(pair.assert_i16()?);
+                    true
+                }
+                _ => false,
+            },
+            HatchEdge::Spline {
+                ref mut degree,
+                ref mut is_periodic,
+                ref mut knot_values,
+                ref mut control_points,
+                ref mut weights,
+                ref mut fit_points,
+                ref mut start_tangent,
+                ref mut end_tangent,
+            } => match pair.code {
+                94 => {
+                    *degree = pair.assert_i32()?;
+                    true
+                }
+                73 => true, // rational flag; implied by the presence of weights
+                74 => {
+                    *is_periodic = as_bool(pair.assert_i16()?);
+                    true
+                }
+                95 | 96 => true, // knot and control point counts
+                40 => {
+                    knot_values.push(pair.assert_f64()?);
+                    true
+                }
+                10 => {
+                    control_points.push(Point::new(pair.assert_f64()?, 0.0, 0.0));
+                    true
+                }
+                20 => {
+                    vec_last!(control_points).y = pair.assert_f64()?;
+                    true
+                }
+                42 => {
+                    weights.push(pair.assert_f64()?);
+                    true
+                }
+                // R2010+ always writes the fit point count after the weights; any later 97 is the
+                // boundary path's source object count
+                97 if iter.version() >= AcadVersion::R2010 && !has_read_fit_points => {
+                    for _ in 0..pair.assert_i32()? {
+                        let x = expect_pair(iter, 11)?.assert_f64()?;
+                        let y = expect_pair(iter, 21)?.assert_f64()?;
+                        fit_points.push(Point::new(x, y, 0.0));
+                    }
+                    has_read_fit_points = true;
+                    true
+                }
+                12 => {
+                    start_tangent.x = pair.assert_f64()?;
+                    true
+                }
+                22 => {
+                    start_tangent.y = pair.assert_f64()?;
+                    true
+                }
+                13 => {
+                    end_tangent.x = pair.assert_f64()?;
+                    true
+                }
+                23 => {
+                    end_tangent.y = pair.assert_f64()?;
+                    true
+                }
+                _ => false,
+            },
+        };
+        if !handled {
+            iter.put_back(Ok(pair));
+            break;
+        }
+    }
+
+    Ok(edge)
+}
+
+fn read_pattern_definition_line(
+    iter: &mut CodePairPutBack,
+) -> DxfResult<HatchPatternDefinitionLine> {
+    let mut line = HatchPatternDefinitionLine {
+        angle: expect_pair(iter, 53)?.assert_f64()?,
+        ..Default::default()
+    };
+    loop {
+        let pair = next_pair_or_err(iter)?;
+        match pair.code {
+            43 => {
+                line.base_point.x = pair.assert_f64()?;
+            }
+            44 => {
+                line.base_point.y = pair.assert_f64()?;
+            }
+            45 => {
+                line.offset.x = pair.assert_f64()?;
+            }
+            46 => {
+                line.offset.y = pair.assert_f64()?;
+            }
+            79 => (), // number of dash lengths
+            49 => {
+                line.dash_lengths.push(pair.assert_f64()?);
+            }
+            _ => {
+                iter.put_back(Ok(pair));
+                break;
+            }
+        }
+    }
+
+    Ok(line)
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Writer
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn add_custom_code_pairs_hatch(
+        pairs: &mut Vec<CodePair>,
+        hatch: &Hatch,
+        version: AcadVersion,
+    ) -> bool {
+        pairs.push(CodePair::new_str(100, "AcDbHatch"));
+        pairs.push(CodePair::new_f64(10, hatch.elevation_point.x));
+        pairs.push(CodePair::new_f64(20, hatch.elevation_point.y));
+        pairs.push(CodePair::new_f64(30, hatch.elevation_point.z));
+        pairs.push(CodePair::new_f64(210, hatch.extrusion_direction.x));
+        pairs.push(CodePair::new_f64(220, hatch.extrusion_direction.y));
+        pairs.push(CodePair::new_f64(230, hatch.extrusion_direction.z));
+        pairs.push(CodePair::new_string(2, &hatch.pattern_name));
+        pairs.push(CodePair::new_i16(70, as_i16(hatch.is_solid_fill)));
+        pairs.push(CodePair::new_i16(71, as_i16(hatch.is_associative)));
+        pairs.push(CodePair::new_i32(91, hatch.boundary_paths.len() as i32));
+        for path in &hatch.boundary_paths {
+            add_boundary_path_code_pairs(pairs, path, version);
+        }
+        pairs.push(CodePair::new_i16(75, hatch.hatch_style as i16));
+        pairs.push(CodePair::new_i16(76, hatch.pattern_type as i16));
+        if !hatch.is_solid_fill {
+            pairs.push(CodePair::new_f64(52, hatch.pattern_angle));
+            pairs.push(CodePair::new_f64(41, hatch.pattern_scale));
+            pairs.push(CodePair::new_i16(77, as_i16(hatch.is_pattern_double)));
+            pairs.push(CodePair::new_i16(
+                78,
+                hatch.pattern_definition_lines.len() as i16,
+            ));
+            for line in &hatch.pattern_definition_lines {
+                add_pattern_definition_line_code_pairs(pairs, line);
+            }
+        }
+        pairs.push(CodePair::new_f64(47, hatch.pixel_size));
+        pairs.push(CodePair::new_i32(98, hatch.seed_points.len() as i32));
+        for seed in &hatch.seed_points {
+            pairs.push(CodePair::new_f64(10, seed.x));
+            pairs.push(CodePair::new_f64(20, seed.y));
+        }
+        if version >= AcadVersion::R2004 && hatch.is_gradient {
+            pairs.push(CodePair::new_i32(450, 1));
+            pairs.push(CodePair::new_i32(451, 0));
+            pairs.push(CodePair::new_f64(460, hatch.gradient_angle));
+            pairs.push(CodePair::new_f64(461, hatch.gradient_shift));
+            pairs.push(CodePair::new_i32(
+                452,
+                i32::from(as_i16(hatch.is_single_color_gradient)),
+            ));
+            pairs.push(CodePair::new_f64(462, hatch.gradient_tint));
+            pairs.push(CodePair::new_i32(453, hatch.gradient_colors.len() as i32));
+            for color in &hatch.gradient_colors {
+                pairs.push(CodePair::new_f64(463, color.value));
+                if color.color.is_index() {
+                    pairs.push(CodePair::new_i16(63, color.color.raw_value()));
+                }
+                if let Some(v) = color.color.true_color_raw_value() {
+                    pairs.push(CodePair::new_i32(421, v));
+                }
+            }
+            pairs.push(CodePair::new_string(470, &hatch.gradient_name));
+        }
+
+        true
+    }
+}
+
+fn add_boundary_path_code_pairs(
+    pairs: &mut Vec<CodePair>,
+    path: &HatchBoundaryPath,
+    version: AcadVersion,
+) {
+    pairs.push(CodePair::new_i32(92, path.flags));
+    if path.is_polyline() {
+        let has_bulge = path.vertices.iter().any(|v| v.bulge != 0.0);
+        pairs.push(CodePair::new_i16(72, as_i16(has_bulge)));
+        pairs.push(CodePair::new_i16(73, as_i16(path.is_closed)));
+        pairs.push(CodePair::new_i32(93, path.vertices.len() as i32));
+        for vertex in &path.vertices {
+            pairs.push(CodePair::new_f64(10, vertex.x));
+            pairs.push(CodePair::new_f64(20, vertex.y));
+            if has_bulge {
+                pairs.push(CodePair::new_f64(42, vertex.bulge));
+            }
+        }
+    } else {
+        pairs.push(CodePair::new_i32(93, path.edges.len() as i32));
+        for edge in &path.edges {
+            add_edge_code_pairs(pairs, edge, version);
+        }
+    }
+    pairs.push(CodePair::new_i32(
+        97,
+        path.source_boundary_handles.len() as i32,
+    ));
+    for handle in &path.source_boundary_handles {
+        pairs.push(CodePair::new_string(330, &handle.as_string()));
+    }
+}
+
+fn add_edge_code_pairs(pairs: &mut Vec<CodePair>, edge: &HatchEdge, version: AcadVersion) {
+    pairs.push(CodePair::new_i16(72, edge.edge_type()));
+    match edge {
+        HatchEdge::Line { start, end } => {
+            pairs.push(CodePair::new_f64(10, start.x));
+            pairs.push(CodePair::new_f64(20, start.y));
+            pairs.push(CodePair::new_f64(11, end.x));
+            pairs.push(CodePair::new_f64(21, end.y));
+        }
+        HatchEdge::CircularArc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            is_counter_clockwise,
+        } => {
+            pairs.push(CodePair::new_f64(10, center.x));
+            pairs.push(CodePair::new_f64(20, center.y));
+            pairs.push(CodePair::new_f64(40, *radius));
+            pairs.push(CodePair::new_f64(50, *start_angle));
+            pairs.push(CodePair::new_f64(51, *end_angle));
+            pairs.push(CodePair::new_i16(73, as_i16(*is_counter_clockwise)));
+        }
+        HatchEdge::EllipticArc {
+            center,
+            major_axis,
+            minor_axis_ratio,
+            start_angle,
+            end_angle,
+            is_counter_clockwise,
+        } => {
+            pairs.push(CodePair::new_f64(10, center.x));
+            pairs.push(CodePair::new_f64(20, center.y));
+            pairs.push(CodePair::new_f64(11, major_axis.x));
+            pairs.push(CodePair::new_f64(21, major_axis.y));
+            pairs.push(CodePair::new_f64(40, *minor_axis_ratio));
+            pairs.push(CodePair::new_f64(50, *start_angle));
+            pairs.push(CodePair::new_f64(51, *end_angle));
+            pairs.push(CodePair::new_i16(73, as_i16(*is_counter_clockwise)));
+        }
+        HatchEdge::Spline {
+            degree,
+            is_periodic,
+            knot_values,
+            control_points,
+            weights,
+            fit_points,
+            start_tangent,
+            end_tangent,
+        } => {
+            pairs.push(CodePair::new_i32(94, *degree));
+            pairs.push(CodePair::new_i16(73, as_i16(!weights.is_empty())));
+            pairs.push(CodePair::new_i16(74, as_i16(*is_periodic)));
+            pairs.push(CodePair::new_i32(95, knot_values.len() as i32));
+            pairs.push(CodePair::new_i32(96, control_points.len() as i32));
+            for knot in knot_values {
+                pairs.push(CodePair::new_f64(40, *knot));
+            }
+            for point in control_points {
+                pairs.push(CodePair::new_f64(10, point.x));
+                pairs.push(CodePair::new_f64(20, point.y));
+            }
+            for weight in weights {
+                pairs.push(CodePair::new_f64(42, *weight));
+            }
+            if version >= AcadVersion::R2010 {
+                pairs.push(CodePair::new_i32(97, fit_points.len() as i32));
+                for point in fit_points {
+                    pairs.push(CodePair::new_f64(11, point.x));
+                    pairs.push(CodePair::new_f64(21, point.y));
+                }
+                if *start_tangent != Vector::zero() {
+                    pairs.push(CodePair::new_f64(12, start_tangent.x));
+                    pairs.push(CodePair::new_f64(22, start_tangent.y));
+                }
+                if *end_tangent != Vector::zero() {
+                    pairs.push(CodePair::new_f64(13, end_tangent.x));
+                    pairs.push(CodePair::new_f64(23, end_tangent.y));
+                }
+            }
+        }
+    }
+}
+
+fn add_pattern_definition_line_code_pairs(
+    pairs: &mut Vec<CodePair>,
+    line: &HatchPatternDefinitionLine,
+) {
+    pairs.push(CodePair::new_f64(53, line.angle));
+    pairs.push(CodePair::new_f64(43, line.base_point.x));
+    pairs.push(CodePair::new_f64(44, line.base_point.y));
+    pairs.push(CodePair::new_f64(45, line.offset.x));
+    pairs.push(CodePair::new_f64(46, line.offset.y));
+    pairs.push(CodePair::new_i16(79, line.dash_lengths.len() as i16));
+    for dash_length in &line.dash_lengths {
+        pairs.push(CodePair::new_f64(49, *dash_length));
+    }
+}
@@ -3,7 +3,7 @@
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::enums::*;
 use crate::helper_functions::*;
-use crate::{CodePair, DxfError, DxfResult};
+use crate::{CodePair, DiagnosticSeverity, DxfError, DxfResult};
 
 pub use crate::generated::header::*;
 
@@ -38,12 +38,25 @@ impl Header {
                                             iter.put_back(Ok(pair));
                                             break;
                                         } else {
-                                            header
-                                                .set_header_value(&last_header_variable, &pair)?;
-                                            if last_header_variable == "$ACADVER"
-                                                && header.version >= AcadVersion::R2007
+                                            match header
+                                                .set_header_value(&last_header_variable, &pair)
                                             {
-                                                iter.read_as_utf8();
+                                                Ok(()) => (),
+                                                Err(e) if iter.is_lenient() => {
+                                                    let message =
+                                                        format!("the value was ignored: {}", e);
+                                                    iter.add_diagnostic(
+                                                        DiagnosticSeverity::Warning,
+                                                        message,
+                                                    );
+                                                }
+                                                Err(e) => return Err(e),
+                                            }
+                                            if last_header_variable == "$ACADVER" {
+                                                iter.set_version(header.version);
+                                                if header.version >= AcadVersion::R2007 {
+                                                    iter.read_as_utf8();
+                                                }
                                             }
                                         }
                                     }
@@ -52,6 +65,10 @@ impl Header {
                                 }
                             }
                         }
+                        _ if iter.is_lenient() => {
+                            let message = format!("the code pair {:?} was ignored", pair);
+                            iter.add_diagnostic(DiagnosticSeverity::Warning, message);
+                        }
                         _ => return Err(DxfError::UnexpectedCodePair(pair, String::from(""))),
                     }
                 }
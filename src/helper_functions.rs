@@ -14,9 +14,10 @@ use uuid::Uuid;
 
 use enum_primitive::FromPrimitive;
 
+use crate::code_pair_put_back::CodePairPutBack;
 use crate::enums::*;
 use crate::tables::Layer;
-use crate::{CodePair, Color, DxfError, DxfResult};
+use crate::{CodePair, CodePairValue, Color, DxfError, DxfResult};
 
 pub(crate) fn verify_code(pair: &CodePair, expected: i32) -> DxfResult<()> {
     if expected == pair.code {
@@ -26,6 +27,20 @@ pub(crate) fn verify_code(pair: &CodePair, expected: i32) -> DxfResult<()> {
     }
 }
 
+pub(crate) fn next_pair_or_err(iter: &mut CodePairPutBack) -> DxfResult<CodePair> {
+    match iter.next() {
+        Some(Ok(pair)) => Ok(pair),
+        Some(Err(e)) => Err(e),
+        None => Err(DxfError::UnexpectedEndOfInput),
+    }
+}
+
+pub(crate) fn expect_pair(iter: &mut CodePairPutBack, code: i32) -> DxfResult<CodePair> {
+    let pair = next_pair_or_err(iter)?;
+    verify_code(&pair, code)?;
+    Ok(pair)
+}
+
 pub(crate) fn as_bool(v: i16) -> bool {
     v == 1
 }
@@ -178,6 +193,12 @@ pub(crate) fn as_i16(b: bool) -> i16 {
     }
 }
 
+// returns true for a subclass marker other than the one shared by all entities, i.e., the start of
+// the type-specific data
+pub(crate) fn is_specific_subclass_marker(pair: &CodePair) -> bool {
+    pair.code == 100 && !matches!(pair.value, CodePairValue::Str(ref s) if s == "AcDbEntity")
+}
+
 pub(crate) fn uuid_string(u: &Uuid) -> String {
     format!("{}", u)
 }
@@ -238,6 +259,16 @@ pub(crate) fn ensure_positive_or_default_i16(val: &mut i16, default: i16) {
     }
 }
 
+/// Wraps `angle` into [0, 360) degrees, snapping values within rounding error of 0 or 360 to 0.
+pub(crate) fn normalize_degrees(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(360.0);
+    if angle >= 360.0 || angle.abs() < 1e-12 {
+        0.0
+    } else {
+        angle
+    }
+}
+
 pub(crate) fn clipping_from_bool(b: bool) -> XrefClippingBoundaryVisibility {
     XrefClippingBoundaryVisibility::from_i16(if b { 1 } else { 0 }).unwrap() // `1` and `0` will always parse so `.unwrap()` is safe
 }
@@ -246,7 +277,7 @@ pub(crate) fn bool_from_clipping(c: XrefClippingBoundaryVisibility) -> bool {
     c != XrefClippingBoundaryVisibility::NotDisplayedNotPlotted
 }
 
-pub(crate) fn parse_f64(s: String, offset: usize) -> DxfResult<f64> {
+pub(crate) fn parse_f64(s: &str, offset: usize) -> DxfResult<f64> {
     match s.trim().parse::<f64>() {
         Ok(d) => Ok(d),
         Err(e) => Err(DxfError::ParseFloatError(e, offset)),
@@ -256,10 +287,10 @@ pub(crate) fn parse_f64(s: String, offset: usize) -> DxfResult<f64> {
 #[test]
 #[allow(clippy::float_cmp)]
 fn parse_f64_test() {
-    assert_eq!(2.5, parse_f64("  2.5 ".to_string(), 0).unwrap());
+    assert_eq!(2.5, parse_f64("  2.5 ", 0).unwrap());
 }
 
-pub(crate) fn parse_i32(s: String, offset: usize) -> DxfResult<i32> {
+pub(crate) fn parse_i32(s: &str, offset: usize) -> DxfResult<i32> {
     match s.trim().parse::<i32>() {
         Ok(i) => Ok(i),
         Err(e) => Err(DxfError::ParseIntError(e, offset)),
@@ -268,10 +299,10 @@ pub(crate) fn parse_i32(s: String, offset: usize) -> DxfResult<i32> {
 
 #[test]
 fn parse_i32_test() {
-    assert_eq!(2, parse_i32("  2 ".to_string(), 0).unwrap());
+    assert_eq!(2, parse_i32("  2 ", 0).unwrap());
 }
 
-pub(crate) fn parse_i64(s: String, offset: usize) -> DxfResult<i64> {
+pub(crate) fn parse_i64(s: &str, offset: usize) -> DxfResult<i64> {
     match s.trim().parse::<i64>() {
         Ok(l) => Ok(l),
         Err(e) => Err(DxfError::ParseIntError(e, offset)),
@@ -280,10 +311,10 @@ pub(crate) fn parse_i64(s: String, offset: usize) -> DxfResult<i64> {
 
 #[test]
 fn parse_i64_test() {
-    assert_eq!(2, parse_i64("  2 ".to_string(), 0).unwrap());
+    assert_eq!(2, parse_i64("  2 ", 0).unwrap());
 }
 
-pub(crate) fn parse_i16(s: String, offset: usize) -> DxfResult<i16> {
+pub(crate) fn parse_i16(s: &str, offset: usize) -> DxfResult<i16> {
     match s.trim().parse::<f64>() {
         Ok(s) => Ok(s as i16),
         Err(e) => Err(DxfError::ParseFloatError(e, offset)),
@@ -292,10 +323,10 @@ pub(crate) fn parse_i16(s: String, offset: usize) -> DxfResult<i16> {
 
 #[test]
 fn parse_i16_test() {
-    assert_eq!(2, parse_i16("  2 ".to_string(), 0).unwrap());
+    assert_eq!(2, parse_i16("  2 ", 0).unwrap());
 
     // some files write shorts as a double
-    assert_eq!(2, parse_i16(" 2.0 ".to_string(), 0).unwrap());
+    assert_eq!(2, parse_i16(" 2.0 ", 0).unwrap());
 }
 
 pub(crate) fn read_color_value(layer: &mut Layer, color: i16) -> Color {
@@ -313,6 +344,7 @@ where
 {
     let mut bytes = vec![];
     let mut skipping_bom = false;
+    #[allow(clippy::unbuffered_bytes)]
     let reader_bytes = reader.bytes();
     for (i, b) in reader_bytes.enumerate() {
         let b = match b {
@@ -368,16 +400,6 @@ macro_rules! try_from_option_io_result {
     };
 }
 
-// used to turn Result<T> into Option<Result<T>>.
-macro_rules! try_into_option {
-    ($expr : expr) => {
-        match $expr {
-            Ok(v) => v,
-            Err(e) => return Some(Err(e)),
-        }
-    };
-}
-
 // safely unwrap an Option<DxfResult<T>>
 macro_rules! try_from_dxf_result {
     ($expr : expr) => {
@@ -521,7 +543,7 @@ pub(crate) fn parse_hex_string(data: &str, bytes: &mut Vec<u8>, offset: usize) -
         Ok(value)
     }
 
-    let mut complete_byte = data.len() % 2 != 0; // handles strings with an odd number of bytes
+    let mut complete_byte = !data.len().is_multiple_of(2); // handles strings with an odd number of bytes
     let mut current_byte = 0u8;
     for c in data.chars() {
         let value = char_to_value(c, offset)?;
@@ -562,7 +584,7 @@ pub mod tests {
         println!("reading from pairs: {:?}", pairs);
         let iter = DirectCodePairIter::new(pairs);
         let iter = Box::new(iter);
-        unwrap_drawing(Drawing::load_from_iter(iter))
+        unwrap_drawing(Drawing::load_from_iter(iter, false).map(|(drawing, _)| drawing))
     }
 
     pub fn parse_drawing(s: &str) -> Drawing {
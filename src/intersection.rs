@@ -0,0 +1,1069 @@
+// other implementation is in `entity.rs`
+
+use std::f64::consts::PI;
+
+use crate::entities::*;
+use crate::enums::PolylineCurvedAndSmoothSurfaceType;
+use crate::nurbs::NurbsCurve;
+use crate::{LwPolylineSegment, ObjectCoordinateSystem, Point, Vector};
+
+/// The smallest tolerance used when deciding whether two curves meet, which absorbs floating point error.
+const MIN_TOLERANCE: f64 = 1e-9;
+
+/// The number of samples per full turn of an arc or ellipse when looking for intersections numerically.
+const SAMPLES_PER_TURN: usize = 64;
+
+/// The number of samples per knot span of a spline when looking for intersections numerically.
+const SAMPLES_PER_SPAN: usize = 16;
+
+/// The most Newton iterations used to refine an intersection found numerically.
+const MAX_ITERATIONS: usize = 32;
+
+/// A 2x2 matrix in row-major order.
+type Matrix2 = [[f64; 2]; 2];
+
+/// Represents a point where two curves meet.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intersection {
+    /// The intersection point in world coordinates.
+    pub point: Point,
+    /// The location of the point along the first curve; see `Entity::intersections()`.
+    pub first_parameter: f64,
+    /// The location of the point along the second curve; see `Entity::intersections()`.
+    pub second_parameter: f64,
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Returns the points where this entity's curve meets the other entity's curve, ordered along this one, or `None`
+    /// if either entity isn't a `Line`, `Ray`, `XLine`, `Arc`, `Circle`, `Ellipse`, `LwPolyline`, `Polyline` that isn't
+    /// a mesh, or `Spline`.  The curves meet wherever they pass within `tolerance` of each other, and points within
+    /// `tolerance` of one already found aren't repeated.
+    ///
+    /// The curves are expected to lie in the same plane, although a line that pierces the plane of the other curve is
+    /// also found.  Collinear lines and coincident arcs that overlap meet at infinitely many points and aren't
+    /// reported.
+    ///
+    /// The parameters locate each point along its curve:
+    ///
+    /// - `Line`: from 0 at `p1` to 1 at `p2`.
+    /// - `Ray` and `XLine`: the multiple of the direction vector from the first point.
+    /// - `Arc` and `Circle`: the angle in degrees in the entity's object coordinate system.
+    /// - `Ellipse`: the parametric angle in radians, like `start_parameter`.
+    /// - `LwPolyline` and `Polyline`: the index of the vertex that starts the segment plus the fraction of the segment,
+    ///   so 2.5 is halfway between the third and fourth vertices.  A spline-fit `Polyline` without generated vertices
+    ///   uses the parameter of the B-spline defined by its frame control points instead.
+    /// - `Spline`: the NURBS parameter, as used by `Spline::point_at()`.
+    pub fn intersections(&self, other: &Entity, tolerance: f64) -> Option<Vec<Intersection>> {
+        let first = pieces(self)?;
+        let second = pieces(other)?;
+        let tolerance = if tolerance.is_nan() {
+            MIN_TOLERANCE
+        } else {
+            tolerance.max(MIN_TOLERANCE)
+        };
+
+        let mut intersections: Vec<Intersection> = vec![];
+        for a in &first {
+            for b in &second {
+                for (s, t) in a.shape.intersect(&b.shape, tolerance) {
+                    let point = a.shape.point(s);
+                    if intersections
+                        .iter()
+                        .any(|i| i.point.distance_to(&point) <= tolerance)
+                    {
+                        continue;
+                    }
+
+                    intersections.push(Intersection {
+                        point,
+                        first_parameter: a.parameter(s),
+                        second_parameter: b.parameter(t),
+                    });
+                }
+            }
+        }
+
+        intersections.sort_by(|a, b| a.first_parameter.total_cmp(&b.first_parameter));
+        Some(intersections)
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Piece
+//------------------------------------------------------------------------------
+/// A smooth part of an entity's curve along with how its parameter maps to the entity's parameter.
+struct Piece {
+    shape: Shape,
+    offset: f64,
+    scale: f64,
+    period: Option<f64>,
+}
+
+impl Piece {
+    fn new(shape: Shape) -> Self {
+        Piece {
+            shape,
+            offset: 0.0,
+            scale: 1.0,
+            period: None,
+        }
+    }
+    /// Creates a piece of a circle whose parameter is reported in degrees.
+    fn degrees(shape: Shape) -> Self {
+        Piece {
+            scale: 180.0 / PI,
+            period: Some(360.0),
+            ..Piece::new(shape)
+        }
+    }
+    fn parameter(&self, t: f64) -> f64 {
+        let parameter = self.offset + self.scale * t;
+        match self.period {
+            Some(period) => parameter.rem_euclid(period),
+            None => parameter,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Shape
+//------------------------------------------------------------------------------
+/// A curve in world coordinates.
+enum Shape {
+    /// The points `origin + direction * t` for `t` from `start` to `end`, either of which may be infinite.
+    Line {
+        origin: Point,
+        direction: Vector,
+        start: f64,
+        end: f64,
+    },
+    /// The points `center + u * cos(t) + v * sin(t)` for `t` from `start` to `end`, which are at most a turn apart.
+    Conic {
+        center: Point,
+        u: Vector,
+        v: Vector,
+        start: f64,
+        end: f64,
+    },
+    Spline(NurbsCurve),
+}
+
+impl Shape {
+    fn point(&self, t: f64) -> Point {
+        match self {
+            Shape::Line {
+                origin, direction, ..
+            } => origin.clone() + direction.scaled(t),
+            Shape::Conic { center, u, v, .. } => {
+                center.clone() + u.scaled(t.cos()) + v.scaled(t.sin())
+            }
+            Shape::Spline(curve) => curve.point_at(t),
+        }
+    }
+    fn derivative(&self, t: f64) -> Vector {
+        match self {
+            Shape::Line { direction, .. } => direction.clone(),
+            Shape::Conic { u, v, .. } => u.scaled(-t.sin()) + v.scaled(t.cos()),
+            Shape::Spline(curve) => curve.derivatives_at(t, 1).swap_remove(1),
+        }
+    }
+    /// Returns the parameter within the curve's range that's closest to `t`, wrapping around a conic if needed.
+    fn clamp(&self, t: f64) -> f64 {
+        match *self {
+            Shape::Line { start, end, .. } => t.clamp(start, end),
+            Shape::Conic { start, end, .. } => {
+                let t = start + (t - start).rem_euclid(2.0 * PI);
+                if t <= end {
+                    t
+                } else if t - end < start + 2.0 * PI - t {
+                    end
+                } else {
+                    start
+                }
+            }
+            Shape::Spline(ref curve) => curve.clamp_parameter(t),
+        }
+    }
+    /// Returns the normal of the plane containing the curve, or `None` for a line or a spline that's straight.
+    fn normal(&self) -> Option<Vector> {
+        match self {
+            Shape::Line { .. } => None,
+            Shape::Conic { u, v, .. } => Some(u.cross(v)),
+            Shape::Spline(curve) => {
+                // Newell's method over the control polygon
+                let points = &curve.control_points;
+                let first = points.first()?;
+                let mut normal = Vector::zero();
+                let mut size: f64 = 0.0;
+                for pair in points.windows(2) {
+                    let a = pair[0].clone() - first.clone();
+                    let b = pair[1].clone() - first.clone();
+                    normal = normal + a.cross(&b);
+                    size = size.max(b.length());
+                }
+
+                if normal.length() <= 1e-12 * size * size {
+                    None
+                } else {
+                    Some(normal)
+                }
+            }
+        }
+    }
+    /// Returns the parameters of the points used to approximate the curve when looking for intersections numerically,
+    /// or `None` for an unbounded line.
+    fn samples(&self) -> Option<Vec<f64>> {
+        match *self {
+            Shape::Line { start, end, .. } => {
+                if start.is_finite() && end.is_finite() {
+                    Some(vec![start, end])
+                } else {
+                    None
+                }
+            }
+            Shape::Conic { start, end, .. } => {
+                let count = ((end - start) / (2.0 * PI) * SAMPLES_PER_TURN as f64)
+                    .ceil()
+                    .max(4.0) as usize;
+                Some(
+                    (0..=count)
+                        .map(|i| start + (end - start) * i as f64 / count as f64)
+                        .collect(),
+                )
+            }
+            Shape::Spline(ref curve) => {
+                let mut samples = vec![];
+                for span in curve.breakpoints().windows(2) {
+                    for i in 0..SAMPLES_PER_SPAN {
+                        samples.push(
+                            span[0] + (span[1] - span[0]) * i as f64 / SAMPLES_PER_SPAN as f64,
+                        );
+                    }
+                }
+                samples.push(curve.domain().1);
+                Some(samples)
+            }
+        }
+    }
+    /// Returns the parameters of the points where the curves meet within `tolerance`.
+    fn intersect(&self, other: &Shape, tolerance: f64) -> Vec<(f64, f64)> {
+        let normal = match (self.normal().or_else(|| other.normal()), self, other) {
+            (Some(normal), _, _) => normal,
+            (None, Shape::Line { direction: a, .. }, Shape::Line { direction: b, .. }) => {
+                a.cross(b)
+            }
+            (None, _, _) => Vector::z_axis(),
+        };
+        if normal.length() == 0.0 {
+            // parallel lines
+            return vec![];
+        }
+
+        // the curves are intersected as seen looking down the normal, then checked in three dimensions
+        let plane = ObjectCoordinateSystem::new(&normal);
+        let candidates = match (self, other) {
+            (Shape::Line { .. }, Shape::Line { .. }) => line_line(&plane, self, other),
+            (Shape::Line { .. }, Shape::Conic { .. }) => {
+                line_conic(&plane, self, other).unwrap_or_else(|| numeric(&plane, self, other))
+            }
+            (Shape::Conic { .. }, Shape::Line { .. }) => line_conic(&plane, other, self)
+                .map(|c| c.into_iter().map(|(t, s)| (s, t)).collect())
+                .unwrap_or_else(|| numeric(&plane, self, other)),
+            (Shape::Conic { .. }, Shape::Conic { .. }) => {
+                circle_circle(&plane, self, other).unwrap_or_else(|| numeric(&plane, self, other))
+            }
+            _ => numeric(&plane, self, other),
+        };
+
+        candidates
+            .into_iter()
+            .filter_map(|(s, t)| {
+                let (s, t) = (self.clamp(s), other.clamp(t));
+                if self.point(s).distance_to(&other.point(t)) <= tolerance {
+                    Some((s, t))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+/// Splits the entity's curve into pieces in world coordinates, or returns `None` if it isn't supported.
+fn pieces(entity: &Entity) -> Option<Vec<Piece>> {
+    let pieces = match entity.specific {
+        EntityType::Line(ref line) => vec![Piece::new(Shape::Line {
+            origin: line.p1.clone(),
+            direction: line.p2.clone() - line.p1.clone(),
+            start: 0.0,
+            end: 1.0,
+        })],
+        EntityType::Ray(ref ray) => vec![Piece::new(Shape::Line {
+            origin: ray.start_point.clone(),
+            direction: ray.unit_direction_vector.clone(),
+            start: 0.0,
+            end: f64::INFINITY,
+        })],
+        EntityType::XLine(ref xline) => vec![Piece::new(Shape::Line {
+            origin: xline.first_point.clone(),
+            direction: xline.unit_direction_vector.clone(),
+            start: f64::NEG_INFINITY,
+            end: f64::INFINITY,
+        })],
+        EntityType::Arc(ref arc) => {
+            let sweep = (arc.end_angle - arc.start_angle).rem_euclid(360.0);
+            let sweep = if sweep == 0.0 { 360.0 } else { sweep };
+            let start = arc.start_angle.to_radians();
+            vec![Piece::degrees(circle(
+                &arc.normal,
+                &arc.center,
+                arc.radius,
+                start,
+                start + sweep.to_radians(),
+            ))]
+        }
+        EntityType::Circle(ref c) => vec![Piece::degrees(circle(
+            &c.normal,
+            &c.center,
+            c.radius,
+            0.0,
+            2.0 * PI,
+        ))],
+        EntityType::Ellipse(ref ellipse) => {
+            let minor_axis = ellipse
+                .normal
+                .normalized()
+                .cross(&ellipse.major_axis)
+                .scaled(ellipse.minor_axis_ratio);
+            let sweep = (ellipse.end_parameter - ellipse.start_parameter).rem_euclid(2.0 * PI);
+            let sweep = if sweep == 0.0 { 2.0 * PI } else { sweep };
+            vec![Piece {
+                period: Some(2.0 * PI),
+                ..Piece::new(Shape::Conic {
+                    center: ellipse.center.clone(),
+                    u: ellipse.major_axis.clone(),
+                    v: minor_axis,
+                    start: ellipse.start_parameter,
+                    end: ellipse.start_parameter + sweep,
+                })
+            }]
+        }
+        EntityType::LwPolyline(ref poly) => {
+            let vertices = poly
+                .vertices
+                .iter()
+                .map(|v| (Point::new(v.x, v.y, entity.common.elevation), v.bulge))
+                .collect::<Vec<_>>();
+            bulge_pieces(
+                &ObjectCoordinateSystem::new(&poly.extrusion_direction),
+                &vertices,
+                poly.is_closed(),
+            )
+        }
+        EntityType::Polyline(ref poly) => polyline_pieces(poly)?,
+        EntityType::Spline(ref spline) => match NurbsCurve::from_spline(spline) {
+            Some(curve) => vec![Piece::new(Shape::Spline(curve))],
+            None => vec![],
+        },
+        _ => return None,
+    };
+
+    Some(pieces)
+}
+
+/// Returns the circle or arc in the object coordinate system of `normal` between the angles `start` and `end` in
+/// radians.
+fn circle(normal: &Vector, center: &Point, radius: f64, start: f64, end: f64) -> Shape {
+    let ocs = ObjectCoordinateSystem::new(normal);
+    Shape::Conic {
+        center: ocs.to_wcs(center),
+        u: ocs.x_axis().scaled(radius),
+        v: ocs.y_axis().scaled(radius),
+        start,
+        end,
+    }
+}
+
+/// Returns the pieces of a 2D polyline whose vertices are given in `ocs` along with the bulge of the segment that
+/// starts at each one.
+fn bulge_pieces(
+    ocs: &ObjectCoordinateSystem,
+    vertices: &[(Point, f64)],
+    is_closed: bool,
+) -> Vec<Piece> {
+    let count = match vertices.len() {
+        0 | 1 => 0,
+        n if is_closed => n,
+        n => n - 1,
+    };
+    (0..count)
+        .map(|i| {
+            let (p1, bulge) = &vertices[i];
+            let (p2, _) = &vertices[(i + 1) % vertices.len()];
+            let shape = match LwPolylineSegment::from_bulge(p1, p2, *bulge) {
+                LwPolylineSegment::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    ..
+                } => {
+                    // the parameter runs from 0 to the sweep in the direction of travel
+                    let sweep = 4.0 * bulge.atan();
+                    let (sin, cos) = start_angle.to_radians().sin_cos();
+                    let radial = ocs.x_axis().scaled(cos) + ocs.y_axis().scaled(sin);
+                    let tangent = ocs.y_axis().scaled(cos) - ocs.x_axis().scaled(sin);
+                    return Piece {
+                        offset: i as f64,
+                        scale: 1.0 / sweep.abs(),
+                        ..Piece::new(Shape::Conic {
+                            center: ocs.to_wcs(&Point::new(center.x, center.y, p1.z)),
+                            u: radial.scaled(radius),
+                            v: tangent.scaled(radius * sweep.signum()),
+                            start: 0.0,
+                            end: sweep.abs(),
+                        })
+                    };
+                }
+                LwPolylineSegment::Line { .. } => {
+                    let start = ocs.to_wcs(p1);
+                    Shape::Line {
+                        direction: ocs.to_wcs(p2) - start.clone(),
+                        origin: start,
+                        start: 0.0,
+                        end: 1.0,
+                    }
+                }
+            };
+            Piece {
+                offset: i as f64,
+                ..Piece::new(shape)
+            }
+        })
+        .collect()
+}
+
+fn polyline_pieces(poly: &Polyline) -> Option<Vec<Piece>> {
+    if poly.is_3d_polygon_mesh() || poly.is_polyface_mesh() {
+        return None;
+    }
+
+    let ocs = ObjectCoordinateSystem::new(&poly.normal);
+    let to_wcs = |p: &Point| {
+        if poly.is_3d_polyline() {
+            p.clone()
+        } else {
+            ocs.to_wcs(&Point::new(p.x, p.y, poly.location.z))
+        }
+    };
+
+    // without generated vertices a spline-fit polyline follows the B-spline of its frame
+    let has_generated_vertices = poly
+        .vertices()
+        .any(|v| v.is_spline_vertex_created_by_spline_fitting());
+    if poly.spline_fit_vertices_added() && !has_generated_vertices {
+        let frame = poly
+            .vertices()
+            .filter(|v| v.is_spline_frame_control_point())
+            .map(|v| to_wcs(&v.location))
+            .collect::<Vec<_>>();
+        let degree = match poly.surface_type {
+            PolylineCurvedAndSmoothSurfaceType::QuadraticBSpline => 2,
+            _ => 3,
+        };
+        let curve = if poly.is_closed() {
+            let weights = vec![1.0; frame.len()];
+            NurbsCurve::periodic(degree, frame, weights)
+        } else {
+            NurbsCurve::clamped(degree, frame)
+        };
+        return Some(
+            curve
+                .map(|c| vec![Piece::new(Shape::Spline(c))])
+                .unwrap_or_default(),
+        );
+    }
+
+    let vertices = poly
+        .vertices()
+        .filter(|v| !v.is_spline_frame_control_point())
+        .collect::<Vec<_>>();
+    if poly.is_3d_polyline() {
+        // a 3D polyline has no bulges, so its vertices are used in a plane of their own
+        let vertices = vertices
+            .iter()
+            .map(|v| (v.location.clone(), 0.0))
+            .collect::<Vec<_>>();
+        return Some(bulge_pieces(
+            &ObjectCoordinateSystem::default(),
+            &vertices,
+            poly.is_closed(),
+        ));
+    }
+
+    let vertices = vertices
+        .iter()
+        .map(|v| {
+            (
+                Point::new(v.location.x, v.location.y, poly.location.z),
+                v.bulge,
+            )
+        })
+        .collect::<Vec<_>>();
+    Some(bulge_pieces(&ocs, &vertices, poly.is_closed()))
+}
+
+/// Returns the point's coordinates in the plane.
+fn flat(plane: &ObjectCoordinateSystem, p: &Point) -> (f64, f64) {
+    let p = plane.from_wcs(p);
+    (p.x, p.y)
+}
+
+fn flat_vector(plane: &ObjectCoordinateSystem, v: &Vector) -> (f64, f64) {
+    let v = plane.vector_from_wcs(v);
+    (v.x, v.y)
+}
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn line_line(plane: &ObjectCoordinateSystem, a: &Shape, b: &Shape) -> Vec<(f64, f64)> {
+    match (a, b) {
+        (
+            Shape::Line {
+                origin: o1,
+                direction: d1,
+                ..
+            },
+            Shape::Line {
+                origin: o2,
+                direction: d2,
+                ..
+            },
+        ) => {
+            let (d1, d2) = (flat_vector(plane, d1), flat_vector(plane, d2));
+            let (o1, o2) = (flat(plane, o1), flat(plane, o2));
+            let det = cross(d1, d2);
+            if det.abs() <= 1e-12 * d1.0.hypot(d1.1) * d2.0.hypot(d2.1) {
+                return vec![];
+            }
+
+            let w = (o2.0 - o1.0, o2.1 - o1.1);
+            vec![(cross(w, d2) / det, cross(w, d1) / det)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns the matrix that maps points in the plane to the conic's `(cos(t), sin(t))`, along with the conic's center,
+/// or `None` if the conic is seen edge-on.
+fn conic_frame(plane: &ObjectCoordinateSystem, conic: &Shape) -> Option<(Matrix2, (f64, f64))> {
+    match conic {
+        Shape::Conic { center, u, v, .. } => {
+            let (u, v) = (flat_vector(plane, u), flat_vector(plane, v));
+            let det = cross(u, v);
+            if det.abs() <= 1e-12 * u.0.hypot(u.1) * v.0.hypot(v.1) {
+                return None;
+            }
+
+            let inverse = [[v.1 / det, -v.0 / det], [-u.1 / det, u.0 / det]];
+            Some((inverse, flat(plane, center)))
+        }
+        _ => None,
+    }
+}
+
+fn apply(m: &Matrix2, p: (f64, f64)) -> (f64, f64) {
+    (m[0][0] * p.0 + m[0][1] * p.1, m[1][0] * p.0 + m[1][1] * p.1)
+}
+
+/// Intersects a line and a conic by mapping the line into the space where the conic is the unit circle.  Returns
+/// `None` if the conic is seen edge-on.
+fn line_conic(
+    plane: &ObjectCoordinateSystem,
+    line: &Shape,
+    conic: &Shape,
+) -> Option<Vec<(f64, f64)>> {
+    let (inverse, center) = conic_frame(plane, conic)?;
+    let (origin, direction) = match line {
+        Shape::Line {
+            origin, direction, ..
+        } => (origin, direction),
+        _ => return None,
+    };
+    let flat_origin = flat(plane, origin);
+    let a = apply(
+        &inverse,
+        (flat_origin.0 - center.0, flat_origin.1 - center.1),
+    );
+    let b = apply(&inverse, flat_vector(plane, direction));
+    let bb = b.0 * b.0 + b.1 * b.1;
+    if bb == 0.0 {
+        // the line is perpendicular to the plane, so it can only meet the conic where it pierces the plane
+        let along = direction.dot(plane.normal());
+        if along == 0.0 {
+            return Some(vec![]);
+        }
+
+        let conic_center = match conic {
+            Shape::Conic { center, .. } => center.clone(),
+            _ => return None,
+        };
+        let s = (conic_center - origin.clone()).dot(plane.normal()) / along;
+        return Some(vec![(s, a.1.atan2(a.0))]);
+    }
+
+    // solve |a + b * s| = 1
+    let ab = a.0 * b.0 + a.1 * b.1;
+    let discriminant = ab * ab - bb * (a.0 * a.0 + a.1 * a.1 - 1.0);
+    let roots = if discriminant <= 0.0 {
+        // a near miss is kept so that a tangent line within the tolerance is found
+        vec![-ab / bb]
+    } else {
+        let root = discriminant.sqrt();
+        vec![(-ab - root) / bb, (-ab + root) / bb]
+    };
+
+    Some(
+        roots
+            .into_iter()
+            .map(|s| (s, (a.1 + b.1 * s).atan2(a.0 + b.0 * s)))
+            .collect(),
+    )
+}
+
+/// Intersects two conics that both appear as circles in the plane.  Returns `None` if either doesn't.
+fn circle_circle(plane: &ObjectCoordinateSystem, a: &Shape, b: &Shape) -> Option<Vec<(f64, f64)>> {
+    let as_circle = |conic: &Shape| match conic {
+        Shape::Conic { center, u, v, .. } => {
+            let (u, v) = (flat_vector(plane, u), flat_vector(plane, v));
+            let (u_length, v_length) = (u.0.hypot(u.1), v.0.hypot(v.1));
+            let dot = u.0 * v.0 + u.1 * v.1;
+            if (u_length - v_length).abs() <= 1e-9 * u_length
+                && dot.abs() <= 1e-9 * u_length * v_length
+            {
+                Some((flat(plane, center), u_length))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    let ((c1, r1), (c2, r2)) = (as_circle(a)?, as_circle(b)?);
+    let (frame1, _) = conic_frame(plane, a)?;
+    let (frame2, _) = conic_frame(plane, b)?;
+
+    let (dx, dy) = (c2.0 - c1.0, c2.1 - c1.1);
+    let distance = dx.hypot(dy);
+    if distance == 0.0 {
+        // concentric circles either miss or coincide
+        return Some(vec![]);
+    }
+
+    // `along` is the distance from the first center to the chord through the intersections, which is `2 * across`
+    // long; circles that miss are kept as a near miss at the closest point
+    let along = (distance * distance + r1 * r1 - r2 * r2) / (2.0 * distance);
+    let across = (r1 * r1 - along * along).max(0.0).sqrt();
+    let (ex, ey) = (dx / distance, dy / distance);
+    let mut points = vec![(
+        c1.0 + ex * along - ey * across,
+        c1.1 + ey * along + ex * across,
+    )];
+    if across > 0.0 {
+        points.push((
+            c1.0 + ex * along + ey * across,
+            c1.1 + ey * along - ex * across,
+        ));
+    }
+
+    let angle = |frame: &Matrix2, center: (f64, f64), p: (f64, f64)| {
+        let (x, y) = apply(frame, (p.0 - center.0, p.1 - center.1));
+        y.atan2(x)
+    };
+    Some(
+        points
+            .into_iter()
+            .map(|p| (angle(&frame1, c1, p), angle(&frame2, c2, p)))
+            .collect(),
+    )
+}
+
+/// Finds intersections by crossing the segments between sample points of each curve, then refining each crossing with
+/// Newton's method.
+fn numeric(plane: &ObjectCoordinateSystem, a: &Shape, b: &Shape) -> Vec<(f64, f64)> {
+    let (a_samples, b_samples) = match (a.samples(), b.samples()) {
+        (Some(a_samples), Some(b_samples)) => (a_samples, b_samples),
+        (None, Some(b_samples)) => (clip_line(a, b, &b_samples), b_samples),
+        (Some(a_samples), None) => (a_samples.clone(), clip_line(b, a, &a_samples)),
+        (None, None) => return vec![],
+    };
+    let a_points = a_samples
+        .iter()
+        .map(|t| flat(plane, &a.point(*t)))
+        .collect::<Vec<_>>();
+    let b_points = b_samples
+        .iter()
+        .map(|t| flat(plane, &b.point(*t)))
+        .collect::<Vec<_>>();
+
+    let mut candidates = vec![];
+    for i in 1..a_points.len() {
+        for j in 1..b_points.len() {
+            let (p1, p2) = (a_points[i - 1], a_points[i]);
+            let (q1, q2) = (b_points[j - 1], b_points[j]);
+            let d1 = (p2.0 - p1.0, p2.1 - p1.1);
+            let d2 = (q2.0 - q1.0, q2.1 - q1.1);
+            let det = cross(d1, d2);
+            if det == 0.0 {
+                continue;
+            }
+
+            // a little slack catches crossings that the sampling nudges just past a segment's end
+            let w = (q1.0 - p1.0, q1.1 - p1.1);
+            let (alpha, beta) = (cross(w, d2) / det, cross(w, d1) / det);
+            let slack = 0.05;
+            if alpha < -slack || alpha > 1.0 + slack || beta < -slack || beta > 1.0 + slack {
+                continue;
+            }
+
+            let s = a_samples[i - 1] + (a_samples[i] - a_samples[i - 1]) * alpha;
+            let t = b_samples[j - 1] + (b_samples[j] - b_samples[j - 1]) * beta;
+            candidates.push(refine(plane, a, b, s, t));
+        }
+    }
+
+    candidates
+}
+
+/// Returns the range of an unbounded line's parameter that covers the other curve, given sample parameters along it.
+fn clip_line(line: &Shape, other: &Shape, other_samples: &[f64]) -> Vec<f64> {
+    match line {
+        Shape::Line {
+            origin,
+            direction,
+            start,
+            end,
+        } => {
+            let length_squared = direction.dot(direction);
+            if length_squared == 0.0 {
+                return vec![];
+            }
+
+            let (low, high) = other_samples
+                .iter()
+                .fold((f64::MAX, f64::MIN), |(low, high), t| {
+                    let along = (other.point(*t) - origin.clone()).dot(direction) / length_squared;
+                    (low.min(along), high.max(along))
+                });
+            let margin = (high - low) * 0.1 + 1.0;
+            let (low, high) = ((low - margin).max(*start), (high + margin).min(*end));
+            if low < high {
+                vec![low, high]
+            } else {
+                vec![]
+            }
+        }
+        _ => vec![],
+    }
+}
+
+/// Moves the parameters toward where the curves meet in the plane with Newton's method.
+fn refine(
+    plane: &ObjectCoordinateSystem,
+    a: &Shape,
+    b: &Shape,
+    mut s: f64,
+    mut t: f64,
+) -> (f64, f64) {
+    for _ in 0..MAX_ITERATIONS {
+        let (p, q) = (flat(plane, &a.point(s)), flat(plane, &b.point(t)));
+        let f = (p.0 - q.0, p.1 - q.1);
+        let da = flat_vector(plane, &a.derivative(s));
+        let db = flat_vector(plane, &b.derivative(t));
+
+        // solve da * ds - db * dt = -f
+        let det = cross(db, da);
+        if det == 0.0 {
+            break;
+        }
+
+        let ds = cross(f, db) / det;
+        let dt = cross(f, da) / det;
+        let (next_s, next_t) = (a.clamp(s + ds), b.clamp(t + dt));
+        let converged = (next_s - s).abs() <= 1e-15 * (1.0 + s.abs())
+            && (next_t - t).abs() <= 1e-15 * (1.0 + t.abs());
+        s = next_s;
+        t = next_t;
+        if converged {
+            break;
+        }
+    }
+
+    (s, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_point(expected: (f64, f64, f64), actual: &Point) {
+        assert_close(expected.0, actual.x);
+        assert_close(expected.1, actual.y);
+        assert_close(expected.2, actual.z);
+    }
+
+    fn intersect(a: EntityType, b: EntityType) -> Vec<Intersection> {
+        Entity::new(a)
+            .intersections(&Entity::new(b), 1e-9)
+            .expect("expected supported entities")
+    }
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> EntityType {
+        EntityType::Line(Line::new(Point::new(x1, y1, 0.0), Point::new(x2, y2, 0.0)))
+    }
+
+    #[test]
+    fn line_line() {
+        let found = intersect(line(0.0, 0.0, 2.0, 2.0), line(0.0, 2.0, 2.0, 0.0));
+        assert_eq!(1, found.len());
+        assert_point((1.0, 1.0, 0.0), &found[0].point);
+        assert_close(0.5, found[0].first_parameter);
+        assert_close(0.5, found[0].second_parameter);
+
+        // segments that would cross if extended
+        assert!(intersect(line(0.0, 0.0, 1.0, 0.0), line(2.0, -1.0, 2.0, 1.0)).is_empty());
+
+        // parallel and skew lines
+        assert!(intersect(line(0.0, 0.0, 1.0, 0.0), line(0.0, 1.0, 1.0, 1.0)).is_empty());
+        let skew = EntityType::Line(Line::new(
+            Point::new(0.5, -1.0, 1.0),
+            Point::new(0.5, 1.0, 1.0),
+        ));
+        assert!(intersect(line(0.0, 0.0, 1.0, 0.0), skew).is_empty());
+    }
+
+    #[test]
+    fn rays_and_xlines_are_unbounded() {
+        let ray = EntityType::Ray(Ray {
+            start_point: Point::new(5.0, 0.0, 0.0),
+            unit_direction_vector: Vector::new(0.0, 1.0, 0.0),
+        });
+        let xline = EntityType::XLine(XLine {
+            first_point: Point::new(0.0, 0.0, 0.0),
+            unit_direction_vector: Vector::new(1.0, 0.0, 0.0),
+        });
+        let found = intersect(xline.clone(), ray);
+        assert_eq!(1, found.len());
+        assert_point((5.0, 0.0, 0.0), &found[0].point);
+        assert_close(5.0, found[0].first_parameter);
+        assert_close(0.0, found[0].second_parameter);
+
+        let circle = EntityType::Circle(Circle::new(Point::new(-10.0, 0.0, 0.0), 1.0));
+        let found = intersect(xline, circle);
+        assert_eq!(2, found.len());
+        assert_close(-11.0, found[0].first_parameter);
+        assert_close(180.0, found[0].second_parameter);
+        assert_close(-9.0, found[1].first_parameter);
+        assert_close(0.0, found[1].second_parameter);
+    }
+
+    #[test]
+    fn line_arc_respects_the_sweep() {
+        let arc = EntityType::Arc(Arc::new(Point::origin(), 1.0, 0.0, 90.0));
+        let found = intersect(line(-2.0, 0.5, 2.0, 0.5), arc.clone());
+        assert_eq!(1, found.len());
+        assert_point((0.75f64.sqrt(), 0.5, 0.0), &found[0].point);
+        assert_close(30.0, found[0].second_parameter);
+
+        // a tangent line touches once
+        let found = intersect(line(-2.0, 1.0, 2.0, 1.0), arc);
+        assert_eq!(1, found.len());
+        assert_close(90.0, found[0].second_parameter);
+    }
+
+    #[test]
+    fn circle_circle() {
+        let found = intersect(
+            EntityType::Circle(Circle::new(Point::origin(), 1.0)),
+            EntityType::Circle(Circle::new(Point::new(1.0, 0.0, 0.0), 1.0)),
+        );
+        assert_eq!(2, found.len());
+        assert_close(60.0, found[0].first_parameter);
+        assert_close(120.0, found[0].second_parameter);
+        assert_close(300.0, found[1].first_parameter);
+        assert_close(240.0, found[1].second_parameter);
+
+        // the second circle's normal is flipped, so its angles run the other way
+        let mut flipped = Circle::new(Point::new(-1.0, 0.0, 0.0), 1.0);
+        flipped.normal = Vector::new(0.0, 0.0, -1.0);
+        let found = intersect(
+            EntityType::Circle(Circle::new(Point::origin(), 1.0)),
+            EntityType::Circle(flipped),
+        );
+        assert_eq!(2, found.len());
+        assert_close(60.0, found[0].first_parameter);
+        assert_close(60.0, found[0].second_parameter);
+
+        // concentric circles don't meet
+        assert!(intersect(
+            EntityType::Circle(Circle::new(Point::origin(), 1.0)),
+            EntityType::Circle(Circle::new(Point::origin(), 2.0)),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn ellipse_circle() {
+        let ellipse = Ellipse {
+            major_axis: Vector::new(2.0, 0.0, 0.0),
+            minor_axis_ratio: 0.5,
+            ..Default::default()
+        };
+        let found = intersect(
+            EntityType::Ellipse(ellipse),
+            EntityType::Circle(Circle::new(Point::origin(), 1.5)),
+        );
+        assert_eq!(4, found.len());
+        for i in &found {
+            assert_close(1.5, i.point.distance_to(&Point::origin()));
+            let (x, y) = (i.point.x / 2.0, i.point.y);
+            assert_close(1.0, x * x + y * y);
+            assert_close(i.first_parameter, y.atan2(x).rem_euclid(2.0 * PI));
+        }
+    }
+
+    #[test]
+    fn lw_polyline_with_bulge() {
+        // a unit square whose top edge bulges up into a half circle
+        let mut poly = LwPolyline {
+            vertices: vec![
+                LwPolylineVertex {
+                    x: 0.0,
+                    y: 0.0,
+                    ..Default::default()
+                },
+                LwPolylineVertex {
+                    x: 1.0,
+                    y: 0.0,
+                    ..Default::default()
+                },
+                LwPolylineVertex {
+                    x: 1.0,
+                    y: 1.0,
+                    bulge: 1.0,
+                    ..Default::default()
+                },
+                LwPolylineVertex {
+                    x: 0.0,
+                    y: 1.0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        poly.set_is_closed(true);
+        let found = intersect(EntityType::LwPolyline(poly), line(0.5, -1.0, 0.5, 3.0));
+        assert_eq!(2, found.len());
+        assert_point((0.5, 0.0, 0.0), &found[0].point);
+        assert_close(0.5, found[0].first_parameter);
+        assert_point((0.5, 1.5, 0.0), &found[1].point);
+        assert_close(2.5, found[1].first_parameter);
+        assert_close(0.625, found[1].second_parameter);
+    }
+
+    #[test]
+    fn shared_vertices_are_reported_once() {
+        let mut drawing = Drawing::new();
+        let mut poly = Polyline::default();
+        for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)] {
+            poly.add_vertex(&mut drawing, Vertex::new(Point::new(x, y, 0.0)));
+        }
+        let entity = Entity::new(EntityType::Polyline(poly));
+        let found = entity
+            .intersections(&Entity::new(line(0.0, -1.0, 2.0, 1.0)), 1e-9)
+            .unwrap();
+        assert_eq!(1, found.len());
+        assert_point((1.0, 0.0, 0.0), &found[0].point);
+        assert_close(1.0, found[0].first_parameter);
+    }
+
+    #[test]
+    fn spline_line() {
+        let spline = Spline::through_points(
+            3,
+            vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(2.0, -1.0, 0.0),
+                Point::new(3.0, 0.0, 0.0),
+            ],
+            None,
+            None,
+        )
+        .unwrap();
+        let found = intersect(
+            EntityType::Spline(spline.clone()),
+            line(-1.0, 0.0, 4.0, 0.0),
+        );
+        assert_eq!(3, found.len());
+        for i in &found {
+            assert_close(0.0, i.point.y);
+            let on_spline = spline.point_at(i.first_parameter).unwrap();
+            assert_point((i.point.x, i.point.y, i.point.z), &on_spline);
+        }
+        assert_point((0.0, 0.0, 0.0), &found[0].point);
+        assert_point((3.0, 0.0, 0.0), &found[2].point);
+    }
+
+    #[test]
+    fn line_through_plane_of_circle() {
+        let pierce = EntityType::Line(Line::new(
+            Point::new(1.0, 0.0, -1.0),
+            Point::new(1.0, 0.0, 1.0),
+        ));
+        let found = intersect(
+            EntityType::Circle(Circle::new(Point::origin(), 1.0)),
+            pierce,
+        );
+        assert_eq!(1, found.len());
+        assert_point((1.0, 0.0, 0.0), &found[0].point);
+        assert_close(0.5, found[0].second_parameter);
+    }
+
+    #[test]
+    fn tolerance_controls_near_misses() {
+        let a = Entity::new(line(0.0, 0.0, 1.0, 0.0));
+        let b = Entity::new(line(1.001, -1.0, 1.001, 1.0));
+        assert!(a.intersections(&b, 1e-6).unwrap().is_empty());
+        let found = a.intersections(&b, 1e-2).unwrap();
+        assert_eq!(1, found.len());
+        assert_close(1.0, found[0].first_parameter);
+    }
+
+    #[test]
+    fn unsupported_entities() {
+        let text = Entity::new(EntityType::Text(Text::default()));
+        let line = Entity::new(line(0.0, 0.0, 1.0, 0.0));
+        assert!(text.intersections(&line, 1e-9).is_none());
+        assert!(line.intersections(&text, 1e-9).is_none());
+    }
+}
@@ -17,7 +17,7 @@
 //! ```
 //!
 //! > Note that `serde` support is intended to aid in debugging and since the serialized format is heavily
-//! dependent on the layout of the structures, it may change at any time.
+//! > dependent on the layout of the structures, it may change at any time.
 //!
 //! # Examples
 //!
@@ -143,6 +143,15 @@ mod thumbnail;
 mod drawing;
 pub use crate::drawing::Drawing;
 
+mod drawing_reader;
+pub use crate::drawing_reader::{DrawingEvent, DrawingReader};
+
+mod drawing_writer;
+pub use crate::drawing_writer::DrawingWriter;
+
+mod load_options;
+pub use crate::load_options::{Diagnostic, DiagnosticSeverity, LoadOptions};
+
 mod drawing_item;
 pub use crate::drawing_item::{DrawingItem, DrawingItemMut};
 
@@ -166,9 +175,21 @@ pub use crate::color::Color;
 mod point;
 pub use crate::point::Point;
 
+mod bounding_box;
+pub use crate::bounding_box::BoundingBox;
+
+mod spatial_index;
+pub use crate::spatial_index::SpatialIndex;
+
+mod effective_properties;
+pub use crate::effective_properties::{EffectiveProperties, InsertContext};
+
 mod vector;
 pub use crate::vector::Vector;
 
+mod object_coordinate_system;
+pub use crate::object_coordinate_system::ObjectCoordinateSystem;
+
 mod generated;
 pub mod entities {
     pub use crate::generated::entities::*;
@@ -197,19 +218,54 @@ pub use crate::class::Class;
 mod header;
 pub use crate::header::Header;
 
+mod raw_section;
+pub use crate::raw_section::RawSection;
+
 mod line_weight;
 pub use crate::line_weight::LineWeight;
 
+mod transparency;
+pub use crate::transparency::Transparency;
+
 mod entity;
-pub use crate::entity::LwPolylineVertex;
+pub use crate::entity::{LwPolylineVertex, UnknownEntity};
+
+mod lw_polyline;
+pub use crate::lw_polyline::LwPolylineSegment;
+
+mod explode;
+mod extents;
+mod transform;
+
+mod intersection;
+pub use crate::intersection::Intersection;
+
+mod nurbs;
+mod tessellation;
+pub use crate::tessellation::Tessellation;
+
+mod hatch_entity;
+pub use crate::hatch_entity::{
+    HatchBoundaryPath, HatchEdge, HatchGradientColor, HatchPatternDefinitionLine,
+    HatchPolylineVertex,
+};
+
+mod mesh_entity;
+pub use crate::mesh_entity::{MeshEdge, MeshOverrideProperty, MeshSubentityOverride};
 
 mod mleader_entity;
 
+mod surface_entity;
+pub use crate::surface_entity::SweepOptions;
+
+mod table_entity;
+pub use crate::table_entity::{TableCell, TableCellAttribute, TableCellRange, TableCellValue};
+
 mod object;
-pub use crate::object::{GeoMeshPoint, MLineStyleElement};
+pub use crate::object::{GeoMeshPoint, MLineStyleElement, UnknownObject};
 
 mod dxf_error;
-pub use crate::dxf_error::DxfError;
+pub use crate::dxf_error::{DxfError, ErrorContext, FilePosition};
 
 mod dxf_result;
 pub use crate::dxf_result::DxfResult;
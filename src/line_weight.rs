@@ -11,11 +11,15 @@ impl LineWeight {
     }
     /// Creates a new `LineWeight` that defaults back to the containing block's line weight.
     pub fn by_block() -> LineWeight {
-        LineWeight::from_raw_value(-1)
+        LineWeight::from_raw_value(-2)
     }
     /// Creates a new `LineWeight` that defaults back to the item's layer's line weight.
     pub fn by_layer() -> LineWeight {
-        LineWeight::from_raw_value(-2)
+        LineWeight::from_raw_value(-1)
+    }
+    /// Creates a new `LineWeight` that uses the drawing's default line weight.
+    pub fn standard() -> LineWeight {
+        LineWeight::from_raw_value(-3)
     }
     /// Gets the raw value of the `LineWeight`.
     pub fn raw_value(&self) -> i16 {
@@ -23,10 +27,14 @@ impl LineWeight {
     }
     /// Returns `true` if the `LineWeight` is BYBLOCK.
     pub fn is_by_block(&self) -> bool {
-        self.raw_value == -1
+        self.raw_value == -2
     }
     /// Returns `true` if the `LineWeight` is BYLAYER.
     pub fn is_by_layer(&self) -> bool {
-        self.raw_value == -2
+        self.raw_value == -1
+    }
+    /// Returns `true` if the `LineWeight` uses the drawing's default line weight.
+    pub fn is_standard(&self) -> bool {
+        self.raw_value == -3
     }
 }
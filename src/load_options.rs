@@ -0,0 +1,51 @@
+use encoding_rs::Encoding;
+
+use crate::{FilePosition, Handle};
+
+/// Controls how a `Drawing` is loaded.
+#[derive(Clone, Debug)]
+pub struct LoadOptions {
+    /// The text encoding used for files older than R2007.
+    pub encoding: &'static Encoding,
+    /// When set, recoverable problems are repaired or skipped and reported as `Diagnostic`s instead of failing the load.
+    /// Values that can't be parsed are ignored, leaving the default in place, and entities and objects that can't be
+    /// read are dropped.
+    pub lenient: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            encoding: encoding_rs::WINDOWS_1252,
+            lenient: false,
+        }
+    }
+}
+
+/// How much of the drawing was affected by a `Diagnostic`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticSeverity {
+    /// A value was repaired or defaulted; the item it belongs to was kept.
+    Warning,
+    /// An item was dropped.
+    Error,
+}
+
+/// Describes a problem that was encountered and skipped while loading a `Drawing` with `LoadOptions::lenient`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// How much of the drawing was affected.
+    pub severity: DiagnosticSeverity,
+    /// The section being read, e.g., `ENTITIES`.
+    pub section: String,
+    /// The type string of the item being read, e.g., `LINE`.
+    pub item_type: String,
+    /// The handle of the item being read, if it was known.
+    pub handle: Handle,
+    /// Where the problem was found.
+    pub position: FilePosition,
+    /// A description of the problem.
+    pub message: String,
+}
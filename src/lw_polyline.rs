@@ -0,0 +1,571 @@
+// other implementation is in `entity.rs`
+
+use crate::entities::LwPolyline;
+use crate::helper_functions::normalize_degrees;
+use crate::{LwPolylineVertex, Point};
+
+/// The distance within which the end of the last segment is considered to be the start of the first one.
+const CLOSED_TOLERANCE: f64 = 1e-9;
+
+//------------------------------------------------------------------------------
+//                                                             LwPolylineSegment
+//------------------------------------------------------------------------------
+/// Represents the straight or circular segment between two consecutive vertices of a `LwPolyline`.  All coordinates
+/// are in the polyline's object coordinate system; the Z values are always zero since the elevation is stored on the
+/// entity.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum LwPolylineSegment {
+    Line {
+        start: Point,
+        end: Point,
+    },
+    /// The segment runs from `start_angle` to `end_angle` in the direction given by `is_counter_clockwise`.  Angles are
+    /// in degrees.
+    Arc {
+        center: Point,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_counter_clockwise: bool,
+    },
+}
+
+impl LwPolylineSegment {
+    /// Creates the segment from `start` to `end` with the specified bulge, which is the tangent of a quarter of the
+    /// included angle of the arc.  A positive bulge is counter-clockwise, and a zero bulge is a straight line.
+    pub fn from_bulge(start: &Point, end: &Point, bulge: f64) -> Self {
+        let start = Point::new(start.x, start.y, 0.0);
+        let end = Point::new(end.x, end.y, 0.0);
+        let length = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+        if bulge == 0.0 || length == 0.0 {
+            return LwPolylineSegment::Line { start, end };
+        }
+
+        // the center is offset from the chord's midpoint along its left-hand normal
+        let (dx, dy) = ((end.x - start.x) / length, (end.y - start.y) / length);
+        let offset = length * (1.0 - bulge * bulge) / (4.0 * bulge);
+        let center = Point::new(
+            (start.x + end.x) / 2.0 - dy * offset,
+            (start.y + end.y) / 2.0 + dx * offset,
+            0.0,
+        );
+        let angle =
+            |p: &Point| normalize_degrees((p.y - center.y).atan2(p.x - center.x).to_degrees());
+        LwPolylineSegment::Arc {
+            radius: ((start.x - center.x).powi(2) + (start.y - center.y).powi(2)).sqrt(),
+            start_angle: angle(&start),
+            end_angle: angle(&end),
+            is_counter_clockwise: bulge > 0.0,
+            center,
+        }
+    }
+    pub fn start_point(&self) -> Point {
+        match self {
+            LwPolylineSegment::Line { start, .. } => start.clone(),
+            LwPolylineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                ..
+            } => point_on_circle(center, *radius, start_angle.to_radians()),
+        }
+    }
+    pub fn end_point(&self) -> Point {
+        match self {
+            LwPolylineSegment::Line { end, .. } => end.clone(),
+            LwPolylineSegment::Arc {
+                center,
+                radius,
+                end_angle,
+                ..
+            } => point_on_circle(center, *radius, end_angle.to_radians()),
+        }
+    }
+    /// Returns the bulge to store on the segment's starting vertex.
+    pub fn bulge(&self) -> f64 {
+        (self.sweep() / 4.0).tan()
+    }
+    pub fn length(&self) -> f64 {
+        match self {
+            LwPolylineSegment::Line { start, end } => {
+                ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt()
+            }
+            LwPolylineSegment::Arc { radius, .. } => radius * self.sweep().abs(),
+        }
+    }
+    /// Returns the point the specified distance along the segment from its start, which is clamped to the segment.
+    pub fn point_at_distance(&self, distance: f64) -> Point {
+        let distance = distance.clamp(0.0, self.length());
+        match self {
+            LwPolylineSegment::Line { start, end } => {
+                let length = self.length();
+                let t = if length == 0.0 {
+                    0.0
+                } else {
+                    distance / length
+                };
+                Point::new(
+                    start.x + (end.x - start.x) * t,
+                    start.y + (end.y - start.y) * t,
+                    0.0,
+                )
+            }
+            LwPolylineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                ..
+            } => {
+                let angle = distance / radius * self.sweep().signum();
+                point_on_circle(center, *radius, start_angle.to_radians() + angle)
+            }
+        }
+    }
+    /// Returns the same segment traveled in the opposite direction.
+    pub fn reversed(&self) -> Self {
+        match self {
+            LwPolylineSegment::Line { start, end } => LwPolylineSegment::Line {
+                start: end.clone(),
+                end: start.clone(),
+            },
+            LwPolylineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => LwPolylineSegment::Arc {
+                center: center.clone(),
+                radius: *radius,
+                start_angle: *end_angle,
+                end_angle: *start_angle,
+                is_counter_clockwise: !is_counter_clockwise,
+            },
+        }
+    }
+}
+
+// private implementation
+impl LwPolylineSegment {
+    /// Returns the signed angle in radians that an arc turns through, which is positive when counter-clockwise.
+    fn sweep(&self) -> f64 {
+        match self {
+            LwPolylineSegment::Line { .. } => 0.0,
+            LwPolylineSegment::Arc {
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+                ..
+            } => {
+                if *is_counter_clockwise {
+                    (end_angle - start_angle).rem_euclid(360.0).to_radians()
+                } else {
+                    -(start_angle - end_angle).rem_euclid(360.0).to_radians()
+                }
+            }
+        }
+    }
+    /// Returns the signed area between the chord and the arc and that area's centroid, or `None` for a line.
+    fn circular_segment(&self) -> Option<(f64, Point)> {
+        match self {
+            LwPolylineSegment::Line { .. } => None,
+            LwPolylineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                ..
+            } => {
+                let sweep = self.sweep();
+                let theta = sweep.abs();
+                if theta == 0.0 {
+                    return None;
+                }
+
+                // the centroid is on the line from the center through the arc's midpoint
+                let area = radius * radius / 2.0 * (theta - theta.sin());
+                let distance =
+                    4.0 * radius * (theta / 2.0).sin().powi(3) / (3.0 * (theta - theta.sin()));
+                let centroid =
+                    point_on_circle(center, distance, start_angle.to_radians() + sweep / 2.0);
+                Some((area * sweep.signum(), centroid))
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                    LwPolyline
+//------------------------------------------------------------------------------
+impl LwPolyline {
+    /// Creates a polyline that visits the start of each segment in turn and ends at the end of the last one.  The
+    /// polyline is marked as closed instead if the last segment ends where the first one starts.  Gaps between
+    /// segments become straight lines.
+    pub fn from_segments(segments: &[LwPolylineSegment]) -> LwPolyline {
+        let mut poly = LwPolyline::default();
+        for segment in segments {
+            let start = segment.start_point();
+            poly.vertices.push(LwPolylineVertex {
+                x: start.x,
+                y: start.y,
+                bulge: segment.bulge(),
+                ..Default::default()
+            });
+        }
+
+        if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+            let (start, end) = (first.start_point(), last.end_point());
+            if (end.x - start.x).abs() <= CLOSED_TOLERANCE
+                && (end.y - start.y).abs() <= CLOSED_TOLERANCE
+            {
+                poly.set_is_closed(true);
+            } else {
+                poly.vertices.push(LwPolylineVertex {
+                    x: end.x,
+                    y: end.y,
+                    ..Default::default()
+                });
+            }
+        }
+
+        poly
+    }
+    /// Returns the segments between consecutive vertices, including the one from the last vertex back to the first if
+    /// the polyline is closed.
+    pub fn segments(&self) -> impl Iterator<Item = LwPolylineSegment> + '_ {
+        let count = match self.vertices.len() {
+            0 | 1 => 0,
+            n if self.is_closed() => n,
+            n => n - 1,
+        };
+        (0..count).map(move |i| {
+            let start = &self.vertices[i];
+            let end = &self.vertices[(i + 1) % self.vertices.len()];
+            LwPolylineSegment::from_bulge(
+                &Point::new(start.x, start.y, 0.0),
+                &Point::new(end.x, end.y, 0.0),
+                start.bulge,
+            )
+        })
+    }
+    /// Returns the total length of the segments.
+    pub fn length(&self) -> f64 {
+        self.segments().map(|s| s.length()).sum()
+    }
+    /// Returns the area enclosed by a closed polyline, which is positive when its vertices run counter-clockwise, or
+    /// `None` if the polyline isn't closed.
+    pub fn area(&self) -> Option<f64> {
+        if !self.is_closed() {
+            return None;
+        }
+
+        Some(self.area_and_moments().0)
+    }
+    /// Returns the centroid of the area enclosed by a closed polyline, or `None` if the polyline isn't closed or
+    /// encloses no area.
+    pub fn centroid(&self) -> Option<Point> {
+        if !self.is_closed() {
+            return None;
+        }
+
+        let (area, moment_x, moment_y) = self.area_and_moments();
+        if area == 0.0 {
+            return None;
+        }
+
+        Some(Point::new(moment_x / area, moment_y / area, 0.0))
+    }
+    /// Returns the point the specified distance along the polyline from its first vertex, or `None` if the distance is
+    /// negative or longer than the polyline.
+    pub fn point_at_distance(&self, distance: f64) -> Option<Point> {
+        if distance < 0.0 {
+            return None;
+        }
+
+        let mut remaining = distance;
+        let mut last = None;
+        for segment in self.segments() {
+            let length = segment.length();
+            if remaining <= length {
+                return Some(segment.point_at_distance(remaining));
+            }
+
+            remaining -= length;
+            last = Some(segment);
+        }
+
+        // allow for the rounding of the accumulated lengths
+        match last {
+            Some(segment) if remaining <= CLOSED_TOLERANCE => Some(segment.end_point()),
+            None if distance == 0.0 => self.vertices.first().map(|v| Point::new(v.x, v.y, 0.0)),
+            _ => None,
+        }
+    }
+    /// Reverses the direction of the polyline without changing its shape.  The bulges and widths move to the vertex at
+    /// the other end of each segment, and the bulges are negated.  A closed polyline keeps its first vertex.
+    pub fn reverse(&mut self) {
+        let count = self.vertices.len();
+        if count < 2 {
+            return;
+        }
+
+        let is_closed = self.is_closed();
+        let original = self.vertices.clone();
+        for (k, vertex) in self.vertices.iter_mut().enumerate() {
+            let (position, segment) = if is_closed {
+                ((count - k) % count, (2 * count - k - 1) % count)
+            } else {
+                (count - 1 - k, (2 * count - 2 - k) % count)
+            };
+            let segment = &original[segment];
+            *vertex = LwPolylineVertex {
+                bulge: -segment.bulge,
+                starting_width: segment.ending_width,
+                ending_width: segment.starting_width,
+                ..original[position]
+            };
+        }
+
+        // the last vertex of an open polyline doesn't start a segment
+        if !is_closed {
+            let last = &mut self.vertices[count - 1];
+            last.bulge = 0.0;
+            last.starting_width = 0.0;
+            last.ending_width = 0.0;
+        }
+    }
+}
+
+// private implementation
+impl LwPolyline {
+    /// Returns the signed area enclosed by the segments and its first moments about the Y and X axes.
+    fn area_and_moments(&self) -> (f64, f64, f64) {
+        let (mut area, mut moment_x, mut moment_y) = (0.0, 0.0, 0.0);
+        for segment in self.segments() {
+            // the triangle from the origin to the chord
+            let (p1, p2) = (segment.start_point(), segment.end_point());
+            let cross = p1.x * p2.y - p2.x * p1.y;
+            area += cross / 2.0;
+            moment_x += (p1.x + p2.x) * cross / 6.0;
+            moment_y += (p1.y + p2.y) * cross / 6.0;
+
+            // the region between the chord and the arc
+            if let Some((segment_area, centroid)) = segment.circular_segment() {
+                area += segment_area;
+                moment_x += segment_area * centroid.x;
+                moment_y += segment_area * centroid.y;
+            }
+        }
+
+        (area, moment_x, moment_y)
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+fn point_on_circle(center: &Point, radius: f64, angle: f64) -> Point {
+    let (sin, cos) = angle.sin_cos();
+    Point::new(center.x + radius * cos, center.y + radius * sin, 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_point(expected: (f64, f64), actual: &Point) {
+        assert_close(expected.0, actual.x);
+        assert_close(expected.1, actual.y);
+    }
+
+    fn vertex(x: f64, y: f64, bulge: f64) -> LwPolylineVertex {
+        LwPolylineVertex {
+            x,
+            y,
+            bulge,
+            ..Default::default()
+        }
+    }
+
+    /// A 2x2 square with its right side replaced by a half circle bulging outward.
+    fn rounded_square() -> LwPolyline {
+        let mut poly = LwPolyline {
+            vertices: vec![
+                vertex(0.0, 0.0, 0.0),
+                vertex(2.0, 0.0, 1.0),
+                vertex(2.0, 2.0, 0.0),
+                vertex(0.0, 2.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        poly.set_is_closed(true);
+        poly
+    }
+
+    #[test]
+    fn segments_from_bulges() {
+        let segments = rounded_square().segments().collect::<Vec<_>>();
+        assert_eq!(4, segments.len());
+        match segments[1] {
+            LwPolylineSegment::Arc {
+                ref center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                assert_point((2.0, 1.0), center);
+                assert_close(1.0, radius);
+                assert_close(270.0, start_angle);
+                assert_close(90.0, end_angle);
+                assert!(is_counter_clockwise);
+            }
+            _ => panic!("expected an arc"),
+        }
+        assert_point((0.0, 0.0), &segments[3].end_point());
+    }
+
+    #[test]
+    fn clockwise_bulge() {
+        let segment = LwPolylineSegment::from_bulge(
+            &Point::new(0.0, 0.0, 0.0),
+            &Point::new(2.0, 0.0, 0.0),
+            -1.0,
+        );
+        assert_point((1.0, 1.0), &segment.point_at_distance(PI / 2.0));
+        assert_close(-1.0, segment.bulge());
+        assert_close(1.0, segment.reversed().bulge());
+    }
+
+    #[test]
+    fn length_and_point_at_distance() {
+        let poly = rounded_square();
+        assert_close(6.0 + PI, poly.length());
+        assert_point((1.0, 0.0), &poly.point_at_distance(1.0).unwrap());
+
+        // halfway around the half circle
+        assert_point((3.0, 1.0), &poly.point_at_distance(2.0 + PI / 2.0).unwrap());
+        assert_point((0.0, 0.0), &poly.point_at_distance(6.0 + PI).unwrap());
+        assert!(poly.point_at_distance(7.0 + PI).is_none());
+        assert!(poly.point_at_distance(-1.0).is_none());
+    }
+
+    #[test]
+    fn area_and_centroid() {
+        let poly = rounded_square();
+        assert_close(4.0 + PI / 2.0, poly.area().unwrap());
+
+        // the half disk's centroid is 4r/3pi to the right of its diameter
+        let centroid = poly.centroid().unwrap();
+        let expected_x = (4.0 * 1.0 + PI / 2.0 * (2.0 + 4.0 / (3.0 * PI))) / (4.0 + PI / 2.0);
+        assert_point((expected_x, 1.0), &centroid);
+
+        // clockwise polylines have a negative area
+        let mut reversed = poly.clone();
+        reversed.reverse();
+        assert_close(-(4.0 + PI / 2.0), reversed.area().unwrap());
+        assert_point((expected_x, 1.0), &reversed.centroid().unwrap());
+
+        let mut open = poly;
+        open.set_is_closed(false);
+        assert!(open.area().is_none());
+        assert!(open.centroid().is_none());
+    }
+
+    #[test]
+    fn reverse_open_polyline() {
+        let mut poly = LwPolyline {
+            vertices: vec![
+                LwPolylineVertex {
+                    starting_width: 1.0,
+                    ending_width: 2.0,
+                    ..vertex(0.0, 0.0, 0.5)
+                },
+                vertex(1.0, 0.0, 0.0),
+                vertex(1.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        let original = poly.segments().collect::<Vec<_>>();
+        poly.reverse();
+        assert_point(
+            (1.0, 1.0),
+            &Point::new(poly.vertices[0].x, poly.vertices[0].y, 0.0),
+        );
+        assert_close(0.0, poly.vertices[0].bulge);
+        assert_close(-0.5, poly.vertices[1].bulge);
+        assert_close(2.0, poly.vertices[1].starting_width);
+        assert_close(1.0, poly.vertices[1].ending_width);
+        assert_close(0.0, poly.vertices[2].bulge);
+
+        let reversed = poly.segments().collect::<Vec<_>>();
+        let midpoint = original[0].point_at_distance(original[0].length() / 2.0);
+        assert_point(
+            (midpoint.x, midpoint.y),
+            &reversed[1].point_at_distance(reversed[1].length() / 2.0),
+        );
+    }
+
+    #[test]
+    fn reverse_closed_polyline_keeps_first_vertex() {
+        let mut poly = rounded_square();
+        poly.reverse();
+        let positions = poly
+            .vertices
+            .iter()
+            .map(|v| (v.x, v.y, v.bulge))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (0.0, 0.0, 0.0),
+                (0.0, 2.0, 0.0),
+                (2.0, 2.0, -1.0),
+                (2.0, 0.0, 0.0)
+            ],
+            positions
+        );
+    }
+
+    #[test]
+    fn from_segments() {
+        let segments = rounded_square().segments().collect::<Vec<_>>();
+        let poly = LwPolyline::from_segments(&segments);
+        assert!(poly.is_closed());
+        assert_eq!(4, poly.vertices.len());
+        assert_close(1.0, poly.vertices[1].bulge);
+        assert_close(6.0 + PI, poly.length());
+
+        let poly = LwPolyline::from_segments(&[
+            LwPolylineSegment::Line {
+                start: Point::new(0.0, 0.0, 0.0),
+                end: Point::new(1.0, 0.0, 0.0),
+            },
+            LwPolylineSegment::Arc {
+                center: Point::new(1.0, 1.0, 0.0),
+                radius: 1.0,
+                start_angle: 270.0,
+                end_angle: 180.0,
+                is_counter_clockwise: false,
+            },
+        ]);
+        assert!(!poly.is_closed());
+        assert_eq!(3, poly.vertices.len());
+        assert_close(-(PI / 8.0).tan(), poly.vertices[1].bulge);
+        assert_point(
+            (0.0, 1.0),
+            &Point::new(poly.vertices[2].x, poly.vertices[2].y, 0.0),
+        );
+    }
+}
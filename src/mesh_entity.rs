@@ -0,0 +1,290 @@
+// other implementation is in `generated/entities.rs`
+
+use std::collections::HashSet;
+
+use crate::{CodePair, DxfError, DxfResult, Point};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::entities::*;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+//------------------------------------------------------------------------------
+//                                                                      MeshEdge
+//------------------------------------------------------------------------------
+/// Represents a single edge of a `Mesh` as the indices of its two vertices.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshEdge {
+    pub start_vertex: usize,
+    pub end_vertex: usize,
+}
+
+impl MeshEdge {
+    pub fn new(start_vertex: usize, end_vertex: usize) -> Self {
+        MeshEdge {
+            start_vertex,
+            end_vertex,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                         MeshSubentityOverride
+//------------------------------------------------------------------------------
+/// Represents the overridden properties of a single face, edge, or vertex of a `Mesh`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshSubentityOverride {
+    pub subentity_marker: i32,
+    pub properties: Vec<MeshOverrideProperty>,
+}
+
+//------------------------------------------------------------------------------
+//                                                          MeshOverrideProperty
+//------------------------------------------------------------------------------
+/// Represents a single overridden property of a mesh subentity.  The layout of the value isn't documented, so the
+/// code pairs that follow the property type are kept as-is.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshOverrideProperty {
+    /// 0 for color, 1 for material, 2 for transparency, and 3 for the material mapper.
+    pub property_type: i32,
+    pub values: Vec<CodePair>,
+}
+
+//------------------------------------------------------------------------------
+//                                                                          Mesh
+//------------------------------------------------------------------------------
+impl Mesh {
+    /// Creates a new mesh from the specified vertices and faces, where each face is a list of indices into
+    /// `vertices`.  The edge list is built from the faces, with each shared edge listed once and given no crease.
+    pub fn from_faces(vertices: Vec<Point>, faces: Vec<Vec<usize>>) -> Self {
+        let mut seen = HashSet::new();
+        let mut edges = vec![];
+        for face in &faces {
+            for (i, &start) in face.iter().enumerate() {
+                let end = face[(i + 1) % face.len()];
+                if start != end && seen.insert((start.min(end), start.max(end))) {
+                    edges.push(MeshEdge::new(start, end));
+                }
+            }
+        }
+
+        let edge_creases = vec![0.0; edges.len()];
+        Mesh {
+            vertices,
+            faces,
+            edges,
+            edge_creases,
+            ..Default::default()
+        }
+    }
+    /// Splits each face into a fan of triangles and returns the vertex indices of each triangle, e.g., for export to
+    /// formats that only support triangles.  Faces with fewer than three vertices or with indices outside of
+    /// `vertices` are skipped.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        let mut triangles = vec![];
+        for face in &self.faces {
+            if face.len() < 3 || face.iter().any(|&i| i >= self.vertices.len()) {
+                continue;
+            }
+
+            for i in 1..face.len() - 1 {
+                triangles.push([face[0], face[i], face[i + 1]]);
+            }
+        }
+
+        triangles
+    }
+    /// Returns the corner points of each triangle from `triangles()`.
+    pub fn triangle_points(&self) -> Vec<[Point; 3]> {
+        self.triangles()
+            .iter()
+            .map(|t| {
+                [
+                    self.vertices[t[0]].clone(),
+                    self.vertices[t[1]].clone(),
+                    self.vertices[t[2]].clone(),
+                ]
+            })
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Reader
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn apply_custom_reader_mesh(
+        common: &mut EntityCommon,
+        mesh: &mut Mesh,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        // codes 91 and 92 are also used by the common entity data, so they're only mesh data after the subclass marker
+        let mut is_reading_mesh = false;
+        loop {
+            let pair = next_pair!(iter);
+            if !is_reading_mesh {
+                if pair.code == 100 && pair.assert_string()? == "AcDbSubDMesh" {
+                    is_reading_mesh = true;
+                } else {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+                continue;
+            }
+
+            match pair.code {
+                71 => {
+                    mesh.version = pair.assert_i16()?;
+                }
+                72 => {
+                    mesh.is_blend_crease = as_bool(pair.assert_i16()?);
+                }
+                91 => {
+                    mesh.subdivision_level = pair.assert_i32()?;
+                }
+                92 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let x = expect_pair(iter, 10)?.assert_f64()?;
+                        let y = expect_pair(iter, 20)?.assert_f64()?;
+                        let z = expect_pair(iter, 30)?.assert_f64()?;
+                        mesh.vertices.push(Point::new(x, y, z));
+                    }
+                }
+                93 => {
+                    // the face list is flattened as the vertex count of each face followed by its indices
+                    let mut remaining = pair.assert_i32()?;
+                    while remaining > 0 {
+                        let count = read_index(iter)?;
+                        let mut face = vec![];
+                        for _ in 0..count {
+                            face.push(read_index(iter)?);
+                        }
+                        mesh.faces.push(face);
+                        remaining -= count as i32 + 1;
+                    }
+                }
+                94 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let start_vertex = read_index(iter)?;
+                        let end_vertex = read_index(iter)?;
+                        mesh.edges.push(MeshEdge::new(start_vertex, end_vertex));
+                    }
+                }
+                95 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let crease = expect_pair(iter, 140)?.assert_f64()?;
+                        mesh.edge_creases.push(crease);
+                    }
+                }
+                90 => {
+                    for _ in 0..pair.assert_i32()? {
+                        let over = read_subentity_override(iter)?;
+                        mesh.overrides.push(over);
+                    }
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+}
+
+fn read_index(iter: &mut CodePairPutBack) -> DxfResult<usize> {
+    let pair = expect_pair(iter, 90)?;
+    let index = pair.assert_i32()?;
+    if index < 0 {
+        return Err(DxfError::UnexpectedCodePair(
+            pair,
+            String::from("expected a non-negative vertex index"),
+        ));
+    }
+
+    Ok(index as usize)
+}
+
+fn read_subentity_override(iter: &mut CodePairPutBack) -> DxfResult<MeshSubentityOverride> {
+    let mut over = MeshSubentityOverride {
+        subentity_marker: expect_pair(iter, 91)?.assert_i32()?,
+        ..Default::default()
+    };
+    for _ in 0..expect_pair(iter, 92)?.assert_i32()? {
+        let mut property = MeshOverrideProperty {
+            property_type: expect_pair(iter, 90)?.assert_i32()?,
+            ..Default::default()
+        };
+        loop {
+            match iter.next() {
+                Some(Ok(pair)) if is_override_value(&pair) => property.values.push(pair),
+                Some(Ok(pair)) => {
+                    iter.put_back(Ok(pair));
+                    break;
+                }
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+        over.properties.push(property);
+    }
+
+    Ok(over)
+}
+
+fn is_override_value(pair: &CodePair) -> bool {
+    // the next property, the next subentity, extension data, and x-data all end the value
+    !matches!(pair.code, 0 | 90 | 91 | 102) && pair.code < 1000
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Writer
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn add_custom_code_pairs_mesh(
+        pairs: &mut Vec<CodePair>,
+        mesh: &Mesh,
+        _version: AcadVersion,
+    ) -> bool {
+        pairs.push(CodePair::new_str(100, "AcDbSubDMesh"));
+        pairs.push(CodePair::new_i16(71, mesh.version));
+        pairs.push(CodePair::new_i16(72, as_i16(mesh.is_blend_crease)));
+        pairs.push(CodePair::new_i32(91, mesh.subdivision_level));
+        pairs.push(CodePair::new_i32(92, mesh.vertices.len() as i32));
+        for vertex in &mesh.vertices {
+            pairs.push(CodePair::new_f64(10, vertex.x));
+            pairs.push(CodePair::new_f64(20, vertex.y));
+            pairs.push(CodePair::new_f64(30, vertex.z));
+        }
+        let face_list_size: usize = mesh.faces.iter().map(|f| f.len() + 1).sum();
+        pairs.push(CodePair::new_i32(93, face_list_size as i32));
+        for face in &mesh.faces {
+            pairs.push(CodePair::new_i32(90, face.len() as i32));
+            for &index in face {
+                pairs.push(CodePair::new_i32(90, index as i32));
+            }
+        }
+        pairs.push(CodePair::new_i32(94, mesh.edges.len() as i32));
+        for edge in &mesh.edges {
+            pairs.push(CodePair::new_i32(90, edge.start_vertex as i32));
+            pairs.push(CodePair::new_i32(90, edge.end_vertex as i32));
+        }
+        pairs.push(CodePair::new_i32(95, mesh.edge_creases.len() as i32));
+        for &crease in &mesh.edge_creases {
+            pairs.push(CodePair::new_f64(140, crease));
+        }
+        pairs.push(CodePair::new_i32(90, mesh.overrides.len() as i32));
+        for over in &mesh.overrides {
+            pairs.push(CodePair::new_i32(91, over.subentity_marker));
+            pairs.push(CodePair::new_i32(92, over.properties.len() as i32));
+            for property in &over.properties {
+                pairs.push(CodePair::new_i32(90, property.property_type));
+                for value in &property.values {
+                    pairs.push(value.clone());
+                }
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,181 @@
+use crate::entities::*;
+use crate::enums::*;
+use crate::*;
+
+fn load(lines: Vec<&str>) -> DxfResult<Drawing> {
+    Drawing::load(&mut lines.join("\n").as_bytes())
+}
+
+fn load_leniently(lines: Vec<&str>) -> (Drawing, Vec<Diagnostic>) {
+    let options = LoadOptions {
+        lenient: true,
+        ..Default::default()
+    };
+    Drawing::load_with_options(&mut lines.join("\n").as_bytes(), &options).unwrap()
+}
+
+fn bad_code_lines() -> Vec<&'static str> {
+    vec![
+        "0", "SECTION", "2", "ENTITIES", "0", "LINE", "10", "1.0", "2O", "2.0", "11", "3.0", "0",
+        "ENDSEC", "0", "EOF",
+    ]
+}
+
+#[test]
+fn bad_code_is_an_error() {
+    assert!(load(bad_code_lines()).is_err());
+}
+
+#[test]
+fn bad_code_is_ignored_leniently() {
+    let (drawing, diagnostics) = load_leniently(bad_code_lines());
+    let entities = drawing.entities().collect::<Vec<_>>();
+    assert_eq!(1, entities.len());
+    match entities[0].specific {
+        EntityType::Line(ref line) => {
+            assert_eq!(Point::new(1.0, 0.0, 0.0), line.p1);
+            assert_eq!(Point::new(3.0, 0.0, 0.0), line.p2);
+        }
+        _ => panic!("expected a line"),
+    }
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Warning, diagnostics[0].severity);
+    assert_eq!("LINE", diagnostics[0].item_type);
+    assert_eq!(FilePosition::Line(9), diagnostics[0].position);
+}
+
+fn bad_header_lines() -> Vec<&'static str> {
+    vec![
+        "0",
+        "SECTION",
+        "2",
+        "HEADER",
+        "9",
+        "$ACADVER",
+        "70",
+        "5",
+        "9",
+        "$CLAYER",
+        "8",
+        "some-layer",
+        "0",
+        "ENDSEC",
+        "0",
+        "EOF",
+    ]
+}
+
+#[test]
+fn bad_header_value_is_an_error() {
+    assert!(load(bad_header_lines()).is_err());
+}
+
+#[test]
+fn bad_header_value_is_defaulted_leniently() {
+    let (drawing, diagnostics) = load_leniently(bad_header_lines());
+    assert_eq!(AcadVersion::R12, drawing.header.version);
+    assert_eq!("some-layer", drawing.header.current_layer);
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Warning, diagnostics[0].severity);
+    assert_eq!("HEADER", diagnostics[0].section);
+    assert_eq!("$ACADVER", diagnostics[0].item_type);
+}
+
+fn bad_dimension_type_lines() -> Vec<&'static str> {
+    vec![
+        "0",
+        "SECTION",
+        "2",
+        "ENTITIES",
+        "0",
+        "DIMENSION",
+        "70",
+        "7",
+        "100",
+        "AcDbAlignedDimension",
+        "13",
+        "1.0",
+        "0",
+        "ENDSEC",
+        "0",
+        "EOF",
+    ]
+}
+
+#[test]
+fn bad_dimension_type_is_an_error() {
+    assert!(load(bad_dimension_type_lines()).is_err());
+}
+
+#[test]
+fn bad_dimension_type_is_defaulted_leniently() {
+    let (drawing, diagnostics) = load_leniently(bad_dimension_type_lines());
+    let entities = drawing.entities().collect::<Vec<_>>();
+    assert_eq!(1, entities.len());
+    match entities[0].specific {
+        EntityType::RotatedDimension(ref dim) => {
+            assert_eq!(DimensionType::Aligned, dim.dimension_base.dimension_type);
+            assert_eq!(Point::new(1.0, 0.0, 0.0), dim.definition_point_2);
+        }
+        _ => panic!("expected a rotated dimension"),
+    }
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Warning, diagnostics[0].severity);
+    assert_eq!("DIMENSION", diagnostics[0].item_type);
+    assert_eq!(FilePosition::Line(7), diagnostics[0].position);
+}
+
+fn unreadable_object_lines() -> Vec<&'static str> {
+    vec![
+        "0",
+        "SECTION",
+        "2",
+        "OBJECTS",
+        "0",
+        "SPATIAL_FILTER",
+        "5",
+        "ABC",
+        "20",
+        "1.0",
+        "0",
+        "DICTIONARY",
+        "0",
+        "ENDSEC",
+        "0",
+        "EOF",
+    ]
+}
+
+#[test]
+fn unreadable_object_is_an_error() {
+    assert!(load(unreadable_object_lines()).is_err());
+}
+
+#[test]
+fn unreadable_object_is_dropped_leniently() {
+    let (drawing, diagnostics) = load_leniently(unreadable_object_lines());
+    let objects = drawing.objects().collect::<Vec<_>>();
+    assert_eq!(1, objects.len());
+    match objects[0].specific {
+        objects::ObjectType::Dictionary(_) => (),
+        _ => panic!("expected a dictionary"),
+    }
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, diagnostics[0].severity);
+    assert_eq!("OBJECTS", diagnostics[0].section);
+    assert_eq!("SPATIAL_FILTER", diagnostics[0].item_type);
+    assert_eq!(Handle(0xABC), diagnostics[0].handle);
+}
+
+#[test]
+fn code_pairs_outside_of_sections_are_skipped_leniently() {
+    let (drawing, diagnostics) = load_leniently(vec![
+        "1", "garbage", "0", "SECTION", "2", "ENTITIES", "0", "LINE", "0", "ENDSEC", "0",
+        "GARBAGE", "0", "EOF",
+    ]);
+    assert_eq!(1, drawing.entities().count());
+    assert_eq!(2, diagnostics.len());
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.severity == DiagnosticSeverity::Error));
+}
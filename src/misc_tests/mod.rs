@@ -6,3 +6,6 @@ mod integration;
 
 #[cfg(test)]
 mod pointers;
+
+#[cfg(test)]
+mod lenient;
@@ -0,0 +1,1145 @@
+// other implementation is in `entity.rs`
+
+use crate::entities::Spline;
+use crate::{DxfError, DxfResult, Point, Vector};
+
+/// The abscissas and weights of 5-point Gauss-Legendre quadrature on [-1, 1].
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.0, 0.568_888_888_888_888_9),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+/// The number of pieces each knot span is split into when measuring the length of a curve.
+const LENGTH_PIECES_PER_SPAN: usize = 4;
+
+/// Pivots smaller than this make a linear system singular.
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// Weights within this distance of 1 are treated as a non-rational curve.
+const WEIGHT_TOLERANCE: f64 = 1e-12;
+
+/// A control point multiplied by its weight, followed by the weight.
+type Homogeneous = [f64; 4];
+
+//------------------------------------------------------------------------------
+//                                                                        Spline
+//------------------------------------------------------------------------------
+impl Spline {
+    /// Checks that the control points, knots, and weights describe a curve of the spline's degree: there must be more
+    /// control points than the degree, the number of knots must be the number of control points plus the degree plus
+    /// one, the knots must not decrease, and the weights must be positive and either absent or one per control point.
+    /// A spline without control points is valid if it has at least two fit points to compute them from.
+    pub fn validate(&self) -> DxfResult<()> {
+        if self.degree_of_curve < 1 {
+            return invalid_spline(format!(
+                "the degree is {}, but must be at least 1",
+                self.degree_of_curve
+            ));
+        }
+
+        let degree = self.degree_of_curve as usize;
+        let count = self.control_points.len();
+        if count == 0 && self.fit_points.len() >= 2 {
+            return Ok(());
+        }
+        if count <= degree {
+            return invalid_spline(format!(
+                "a curve of degree {} needs at least {} control points, but there are {}",
+                degree,
+                degree + 1,
+                count
+            ));
+        }
+        if self.knot_values.len() != count + degree + 1 {
+            return invalid_spline(format!(
+                "{} control points of degree {} need {} knots, but there are {}",
+                count,
+                degree,
+                count + degree + 1,
+                self.knot_values.len()
+            ));
+        }
+        if self.knot_values.windows(2).any(|pair| pair[1] < pair[0]) {
+            return invalid_spline(String::from("the knot values decrease"));
+        }
+        if self.knot_values[degree] >= self.knot_values[count] {
+            return invalid_spline(String::from(
+                "the knot values don't span a range of parameters",
+            ));
+        }
+        if !self.weight_values.is_empty() && self.weight_values.len() != count {
+            return invalid_spline(format!(
+                "{} control points need {} weights, but there are {}",
+                count,
+                count,
+                self.weight_values.len()
+            ));
+        }
+        if self.weight_values.iter().any(|w| w.is_nan() || *w <= 0.0) {
+            return invalid_spline(String::from("the weights must be positive"));
+        }
+
+        Ok(())
+    }
+    /// Returns the range of parameters that the curve is defined over, or `None` if the curve can't be determined.
+    ///
+    /// The evaluation methods follow the same curve as `Entity::tessellate()`, so a spline with only fit points is
+    /// interpolated through them and missing or mismatched knots are replaced by uniform ones.  Use
+    /// `Spline::validate()` to detect those cases.
+    pub fn parameter_range(&self) -> Option<(f64, f64)> {
+        NurbsCurve::from_spline(self).map(|curve| curve.domain())
+    }
+    /// Returns the point on the curve at parameter `u`, which is clamped to the parameter range.  See
+    /// `Spline::parameter_range()` for how the curve is determined.
+    pub fn point_at(&self, u: f64) -> Option<Point> {
+        NurbsCurve::from_spline(self).map(|curve| curve.point_at(curve.clamp_parameter(u)))
+    }
+    /// Returns the derivative of the specified order with respect to the parameter at `u`, e.g., `1` for the tangent
+    /// and `2` for the second derivative.  Order `0` returns the point as a vector.  See `Spline::parameter_range()` for
+    /// how the curve is determined.
+    pub fn derivative_at(&self, u: f64, order: usize) -> Option<Vector> {
+        NurbsCurve::from_spline(self).map(|curve| {
+            curve
+                .derivatives_at(curve.clamp_parameter(u), order)
+                .swap_remove(order)
+        })
+    }
+    /// Returns the length of the curve, computed by numerical integration.  See `Spline::parameter_range()` for how
+    /// the curve is determined.
+    pub fn length(&self) -> Option<f64> {
+        NurbsCurve::from_spline(self).map(|curve| curve.length())
+    }
+    /// Inserts a knot at parameter `u` without changing the shape of the curve, which adds one control point.  Fails if
+    /// the spline isn't valid, `u` is outside the parameter range, or the knot already appears as many times as the
+    /// degree.
+    pub fn insert_knot(&mut self, u: f64) -> DxfResult<()> {
+        let mut curve = self.control_point_curve()?;
+        let (start, end) = curve.domain();
+        if u.is_nan() || u < start || u > end {
+            return invalid_spline(format!(
+                "the knot {} is outside of the parameter range {} to {}",
+                u, start, end
+            ));
+        }
+
+        let multiplicity = curve.multiplicity(u);
+        if multiplicity >= curve.degree {
+            return invalid_spline(format!(
+                "the knot {} already appears {} times",
+                u, multiplicity
+            ));
+        }
+
+        curve.insert_knot(u);
+        self.set_curve(curve);
+        Ok(())
+    }
+    /// Raises the degree of the curve by one without changing its shape.  The knots at the ends are clamped first, so a
+    /// periodic spline is no longer marked as periodic.  Fails if the spline isn't valid.
+    pub fn elevate_degree(&mut self) -> DxfResult<()> {
+        let curve = self
+            .control_point_curve()?
+            .elevate_degree()
+            .ok_or_else(|| DxfError::InvalidSpline(String::from("the degree can't be elevated")))?;
+        self.set_is_periodic(false);
+        self.set_curve(curve);
+        Ok(())
+    }
+    /// Replaces the control points, knots, and weights with a curve of the spline's degree that passes through the fit
+    /// points.  The fit points are parameterized by the distance between them, and a non-zero `start_tangent` or
+    /// `end_tangent` sets the direction of the curve at that end.  The degree is lowered if there aren't enough fit
+    /// points for it.  Fails if there are fewer than two fit points or two consecutive ones are the same.
+    pub fn interpolate_fit_points(&mut self) -> DxfResult<()> {
+        if self.degree_of_curve < 1 {
+            return invalid_spline(format!(
+                "the degree is {}, but must be at least 1",
+                self.degree_of_curve
+            ));
+        }
+        if self.fit_points.len() < 2 {
+            return invalid_spline(String::from("at least two fit points are needed"));
+        }
+
+        let curve = NurbsCurve::interpolate(
+            self.degree_of_curve as usize,
+            &self.fit_points,
+            tangent(&self.start_tangent),
+            tangent(&self.end_tangent),
+        )
+        .ok_or_else(|| {
+            DxfError::InvalidSpline(String::from("consecutive fit points must be distinct"))
+        })?;
+        self.set_curve(curve);
+        Ok(())
+    }
+    /// Creates a spline of the specified degree that passes through `fit_points`, optionally in the direction of the
+    /// specified tangents at its ends.  See `Spline::interpolate_fit_points()`.
+    pub fn through_points(
+        degree: i32,
+        fit_points: Vec<Point>,
+        start_tangent: Option<Vector>,
+        end_tangent: Option<Vector>,
+    ) -> DxfResult<Spline> {
+        let as_point = |v: Option<Vector>| v.map(|v| Point::new(v.x, v.y, v.z)).unwrap_or_default();
+        let mut spline = Spline {
+            degree_of_curve: degree,
+            fit_points,
+            start_tangent: as_point(start_tangent),
+            end_tangent: as_point(end_tangent),
+            ..Default::default()
+        };
+        spline.interpolate_fit_points()?;
+        Ok(spline)
+    }
+}
+
+// private implementation
+impl Spline {
+    /// Returns the curve given by the control points, which must be valid.
+    fn control_point_curve(&self) -> DxfResult<NurbsCurve> {
+        self.validate()?;
+        if self.control_points.is_empty() {
+            return invalid_spline(String::from("there are no control points"));
+        }
+
+        NurbsCurve::from_spline(self)
+            .ok_or_else(|| DxfError::InvalidSpline(String::from("there are no control points")))
+    }
+    fn set_curve(&mut self, curve: NurbsCurve) {
+        let is_rational = curve
+            .weights
+            .iter()
+            .any(|w| (w - 1.0).abs() > WEIGHT_TOLERANCE);
+        self.degree_of_curve = curve.degree as i32;
+        self.knot_values = curve.knots;
+        self.control_points = curve.control_points;
+        self.weight_values = if is_rational { curve.weights } else { vec![] };
+        self.set_is_rational(is_rational);
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                    NurbsCurve
+//------------------------------------------------------------------------------
+/// A non-uniform rational B-spline curve in world coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NurbsCurve {
+    pub degree: usize,
+    pub knots: Vec<f64>,
+    pub control_points: Vec<Point>,
+    pub weights: Vec<f64>,
+}
+
+// internal visibility only
+impl NurbsCurve {
+    /// Creates the curve described by the spline's control points.  A knot vector of the wrong length is replaced by a
+    /// clamped uniform one, or for a closed or periodic spline, the control points are wrapped around so that the curve
+    /// closes smoothly.  A spline without enough control points is interpolated through its fit points.  Returns
+    /// `None` if neither is possible.
+    pub fn from_spline(spline: &Spline) -> Option<Self> {
+        let degree = usize::try_from(spline.degree_of_curve).ok()?.max(1);
+        let control_points = spline.control_points.clone();
+        if control_points.len() <= degree {
+            return NurbsCurve::interpolate(
+                degree,
+                &spline.fit_points,
+                tangent(&spline.start_tangent),
+                tangent(&spline.end_tangent),
+            );
+        }
+
+        let weights = if spline.weight_values.len() == control_points.len()
+            && spline.weight_values.iter().all(|w| *w > 0.0)
+        {
+            spline.weight_values.clone()
+        } else {
+            vec![1.0; control_points.len()]
+        };
+
+        if spline.knot_values.len() == control_points.len() + degree + 1 {
+            Some(NurbsCurve {
+                degree,
+                knots: spline.knot_values.clone(),
+                control_points,
+                weights,
+            })
+        } else if spline.is_closed() || spline.is_periodic() {
+            NurbsCurve::periodic(degree, control_points, weights)
+        } else {
+            Some(NurbsCurve {
+                degree,
+                knots: NurbsCurve::clamped_knots(control_points.len(), degree),
+                control_points,
+                weights,
+            })
+        }
+    }
+    /// Creates a non-rational curve that starts at the first control point and ends at the last one.  Returns `None`
+    /// if there are too few control points for the degree.
+    pub fn clamped(degree: usize, control_points: Vec<Point>) -> Option<Self> {
+        let degree = degree.max(1);
+        if control_points.len() <= degree {
+            return None;
+        }
+
+        Some(NurbsCurve {
+            degree,
+            knots: NurbsCurve::clamped_knots(control_points.len(), degree),
+            weights: vec![1.0; control_points.len()],
+            control_points,
+        })
+    }
+    /// Creates a curve that smoothly joins its end to its start by wrapping the first `degree` control points around.
+    /// Returns `None` if there are too few control points for the degree.
+    pub fn periodic(
+        degree: usize,
+        mut control_points: Vec<Point>,
+        mut weights: Vec<f64>,
+    ) -> Option<Self> {
+        let degree = degree.max(1);
+        if control_points.len() <= degree || weights.len() != control_points.len() {
+            return None;
+        }
+
+        for i in 0..degree {
+            control_points.push(control_points[i].clone());
+            weights.push(weights[i]);
+        }
+        Some(NurbsCurve {
+            degree,
+            knots: (0..control_points.len() + degree + 1)
+                .map(|i| i as f64)
+                .collect(),
+            control_points,
+            weights,
+        })
+    }
+    /// Creates a non-rational curve that passes through `points` in order, optionally with the specified derivatives at
+    /// its ends.  The degree is lowered if there aren't enough conditions for it.  Returns `None` if there are fewer
+    /// than two points or two consecutive points are the same.
+    pub fn interpolate(
+        degree: usize,
+        points: &[Point],
+        start_tangent: Option<Vector>,
+        end_tangent: Option<Vector>,
+    ) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+
+        // parameterize by the distance between the points
+        let chords = points
+            .windows(2)
+            .map(|pair| distance(&pair[0], &pair[1]))
+            .collect::<Vec<_>>();
+        let total = chords.iter().sum::<f64>();
+        if chords.contains(&0.0) {
+            return None;
+        }
+
+        let mut parameters = vec![0.0];
+        for chord in &chords {
+            parameters.push(parameters.last().unwrap() + chord / total);
+        }
+        *parameters.last_mut().unwrap() = 1.0;
+
+        // each end tangent adds a condition, which is scaled to the parameterization
+        let derivative = |t: Option<Vector>| t.map(|t| t.normalized().scaled(total));
+        let mut conditions = vec![(0.0, 0, point_vector(&points[0]))];
+        if let Some(d) = derivative(start_tangent) {
+            conditions.push((0.0, 1, d));
+        }
+        for (u, p) in parameters.iter().zip(points).skip(1).take(points.len() - 2) {
+            conditions.push((*u, 0, point_vector(p)));
+        }
+        if let Some(d) = derivative(end_tangent) {
+            conditions.push((1.0, 1, d));
+        }
+        conditions.push((1.0, 0, point_vector(&points[points.len() - 1])));
+
+        // average the parameters into the interior knots
+        let count = conditions.len();
+        let degree = degree.max(1).min(count - 1);
+        let mut knots = vec![0.0; degree + 1];
+        for j in 1..count - degree {
+            let sum = conditions[j..j + degree]
+                .iter()
+                .map(|(u, _, _)| u)
+                .sum::<f64>();
+            knots.push(sum / degree as f64);
+        }
+        knots.extend(vec![1.0; degree + 1]);
+
+        let mut curve = NurbsCurve {
+            degree,
+            knots,
+            control_points: vec![Point::origin(); count],
+            weights: vec![1.0; count],
+        };
+        let mut matrix = vec![vec![0.0; count]; count];
+        let mut values = vec![];
+        for (row, (u, order, value)) in conditions.iter().enumerate() {
+            let span = curve.span(*u);
+            let basis = curve.basis_derivatives(span, *u, *order);
+            for (j, b) in basis[*order].iter().enumerate() {
+                matrix[row][span - degree + j] = *b;
+            }
+            values.push(vec![value.x, value.y, value.z]);
+        }
+
+        let solution = solve(matrix, values)?;
+        curve.control_points = solution
+            .iter()
+            .map(|p| Point::new(p[0], p[1], p[2]))
+            .collect();
+        Some(curve)
+    }
+    /// Returns the range of parameters that the curve is defined over.
+    pub fn domain(&self) -> (f64, f64) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+    /// Returns the distinct knot values within the domain, which are the parameters where the curve's pieces meet.
+    pub fn breakpoints(&self) -> Vec<f64> {
+        let (start, end) = self.domain();
+        let mut breakpoints: Vec<f64> = vec![];
+        for knot in &self.knots[self.degree..=self.control_points.len()] {
+            if *knot >= start && *knot <= end && breakpoints.last() != Some(knot) {
+                breakpoints.push(*knot);
+            }
+        }
+
+        breakpoints
+    }
+    /// Evaluates the curve at parameter `u` with de Boor's algorithm in homogeneous coordinates.
+    pub fn point_at(&self, u: f64) -> Point {
+        let p = self.degree;
+        let span = self.span(u);
+        let mut d: Vec<Homogeneous> = (0..=p).map(|j| self.homogeneous(span - p + j)).collect();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let left = self.knots[j + span - p];
+                let right = self.knots[j + 1 + span - r];
+                let alpha = if right == left {
+                    0.0
+                } else {
+                    (u - left) / (right - left)
+                };
+                d[j] = lerp(&d[j - 1], &d[j], alpha);
+            }
+        }
+
+        let [x, y, z, w] = d[p];
+        Point::new(x / w, y / w, z / w)
+    }
+    /// Returns the point (as a vector) and its derivatives up to `order` at parameter `u`.
+    pub fn derivatives_at(&self, u: f64, order: usize) -> Vec<Vector> {
+        // differentiate the homogeneous curve, then apply the quotient rule to remove the weights
+        let span = self.span(u);
+        let basis = self.basis_derivatives(span, u, order);
+        let homogeneous = basis
+            .iter()
+            .map(|row| {
+                let mut sum = [0.0; 4];
+                for (j, b) in row.iter().enumerate() {
+                    let point = self.homogeneous(span - self.degree + j);
+                    for (s, c) in sum.iter_mut().zip(point) {
+                        *s += b * c;
+                    }
+                }
+                sum
+            })
+            .collect::<Vec<_>>();
+
+        let mut derivatives: Vec<Vector> = vec![];
+        for k in 0..=order {
+            let [x, y, z, _] = homogeneous[k];
+            let mut v = Vector::new(x, y, z);
+            for i in 1..=k {
+                let factor = binomial(k, i) as f64 * homogeneous[i][3];
+                let previous = &derivatives[k - i];
+                v = Vector::new(
+                    v.x - factor * previous.x,
+                    v.y - factor * previous.y,
+                    v.z - factor * previous.z,
+                );
+            }
+            derivatives.push(v.scaled(1.0 / homogeneous[0][3]));
+        }
+
+        derivatives
+    }
+    /// Returns the length of the curve by integrating the length of its tangent over each piece.
+    pub fn length(&self) -> f64 {
+        let mut length = 0.0;
+        for span in self.breakpoints().windows(2) {
+            let step = (span[1] - span[0]) / LENGTH_PIECES_PER_SPAN as f64;
+            for piece in 0..LENGTH_PIECES_PER_SPAN {
+                let middle = span[0] + step * (piece as f64 + 0.5);
+                for (x, weight) in GAUSS_LEGENDRE {
+                    let tangent = &self.derivatives_at(middle + x * step / 2.0, 1)[1];
+                    length += weight * tangent.length() * step / 2.0;
+                }
+            }
+        }
+
+        length
+    }
+    pub fn clamp_parameter(&self, u: f64) -> f64 {
+        let (start, end) = self.domain();
+        u.clamp(start, end)
+    }
+    /// Returns the number of times `u` appears in the knot vector.
+    pub fn multiplicity(&self, u: f64) -> usize {
+        self.knots.iter().filter(|k| **k == u).count()
+    }
+    /// Inserts the knot `u` with Boehm's algorithm, which adds a control point without changing the curve.
+    pub fn insert_knot(&mut self, u: f64) {
+        let p = self.degree;
+        let span = self.span(u);
+        let old = (0..self.control_points.len())
+            .map(|i| self.homogeneous(i))
+            .collect::<Vec<_>>();
+        let mut new = Vec::with_capacity(old.len() + 1);
+        for i in 0..=old.len() {
+            if i <= span - p {
+                new.push(old[i]);
+            } else if i <= span {
+                let alpha = (u - self.knots[i]) / (self.knots[i + p] - self.knots[i]);
+                new.push(lerp(&old[i - 1], &old[i], alpha));
+            } else {
+                new.push(old[i - 1]);
+            }
+        }
+
+        self.knots.insert(span + 1, u);
+        self.set_homogeneous(new);
+    }
+    /// Returns the same curve with its degree raised by one.  The spline space with one more of each knot contains the
+    /// curve, so its control points are found by interpolating the curve at the Greville abscissas of the new knots.
+    pub fn elevate_degree(&self) -> Option<NurbsCurve> {
+        let mut curve = self.clone();
+        curve.clamp();
+
+        let degree = curve.degree + 1;
+        let mut knots: Vec<f64> = vec![];
+        for knot in &curve.knots {
+            if knots.last() != Some(knot) {
+                knots.push(*knot);
+            }
+            knots.push(*knot);
+        }
+
+        let count = knots.len() - degree - 1;
+        let mut elevated = NurbsCurve {
+            degree,
+            knots,
+            control_points: vec![Point::origin(); count],
+            weights: vec![1.0; count],
+        };
+        let mut matrix = vec![vec![0.0; count]; count];
+        let mut values = vec![];
+        for (row, coefficients) in matrix.iter_mut().enumerate() {
+            let u = elevated.knots[row + 1..=row + degree].iter().sum::<f64>() / degree as f64;
+            let span = elevated.span(u);
+            for (j, b) in elevated.basis_derivatives(span, u, 0)[0].iter().enumerate() {
+                coefficients[span - degree + j] = *b;
+            }
+
+            let p = curve.point_at(u);
+            let w = curve.weight_at(u);
+            values.push(vec![p.x * w, p.y * w, p.z * w, w]);
+        }
+
+        let solution = solve(matrix, values)?;
+        elevated.set_homogeneous(
+            solution
+                .iter()
+                .map(|row| [row[0], row[1], row[2], row[3]])
+                .collect(),
+        );
+        Some(elevated)
+    }
+}
+
+// private implementation
+impl NurbsCurve {
+    fn homogeneous(&self, index: usize) -> Homogeneous {
+        let p = &self.control_points[index];
+        let w = self.weights[index];
+        [p.x * w, p.y * w, p.z * w, w]
+    }
+    fn set_homogeneous(&mut self, points: Vec<Homogeneous>) {
+        self.control_points = points
+            .iter()
+            .map(|[x, y, z, w]| Point::new(x / w, y / w, z / w))
+            .collect();
+        self.weights = points.iter().map(|p| p[3]).collect();
+    }
+    /// Returns the weight function of the curve, i.e., the denominator of the rational curve, at `u`.
+    fn weight_at(&self, u: f64) -> f64 {
+        let span = self.span(u);
+        self.basis_derivatives(span, u, 0)[0]
+            .iter()
+            .enumerate()
+            .map(|(j, b)| b * self.weights[span - self.degree + j])
+            .sum()
+    }
+    /// Returns the index of the knot span containing `u`, clamped to the domain.
+    fn span(&self, u: f64) -> usize {
+        let last = self.control_points.len() - 1;
+        let mut span = self.degree;
+        while span < last && self.knots[span + 1] <= u {
+            span += 1;
+        }
+
+        span
+    }
+    /// Returns the values of the `degree + 1` basis functions that are non-zero in `span` and their derivatives up to
+    /// `order`, indexed by derivative and then function.  This is algorithm A2.3 from The NURBS Book.
+    fn basis_derivatives(&self, span: usize, u: f64, order: usize) -> Vec<Vec<f64>> {
+        let p = self.degree;
+        let knots = &self.knots;
+
+        // the basis functions and knot differences of every degree up to `p`
+        let mut ndu = vec![vec![0.0; p + 1]; p + 1];
+        let mut left = vec![0.0; p + 1];
+        let mut right = vec![0.0; p + 1];
+        ndu[0][0] = 1.0;
+        for j in 1..=p {
+            left[j] = u - knots[span + 1 - j];
+            right[j] = knots[span + j] - u;
+            let mut saved = 0.0;
+            for r in 0..j {
+                ndu[j][r] = right[r + 1] + left[j - r];
+                let temp = ndu[r][j - 1] / ndu[j][r];
+                ndu[r][j] = saved + right[r + 1] * temp;
+                saved = left[j - r] * temp;
+            }
+            ndu[j][j] = saved;
+        }
+
+        let mut derivatives = vec![vec![0.0; p + 1]; order + 1];
+        for j in 0..=p {
+            derivatives[0][j] = ndu[j][p];
+        }
+
+        let p = p as isize;
+        for r in 0..=p {
+            let mut a = [vec![0.0; p as usize + 1], vec![0.0; p as usize + 1]];
+            let (mut s1, mut s2) = (0, 1);
+            a[0][0] = 1.0;
+            for k in 1..=(order as isize).min(p) {
+                let mut d = 0.0;
+                let rk = r - k;
+                let pk = (p - k) as usize;
+                if r >= k {
+                    a[s2][0] = a[s1][0] / ndu[pk + 1][rk as usize];
+                    d = a[s2][0] * ndu[rk as usize][pk];
+                }
+                let j1 = if rk >= -1 { 1 } else { -rk };
+                let j2 = if r - 1 <= pk as isize { k - 1 } else { p - r };
+                for j in j1..=j2 {
+                    let (j, index) = (j as usize, (rk + j) as usize);
+                    a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][index];
+                    d += a[s2][j] * ndu[index][pk];
+                }
+                if r <= pk as isize {
+                    let (k, r) = (k as usize, r as usize);
+                    a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                    d += a[s2][k] * ndu[r][pk];
+                }
+                derivatives[k as usize][r as usize] = d;
+                std::mem::swap(&mut s1, &mut s2);
+            }
+        }
+
+        let mut factor = p as f64;
+        for (k, row) in derivatives.iter_mut().enumerate().skip(1) {
+            for value in row.iter_mut() {
+                *value *= factor;
+            }
+            factor *= (p - k as isize) as f64;
+        }
+
+        derivatives
+    }
+    /// Makes the curve start and end at its first and last control points by raising the multiplicity of the knots at
+    /// the ends of the domain to `degree + 1` and dropping the control points and knots outside of it.
+    fn clamp(&mut self) {
+        let p = self.degree;
+        let (start, end) = self.domain();
+        while self.multiplicity(start) < p {
+            self.insert_knot(start);
+        }
+
+        // only the last `degree` control points before the start knots affect the curve
+        let first = self.knots.iter().position(|k| *k == start).unwrap_or(0);
+        let multiplicity = self.multiplicity(start).min(p + 1);
+        let dropped = first + multiplicity - 1 - p;
+        self.knots.drain(..dropped);
+        self.control_points.drain(..dropped);
+        self.weights.drain(..dropped);
+        for knot in self.knots.iter_mut().take(p + 1) {
+            *knot = start;
+        }
+
+        while self.multiplicity(end) < p {
+            self.insert_knot(end);
+        }
+
+        // only the control points up to the one before the end knots affect the curve
+        let first = self.knots.iter().position(|k| *k == end).unwrap_or(0);
+        self.knots.truncate(first + p + 1);
+        self.control_points.truncate(first);
+        self.weights.truncate(first);
+        for knot in self.knots.iter_mut().skip(first) {
+            *knot = end;
+        }
+    }
+    fn clamped_knots(count: usize, degree: usize) -> Vec<f64> {
+        let inner = count - degree;
+        let mut knots = vec![0.0; degree + 1];
+        knots.extend((1..inner).map(|i| i as f64 / inner as f64));
+        knots.extend(vec![1.0; degree + 1]);
+        knots
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+fn invalid_spline<T>(message: String) -> DxfResult<T> {
+    Err(DxfError::InvalidSpline(message))
+}
+
+/// Returns the spline tangent stored as a point, or `None` if it isn't set.
+fn tangent(p: &Point) -> Option<Vector> {
+    if p.x == 0.0 && p.y == 0.0 && p.z == 0.0 {
+        None
+    } else {
+        Some(point_vector(p))
+    }
+}
+
+fn point_vector(p: &Point) -> Vector {
+    Vector::new(p.x, p.y, p.z)
+}
+
+fn distance(a: &Point, b: &Point) -> f64 {
+    Vector::new(b.x - a.x, b.y - a.y, b.z - a.z).length()
+}
+
+fn lerp(a: &Homogeneous, b: &Homogeneous, t: f64) -> Homogeneous {
+    let mut result = [0.0; 4];
+    for (r, (a, b)) in result.iter_mut().zip(a.iter().zip(b)) {
+        *r = (1.0 - t) * a + t * b;
+    }
+    result
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+/// Solves `matrix * x = values` for each column of `values` with Gaussian elimination and partial pivoting.  Returns
+/// `None` if the matrix is singular.
+fn solve(mut matrix: Vec<Vec<f64>>, mut values: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|a, b| {
+            matrix[*a][column]
+                .abs()
+                .total_cmp(&matrix[*b][column].abs())
+        })?;
+        if matrix[pivot][column].abs() < SINGULAR_TOLERANCE {
+            return None;
+        }
+
+        matrix.swap(column, pivot);
+        values.swap(column, pivot);
+        for row in column + 1..n {
+            let factor = matrix[row][column] / matrix[column][column];
+            if factor == 0.0 {
+                continue;
+            }
+            let pivot_row = matrix[column].clone();
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                *value -= factor * pivot_value;
+            }
+            for c in 0..values[row].len() {
+                values[row][c] -= factor * values[column][c];
+            }
+        }
+    }
+
+    for row in (0..n).rev() {
+        for c in 0..values[row].len() {
+            let sum = (row + 1..n)
+                .map(|k| matrix[row][k] * values[k][c])
+                .sum::<f64>();
+            values[row][c] = (values[row][c] - sum) / matrix[row][row];
+        }
+    }
+
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::nurbs::NurbsCurve;
+    use crate::*;
+
+    fn assert_close(expected: (f64, f64, f64), actual: (f64, f64, f64)) {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(
+            close(expected.0, actual.0)
+                && close(expected.1, actual.1)
+                && close(expected.2, actual.2),
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn xyz(p: &Point) -> (f64, f64, f64) {
+        (p.x, p.y, p.z)
+    }
+
+    fn distance(a: &Point, b: &Point) -> f64 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+    }
+
+    fn quarter_circle() -> Spline {
+        Spline {
+            degree_of_curve: 2,
+            knot_values: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            weight_values: vec![1.0, 0.5f64.sqrt(), 1.0],
+            control_points: vec![
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            flags: 4,
+            ..Default::default()
+        }
+    }
+
+    fn cubic() -> Spline {
+        Spline {
+            degree_of_curve: 3,
+            knot_values: vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0],
+            control_points: vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 2.0, 0.0),
+                Point::new(3.0, 2.0, 1.0),
+                Point::new(4.0, 0.0, 0.0),
+                Point::new(5.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn quadratic_bezier() {
+        let curve = NurbsCurve::clamped(
+            2,
+            vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 2.0, 0.0),
+                Point::new(2.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        assert_eq!((0.0, 1.0), curve.domain());
+        assert_eq!(Point::new(0.0, 0.0, 0.0), curve.point_at(0.0));
+        assert_eq!(Point::new(1.0, 1.0, 0.0), curve.point_at(0.5));
+        assert_eq!(Point::new(2.0, 0.0, 0.0), curve.point_at(1.0));
+    }
+
+    #[test]
+    fn rational_quarter_circle() {
+        let curve = NurbsCurve::from_spline(&quarter_circle()).unwrap();
+        for i in 0..=10 {
+            let p = curve.point_at(f64::from(i) / 10.0);
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn periodic_spline_without_knots_is_closed() {
+        let mut spline = Spline {
+            degree_of_curve: 3,
+            control_points: vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        spline.set_is_periodic(true);
+        let curve = NurbsCurve::from_spline(&spline).unwrap();
+        let (start, end) = curve.domain();
+        let (p1, p2) = (curve.point_at(start), curve.point_at(end));
+        assert!((p1.x - p2.x).abs() < 1e-12 && (p1.y - p2.y).abs() < 1e-12);
+    }
+
+    #[test]
+    fn validate_spline() {
+        assert!(cubic().validate().is_ok());
+        assert!(quarter_circle().validate().is_ok());
+
+        let mut spline = cubic();
+        spline.knot_values.pop();
+        match spline.validate() {
+            Err(DxfError::InvalidSpline(message)) => assert_eq!(
+                "5 control points of degree 3 need 9 knots, but there are 8",
+                message
+            ),
+            _ => panic!("expected an invalid spline"),
+        }
+
+        let mut spline = cubic();
+        spline.knot_values.swap(4, 5);
+        assert!(spline.validate().is_err());
+
+        let mut spline = cubic();
+        spline.degree_of_curve = 5;
+        assert!(spline.validate().is_err());
+
+        let mut spline = quarter_circle();
+        spline.weight_values[1] = 0.0;
+        assert!(spline.validate().is_err());
+
+        // fit points alone are enough
+        let spline = Spline {
+            fit_points: vec![Point::origin(), Point::new(1.0, 0.0, 0.0)],
+            ..Default::default()
+        };
+        assert!(spline.validate().is_ok());
+    }
+
+    #[test]
+    fn rational_derivatives() {
+        // the quarter circle's tangent is perpendicular to the radius and its second derivative points inward
+        let spline = quarter_circle();
+        for u in [0.0, 0.3, 0.5, 1.0] {
+            let p = spline.derivative_at(u, 0).unwrap();
+            let d1 = spline.derivative_at(u, 1).unwrap();
+            let d2 = spline.derivative_at(u, 2).unwrap();
+            assert!((p.x * d1.x + p.y * d1.y).abs() < 1e-9);
+            assert!(p.x * d2.x + p.y * d2.y < 0.0);
+        }
+
+        // the tangent at the start points toward the second control point, scaled by the degree and weight
+        let d1 = spline.derivative_at(0.0, 1).unwrap();
+        assert_close((0.0, 2.0 * 0.5f64.sqrt(), 0.0), (d1.x, d1.y, d1.z));
+    }
+
+    #[test]
+    fn polynomial_derivatives_match_differences() {
+        let spline = cubic();
+        let h = 1e-6;
+        for u in [0.1, 0.5, 0.8] {
+            let before = spline.point_at(u - h).unwrap();
+            let after = spline.point_at(u + h).unwrap();
+            let d1 = spline.derivative_at(u, 1).unwrap();
+            assert!((d1.x - (after.x - before.x) / (2.0 * h)).abs() < 1e-5);
+            assert!((d1.y - (after.y - before.y) / (2.0 * h)).abs() < 1e-5);
+            assert!((d1.z - (after.z - before.z) / (2.0 * h)).abs() < 1e-5);
+        }
+
+        // a cubic has no fourth derivative
+        let d4 = spline.derivative_at(0.3, 4).unwrap();
+        assert_close((0.0, 0.0, 0.0), (d4.x, d4.y, d4.z));
+    }
+
+    #[test]
+    fn quarter_circle_length() {
+        let length = quarter_circle().length().unwrap();
+        assert!((length - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn insert_knot_keeps_shape() {
+        let original = cubic();
+        let mut spline = cubic();
+        spline.insert_knot(0.25).unwrap();
+        spline.insert_knot(0.5).unwrap();
+        assert_eq!(7, spline.control_points.len());
+        assert_eq!(11, spline.knot_values.len());
+        assert!(spline.validate().is_ok());
+        for i in 0..=20 {
+            let u = f64::from(i) / 20.0;
+            assert_close(
+                xyz(&original.point_at(u).unwrap()),
+                xyz(&spline.point_at(u).unwrap()),
+            );
+        }
+
+        // the knot at 0.5 now appears twice, and a third time is the most for a cubic
+        spline.insert_knot(0.5).unwrap();
+        assert!(spline.insert_knot(0.5).is_err());
+        assert!(spline.insert_knot(1.5).is_err());
+    }
+
+    #[test]
+    fn insert_knot_in_rational_spline() {
+        let mut spline = quarter_circle();
+        spline.insert_knot(0.5).unwrap();
+        assert!(spline.is_rational());
+        for i in 0..=10 {
+            let p = spline.point_at(f64::from(i) / 10.0).unwrap();
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn elevate_degree_keeps_shape() {
+        let original = cubic();
+        let mut spline = cubic();
+        spline.elevate_degree().unwrap();
+        assert_eq!(4, spline.degree_of_curve);
+        assert!(spline.validate().is_ok());
+        assert!(!spline.is_rational());
+        for i in 0..=20 {
+            let u = f64::from(i) / 20.0;
+            assert_close(
+                xyz(&original.point_at(u).unwrap()),
+                xyz(&spline.point_at(u).unwrap()),
+            );
+        }
+
+        let mut spline = quarter_circle();
+        spline.elevate_degree().unwrap();
+        assert_eq!(3, spline.degree_of_curve);
+        for i in 0..=10 {
+            let p = spline.point_at(f64::from(i) / 10.0).unwrap();
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn elevate_degree_of_unclamped_spline() {
+        let original = Spline {
+            degree_of_curve: 2,
+            knot_values: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            control_points: vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(2.0, 0.0, 0.0),
+                Point::new(3.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        let mut spline = original.clone();
+        spline.elevate_degree().unwrap();
+        assert_eq!(Some((2.0, 4.0)), spline.parameter_range());
+        for i in 0..=10 {
+            let u = 2.0 + f64::from(i) / 5.0;
+            assert_close(
+                xyz(&original.point_at(u).unwrap()),
+                xyz(&spline.point_at(u).unwrap()),
+            );
+        }
+    }
+
+    #[test]
+    fn interpolate_fit_points() {
+        let fit_points = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(3.0, 0.0, 0.0),
+            Point::new(4.0, 2.0, 1.0),
+            Point::new(6.0, 1.0, 0.0),
+        ];
+        let spline = Spline::through_points(3, fit_points.clone(), None, None).unwrap();
+        assert_eq!(3, spline.degree_of_curve);
+        assert_eq!(5, spline.control_points.len());
+        assert!(spline.validate().is_ok());
+
+        // every fit point is on the curve, in order
+        let mut previous = -1.0;
+        for fit_point in &fit_points {
+            let (u, closest) = (0..=1000)
+                .map(|i| f64::from(i) / 1000.0)
+                .map(|u| (u, distance(&spline.point_at(u).unwrap(), fit_point)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            assert!(closest < 1e-2);
+            assert!(u > previous);
+            previous = u;
+        }
+        assert_close(xyz(&fit_points[0]), xyz(&spline.point_at(0.0).unwrap()));
+        assert_close(xyz(&fit_points[4]), xyz(&spline.point_at(1.0).unwrap()));
+    }
+
+    #[test]
+    fn interpolate_with_tangents() {
+        let spline = Spline::through_points(
+            3,
+            vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(2.0, 1.0, 0.0),
+                Point::new(4.0, 0.0, 0.0),
+            ],
+            Some(Vector::new(0.0, 1.0, 0.0)),
+            Some(Vector::new(0.0, -2.0, 0.0)),
+        )
+        .unwrap();
+        assert_eq!(5, spline.control_points.len());
+        assert_eq!(Point::new(0.0, 1.0, 0.0), spline.start_tangent);
+
+        let start = spline.derivative_at(0.0, 1).unwrap();
+        let end = spline.derivative_at(1.0, 1).unwrap();
+        assert!(start.x.abs() < 1e-9 && start.y > 0.0);
+        assert!(end.x.abs() < 1e-9 && end.y < 0.0);
+    }
+
+    #[test]
+    fn interpolate_lowers_degree() {
+        let spline = Spline::through_points(
+            3,
+            vec![Point::new(0.0, 0.0, 0.0), Point::new(2.0, 2.0, 0.0)],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(1, spline.degree_of_curve);
+        assert_close((1.0, 1.0, 0.0), xyz(&spline.point_at(0.5).unwrap()));
+    }
+
+    #[test]
+    fn interpolate_invalid_fit_points() {
+        assert!(Spline::through_points(3, vec![Point::origin()], None, None).is_err());
+        assert!(Spline::through_points(
+            3,
+            vec![Point::origin(), Point::origin(), Point::new(1.0, 0.0, 0.0)],
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn evaluate_spline_with_only_fit_points() {
+        let spline = Spline {
+            degree_of_curve: 2,
+            fit_points: vec![
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(2.0, 0.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(Some((0.0, 1.0)), spline.parameter_range());
+        assert_close((2.0, 0.0, 0.0), xyz(&spline.point_at(1.0).unwrap()));
+    }
+}
@@ -56,6 +56,28 @@ impl MLineStyleElement {
     }
 }
 
+//------------------------------------------------------------------------------
+//                                                                 UnknownObject
+//------------------------------------------------------------------------------
+/// Represents an object type that isn't natively supported.  Everything other than the common object
+/// data is kept verbatim in `code_pairs`, starting with the first unrecognized code or the first
+/// subclass marker, so the object can be written back unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownObject {
+    pub type_string: String,
+    pub code_pairs: Vec<CodePair>,
+}
+
+impl UnknownObject {
+    pub fn new(type_string: &str, code_pairs: Vec<CodePair>) -> Self {
+        UnknownObject {
+            type_string: String::from(type_string),
+            code_pairs,
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 //                                                                     DataTable
 //------------------------------------------------------------------------------
@@ -94,65 +116,45 @@ impl Object {
         // no object-specific values to set
     }
     pub(crate) fn read(iter: &mut CodePairPutBack) -> DxfResult<Option<Object>> {
-        loop {
-            match iter.next() {
-                // first code pair must be 0/object-type
-                Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                    let type_string = pair.assert_string()?;
-                    if type_string == "ENDSEC" || type_string == "ENDBLK" {
-                        iter.put_back(Ok(pair));
-                        return Ok(None);
-                    }
-
-                    match ObjectType::from_type_string(&type_string) {
-                        Some(e) => {
-                            let mut obj = Object::new(e);
-                            if !obj.apply_custom_reader(iter)? {
-                                // no custom reader, use the auto-generated one
-                                loop {
-                                    match iter.next() {
-                                        Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                            // new object or ENDSEC
-                                            iter.put_back(Ok(pair));
-                                            break;
-                                        }
-                                        Some(Ok(pair)) => obj.apply_code_pair(&pair, iter)?,
-                                        Some(Err(e)) => return Err(e),
-                                        None => return Err(DxfError::UnexpectedEndOfInput),
-                                    }
-                                }
-
-                                obj.post_parse()?;
-                            }
+        match iter.next() {
+            // first code pair must be 0/object-type
+            Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                let type_string = pair.assert_string()?;
+                if type_string == "ENDSEC" || type_string == "ENDBLK" {
+                    iter.put_back(Ok(pair));
+                    return Ok(None);
+                }
 
-                            return Ok(Some(obj));
-                        }
-                        None => {
-                            // swallow unsupported object
-                            loop {
-                                match iter.next() {
-                                    Some(Ok(pair @ CodePair { code: 0, .. })) => {
-                                        // found another object or ENDSEC
-                                        iter.put_back(Ok(pair));
-                                        break;
-                                    }
-                                    Some(Ok(_)) => (), // part of the unsupported object
-                                    Some(Err(e)) => return Err(e),
-                                    None => return Err(DxfError::UnexpectedEndOfInput),
-                                }
+                let specific = ObjectType::from_type_string(&type_string).unwrap_or_else(|| {
+                    ObjectType::Unknown(UnknownObject::new(&type_string, vec![]))
+                });
+                let mut obj = Object::new(specific);
+                if !obj.apply_custom_reader(iter)? {
+                    // no custom reader, use the auto-generated one
+                    loop {
+                        match iter.next() {
+                            Some(Ok(pair @ CodePair { code: 0, .. })) => {
+                                // new object or ENDSEC
+                                iter.put_back(Ok(pair));
+                                break;
                             }
+                            Some(Ok(pair)) => obj.apply_code_pair(&pair, iter)?,
+                            Some(Err(e)) => return Err(e),
+                            None => return Err(DxfError::UnexpectedEndOfInput),
                         }
                     }
+
+                    obj.post_parse()?;
                 }
-                Some(Ok(pair)) => {
-                    return Err(DxfError::UnexpectedCodePair(
-                        pair,
-                        String::from("expected 0/object-type or 0/ENDSEC"),
-                    ))
-                }
-                Some(Err(e)) => return Err(e),
-                None => return Err(DxfError::UnexpectedEndOfInput),
+
+                Ok(Some(obj))
             }
+            Some(Ok(pair)) => Err(DxfError::UnexpectedCodePair(
+                pair,
+                String::from("expected 0/object-type or 0/ENDSEC"),
+            )),
+            Some(Err(e)) => Err(e),
+            None => Err(DxfError::UnexpectedEndOfInput),
         }
     }
     fn apply_code_pair(&mut self, pair: &CodePair, iter: &mut CodePairPutBack) -> DxfResult<()> {
@@ -310,6 +312,9 @@ impl Object {
             ObjectType::XRecordObject(ref mut xr) => {
                 Object::apply_custom_reader_xrecordobject(&mut self.common, xr, iter)
             }
+            ObjectType::Unknown(ref mut unknown) => {
+                Object::apply_custom_reader_unknown(&mut self.common, unknown, iter)
+            }
             _ => Ok(false), // no custom reader
         }
     }
@@ -414,16 +419,12 @@ impl Object {
                     );
                     current_3d_point = Point::origin();
                 }
-                330 | 331 | 340 | 350 | 360 => {
-                    if read_row_count || read_column_count {
-                        data.set_value(
-                            current_row,
-                            current_column,
-                            DataTableValue::Handle(pair.as_handle()?),
-                        );
-                    } else {
-                        common.apply_individual_pair(&pair, iter)?;
-                    }
+                330 | 331 | 340 | 350 | 360 if (read_row_count || read_column_count) => {
+                    data.set_value(
+                        current_row,
+                        current_column,
+                        DataTableValue::Handle(pair.as_handle()?),
+                    );
                 }
 
                 _ => {
@@ -613,6 +614,9 @@ impl Object {
                     330 => {
                         layout.__viewport_handle = pair.as_handle()?;
                     }
+                    331 => {
+                        layout.__last_active_viewport_handle = pair.as_handle()?;
+                    }
                     345 => {
                         layout.__table_record_handle = pair.as_handle()?;
                     }
@@ -757,7 +761,8 @@ impl Object {
                         .push(pair.assert_f64()?);
                 }
                 62 => {
-                    mat.gen_proc_color_index_value = Color::from_raw_value(pair.assert_i16()?);
+                    mat.gen_proc_color_index_value
+                        .set_raw_value(pair.assert_i16()?);
                 }
                 70 => {
                     mat.override_ambient_color = as_bool(pair.assert_i16()?);
@@ -1035,10 +1040,12 @@ impl Object {
                     mat.gen_proc_text_value = pair.assert_string()?;
                 }
                 420 => {
-                    mat.gen_proc_color_rgb_value = pair.assert_i32()?;
+                    mat.gen_proc_color_index_value
+                        .set_true_color_raw_value(pair.assert_i32()?);
                 }
                 430 => {
-                    mat.gen_proc_color_name = pair.assert_string()?;
+                    mat.gen_proc_color_index_value
+                        .set_color_name_raw_value(pair.assert_string()?);
                 }
                 460 => {
                     mat.color_bleed_scale = pair.assert_f64()?;
@@ -1472,6 +1479,23 @@ impl Object {
             }
         }
     }
+    fn apply_custom_reader_unknown(
+        common: &mut ObjectCommon,
+        unknown: &mut UnknownObject,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        loop {
+            let pair = next_pair!(iter);
+            if unknown.code_pairs.is_empty()
+                && !is_specific_subclass_marker(&pair)
+                && common.apply_individual_pair(&pair, iter)?
+            {
+                continue;
+            }
+
+            unknown.code_pairs.push(pair);
+        }
+    }
     pub(crate) fn add_code_pairs(&self, pairs: &mut Vec<CodePair>, version: AcadVersion) {
         if self.specific.is_supported_on_version(version) {
             pairs.push(CodePair::new_str(0, self.specific.to_type_string()));
@@ -1649,6 +1673,11 @@ impl Object {
                     pairs.push(pair.clone());
                 }
             }
+            ObjectType::Unknown(ref unknown) => {
+                for pair in &unknown.code_pairs {
+                    pairs.push(pair.clone());
+                }
+            }
             _ => return false, // no custom writer
         }
 
@@ -1701,7 +1730,18 @@ mod tests {
             CodePair::new_str(0, "ENDSEC"),
             CodePair::new_str(0, "EOF"),
         ]);
-        assert_eq!(0, drawing.objects().count());
+        let objects = drawing.objects().collect::<Vec<_>>();
+        assert_eq!(1, objects.len());
+        match objects[0].specific {
+            ObjectType::Unknown(ref unknown) => {
+                assert_eq!("UNSUPPORTED_OBJECT", unknown.type_string);
+                assert_eq!(
+                    vec![CodePair::new_str(1, "unsupported string")],
+                    unknown.code_pairs
+                );
+            }
+            _ => panic!("expected an unknown object"),
+        }
     }
 
     #[test]
@@ -1717,17 +1757,50 @@ mod tests {
             CodePair::new_str(0, "EOF"),
         ]);
         let objects = drawing.objects().collect::<Vec<_>>();
-        assert_eq!(2, objects.len());
+        assert_eq!(3, objects.len());
         match objects[0].specific {
             ObjectType::DictionaryVariable(_) => (),
             _ => panic!("expected a dictionary variable"),
         }
         match objects[1].specific {
+            ObjectType::Unknown(_) => (),
+            _ => panic!("expected an unknown object"),
+        }
+        match objects[2].specific {
             ObjectType::ImageDefinition(_) => (),
             _ => panic!("expected an image definition"),
         }
     }
 
+    #[test]
+    fn round_trip_unsupported_object() {
+        let mut drawing = drawing_from_pairs(vec![
+            CodePair::new_str(0, "SECTION"),
+            CodePair::new_str(2, "OBJECTS"),
+            CodePair::new_str(0, "ACME_OBJECT"),
+            CodePair::new_str(5, "ABC"),
+            CodePair::new_str(100, "AcmeDbObject"),
+            CodePair::new_str(330, "DEF"),
+            CodePair::new_i16(70, 7),
+            CodePair::new_str(0, "ENDSEC"),
+            CodePair::new_str(0, "EOF"),
+        ]);
+        let objects = drawing.objects().collect::<Vec<_>>();
+        assert_eq!(1, objects.len());
+        assert_eq!(Handle(0xABC), objects[0].common.handle);
+        drawing.header.version = AcadVersion::R2000;
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(0, "ACME_OBJECT"),
+                CodePair::new_str(5, "ABC"),
+                CodePair::new_str(100, "AcmeDbObject"),
+                CodePair::new_str(330, "DEF"),
+                CodePair::new_i16(70, 7),
+            ],
+        );
+    }
+
     #[test]
     fn read_common_object_fields() {
         let obj = read_object("IMAGEDEF", vec![CodePair::new_str(5, "DEADBEEF")]);
@@ -0,0 +1,274 @@
+use crate::entities::*;
+use crate::{Point, TransformationMatrix, Vector};
+
+/// Represents the object coordinate system (OCS) that planar entities store their coordinates in.  The system's Z axis
+/// is the entity's normal (or extrusion direction) and its X and Y axes are derived from the normal with the arbitrary
+/// axis algorithm.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectCoordinateSystem {
+    x_axis: Vector,
+    y_axis: Vector,
+    normal: Vector,
+}
+
+impl Default for ObjectCoordinateSystem {
+    fn default() -> Self {
+        ObjectCoordinateSystem::new(&Vector::z_axis())
+    }
+}
+
+// public implementation
+impl ObjectCoordinateSystem {
+    /// Creates the object coordinate system for the specified normal.  A zero normal is treated as the Z axis, which
+    /// gives a system identical to world coordinates.
+    pub fn new(normal: &Vector) -> Self {
+        let normal = if normal.length() == 0.0 {
+            Vector::z_axis()
+        } else {
+            normal.normalized()
+        };
+        let limit = 1.0 / 64.0;
+        let x_axis = if normal.x.abs() < limit && normal.y.abs() < limit {
+            Vector::y_axis().cross(&normal)
+        } else {
+            Vector::z_axis().cross(&normal)
+        }
+        .normalized();
+        let y_axis = normal.cross(&x_axis).normalized();
+        ObjectCoordinateSystem {
+            x_axis,
+            y_axis,
+            normal,
+        }
+    }
+    /// Returns the unit X axis of the system in world coordinates.
+    pub fn x_axis(&self) -> &Vector {
+        &self.x_axis
+    }
+    /// Returns the unit Y axis of the system in world coordinates.
+    pub fn y_axis(&self) -> &Vector {
+        &self.y_axis
+    }
+    /// Returns the unit normal, which is also the Z axis of the system.
+    pub fn normal(&self) -> &Vector {
+        &self.normal
+    }
+    /// Converts a point from this coordinate system to world coordinates.
+    pub fn to_wcs(&self, p: &Point) -> Point {
+        Point::from(self.vector_to_wcs(&Vector::new(p.x, p.y, p.z)))
+    }
+    /// Converts a point from world coordinates to this coordinate system.
+    pub fn from_wcs(&self, p: &Point) -> Point {
+        Point::from(self.vector_from_wcs(&Vector::new(p.x, p.y, p.z)))
+    }
+    /// Converts a direction or offset from this coordinate system to world coordinates.
+    pub fn vector_to_wcs(&self, v: &Vector) -> Vector {
+        self.x_axis.scaled(v.x) + self.y_axis.scaled(v.y) + self.normal.scaled(v.z)
+    }
+    /// Converts a direction or offset from world coordinates to this coordinate system.
+    pub fn vector_from_wcs(&self, v: &Vector) -> Vector {
+        Vector::new(
+            v.dot(&self.x_axis),
+            v.dot(&self.y_axis),
+            v.dot(&self.normal),
+        )
+    }
+    /// Returns the transformation that converts points from this coordinate system to world coordinates.  Since the
+    /// axes are orthonormal, its inverse is its transpose.
+    pub fn to_wcs_transformation(&self) -> TransformationMatrix {
+        TransformationMatrix {
+            m11: self.x_axis.x,
+            m12: self.y_axis.x,
+            m13: self.normal.x,
+            m21: self.x_axis.y,
+            m22: self.y_axis.y,
+            m23: self.normal.y,
+            m31: self.x_axis.z,
+            m32: self.y_axis.z,
+            m33: self.normal.z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                           Arc
+//------------------------------------------------------------------------------
+impl Arc {
+    /// Returns the center, start point, and end point of the arc in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        let ocs = ObjectCoordinateSystem::new(&self.normal);
+        let on_arc = |angle: f64| {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            Point::new(
+                self.center.x + self.radius * cos,
+                self.center.y + self.radius * sin,
+                self.center.z,
+            )
+        };
+        vec![
+            ocs.to_wcs(&self.center),
+            ocs.to_wcs(&on_arc(self.start_angle)),
+            ocs.to_wcs(&on_arc(self.end_angle)),
+        ]
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Circle
+//------------------------------------------------------------------------------
+impl Circle {
+    /// Returns the center of the circle in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        vec![ObjectCoordinateSystem::new(&self.normal).to_wcs(&self.center)]
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Insert
+//------------------------------------------------------------------------------
+impl Insert {
+    /// Returns the insertion point in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        vec![ObjectCoordinateSystem::new(&self.extrusion_direction).to_wcs(&self.location)]
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                    LwPolyline
+//------------------------------------------------------------------------------
+impl LwPolyline {
+    /// Returns the vertices in world coordinates.  The polyline's elevation is stored on the entity, so it must be
+    /// passed in, e.g., from `entity.common.elevation`.
+    pub fn wcs_points(&self, elevation: f64) -> Vec<Point> {
+        let ocs = ObjectCoordinateSystem::new(&self.extrusion_direction);
+        self.vertices
+            .iter()
+            .map(|v| ocs.to_wcs(&Point::new(v.x, v.y, elevation)))
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Solid
+//------------------------------------------------------------------------------
+impl Solid {
+    /// Returns the four corners in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        let ocs = ObjectCoordinateSystem::new(&self.extrusion_direction);
+        [
+            &self.first_corner,
+            &self.second_corner,
+            &self.third_corner,
+            &self.fourth_corner,
+        ]
+        .iter()
+        .map(|p| ocs.to_wcs(p))
+        .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                          Text
+//------------------------------------------------------------------------------
+impl Text {
+    /// Returns the location and the second alignment point in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        let ocs = ObjectCoordinateSystem::new(&self.normal);
+        vec![
+            ocs.to_wcs(&self.location),
+            ocs.to_wcs(&self.second_alignment_point),
+        ]
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Trace
+//------------------------------------------------------------------------------
+impl Trace {
+    /// Returns the four corners in world coordinates.
+    pub fn wcs_points(&self) -> Vec<Point> {
+        let ocs = ObjectCoordinateSystem::new(&self.extrusion_direction);
+        [
+            &self.first_corner,
+            &self.second_corner,
+            &self.third_corner,
+            &self.fourth_corner,
+        ]
+        .iter()
+        .map(|p| ocs.to_wcs(p))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_point(expected: (f64, f64, f64), actual: &Point) {
+        assert!(
+            (expected.0 - actual.x).abs() < 1e-9
+                && (expected.1 - actual.y).abs() < 1e-9
+                && (expected.2 - actual.z).abs() < 1e-9,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn world_normal_is_identity() {
+        let ocs = ObjectCoordinateSystem::default();
+        assert_eq!(&Vector::x_axis(), ocs.x_axis());
+        assert_eq!(&Vector::y_axis(), ocs.y_axis());
+        assert_point((1.0, 2.0, 3.0), &ocs.to_wcs(&Point::new(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn arbitrary_axis_algorithm() {
+        // a normal along +X is far from the Z axis, so the OCS X axis is Z cross N
+        let ocs = ObjectCoordinateSystem::new(&Vector::new(2.0, 0.0, 0.0));
+        assert_point((0.0, 1.0, 0.0), &Point::from(ocs.x_axis().clone()));
+        assert_point((0.0, 0.0, 1.0), &Point::from(ocs.y_axis().clone()));
+        assert_point((3.0, 1.0, 2.0), &ocs.to_wcs(&Point::new(1.0, 2.0, 3.0)));
+
+        // a normal along -Z flips the X axis
+        let ocs = ObjectCoordinateSystem::new(&Vector::new(0.0, 0.0, -1.0));
+        assert_point((-1.0, 2.0, -3.0), &ocs.to_wcs(&Point::new(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let ocs = ObjectCoordinateSystem::new(&Vector::new(1.0, 2.0, 3.0));
+        let p = Point::new(4.0, -5.0, 6.0);
+        assert_point((4.0, -5.0, 6.0), &ocs.from_wcs(&ocs.to_wcs(&p)));
+        assert_point(
+            ocs.to_wcs(&p).tuple(),
+            &ocs.to_wcs_transformation().transform_point(&p),
+        );
+    }
+
+    #[test]
+    fn entity_wcs_points() {
+        let mut arc = Arc::new(Point::new(1.0, 0.0, 0.0), 1.0, 0.0, 90.0);
+        arc.normal = Vector::new(0.0, 0.0, -1.0);
+        let points = arc.wcs_points();
+        assert_point((-1.0, 0.0, 0.0), &points[0]);
+        assert_point((-2.0, 0.0, 0.0), &points[1]);
+        assert_point((-1.0, 1.0, 0.0), &points[2]);
+
+        let poly = LwPolyline {
+            extrusion_direction: Vector::new(0.0, 0.0, -1.0),
+            vertices: vec![LwPolylineVertex {
+                x: 1.0,
+                y: 2.0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(vec![Point::new(-1.0, 2.0, -5.0)], poly.wcs_points(5.0));
+    }
+}
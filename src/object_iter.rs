@@ -1,17 +1,44 @@
 use crate::code_pair_put_back::CodePairPutBack;
 use crate::objects::Object;
+use crate::{DiagnosticSeverity, DxfError};
 
+/// Reads objects until the end of the section.  A read error ends the iteration and is held in `error`, unless the
+/// reader is lenient, in which case the broken object is skipped and a diagnostic is recorded.
 pub(crate) struct ObjectIter<'a> {
     pub iter: &'a mut CodePairPutBack,
+    pub error: Option<DxfError>,
 }
 
 impl<'a> Iterator for ObjectIter<'a> {
     type Item = Object;
 
     fn next(&mut self) -> Option<Object> {
-        match Object::read(self.iter) {
-            Ok(Some(o)) => Some(o),
-            Ok(None) | Err(_) => None,
+        if self.error.is_some() {
+            return None;
         }
+
+        loop {
+            match Object::read(self.iter) {
+                Ok(o) => return o,
+                Err(e) if self.iter.is_lenient() => {
+                    let message = format!("the object was dropped: {}", e);
+                    self.iter.add_diagnostic(DiagnosticSeverity::Error, message);
+                    if let Err(e) = self.iter.skip_to_next_item() {
+                        self.error = Some(e);
+                        return None;
+                    }
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl<'a> ObjectIter<'a> {
+    pub(crate) fn new(iter: &'a mut CodePairPutBack) -> Self {
+        ObjectIter { iter, error: None }
     }
 }
@@ -1,4 +1,6 @@
-use crate::{CodePair, DxfError, DxfResult};
+use std::ops::{Add, Sub};
+
+use crate::{CodePair, DxfError, DxfResult, Vector};
 
 /// Represents a simple point in Cartesian space.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -57,6 +59,10 @@ impl Point {
     pub fn origin() -> Point {
         Point::new(0.0, 0.0, 0.0)
     }
+    /// Returns the distance between the two points.
+    pub fn distance_to(&self, other: &Point) -> f64 {
+        (other.clone() - self.clone()).length()
+    }
     pub(crate) fn set(&mut self, pair: &CodePair) -> DxfResult<()> {
         match pair.code {
             10 => self.x = pair.assert_f64()?,
@@ -78,6 +84,39 @@ impl Point {
     }
 }
 
+impl From<Vector> for Point {
+    fn from(v: Vector) -> Self {
+        Point::new(v.x, v.y, v.z)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    /// Moves the point by the specified offset.
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    /// Moves the point by the opposite of the specified offset.
+    fn sub(self, rhs: Vector) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    /// Returns the offset from `rhs` to this point.
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,4 +138,13 @@ mod tests {
         assert_eq!(p.y, 2.0);
         assert_eq!(p.z, 3.5);
     }
+    #[test]
+    fn arithmetic() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let offset = Point::new(4.0, 6.0, 3.0) - p.clone();
+        assert_eq!(Vector::new(3.0, 4.0, 0.0), offset);
+        assert_eq!(Point::new(4.0, 6.0, 3.0), p.clone() + offset.clone());
+        assert_eq!(Point::new(-2.0, -2.0, 3.0), p.clone() - offset);
+        assert_eq!(5.0, p.distance_to(&Point::new(4.0, 6.0, 3.0)));
+    }
 }
@@ -0,0 +1,70 @@
+use crate::{CodePair, DxfResult};
+
+use crate::code_pair_put_back::CodePairPutBack;
+
+/// Represents a section that isn't otherwise understood, e.g., `ACDSDATA` or an application-defined section.  The
+/// code pairs are preserved verbatim so the section can be written back out.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawSection {
+    /// The name of the section, e.g., `ACDSDATA`.
+    pub name: String,
+    /// The code pairs contained between the section name and `0/ENDSEC`.
+    pub code_pairs: Vec<CodePair>,
+    /// The name of the standard section this section is written after, e.g., `OBJECTS`.  If empty, the section is
+    /// written before the `HEADER` section.  Sections following an unrecognized name are written last.
+    pub preceding_section: String,
+}
+
+// public implementation
+impl RawSection {
+    /// Creates a new `RawSection` that will be written after the `OBJECTS` section, which is where `ACDSDATA` is
+    /// normally found.
+    pub fn new(name: &str, code_pairs: Vec<CodePair>) -> Self {
+        RawSection {
+            name: String::from(name),
+            code_pairs,
+            preceding_section: String::from("OBJECTS"),
+        }
+    }
+}
+
+// internal visibility only
+impl RawSection {
+    pub(crate) fn read(
+        name: &str,
+        preceding_section: &str,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<RawSection> {
+        let mut section = RawSection {
+            name: String::from(name),
+            code_pairs: vec![],
+            preceding_section: String::from(preceding_section),
+        };
+        loop {
+            match iter.next() {
+                Some(Ok(pair)) => {
+                    if pair.code == 0 && pair.assert_string()? == "ENDSEC" {
+                        iter.put_back(Ok(pair));
+                        break;
+                    }
+
+                    section.code_pairs.push(pair);
+                }
+                Some(Err(e)) => return Err(e),
+                None => break,
+            }
+        }
+
+        Ok(section)
+    }
+    pub(crate) fn add_code_pairs(&self, pairs: &mut Vec<CodePair>) {
+        pairs.push(CodePair::new_str(0, "SECTION"));
+        pairs.push(CodePair::new_str(2, &self.name));
+        for pair in &self.code_pairs {
+            pairs.push(pair.clone());
+        }
+
+        pairs.push(CodePair::new_str(0, "ENDSEC"));
+    }
+}
@@ -0,0 +1,598 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{BoundingBox, Drawing, Handle, Point};
+
+/// The most entries a node holds before it's split.
+const MAX_ENTRIES: usize = 16;
+
+/// The fewest entries a non-root node holds; smaller nodes are dissolved and their entries reinserted.
+const MIN_ENTRIES: usize = 6;
+
+/// An R-tree of entity handles keyed by their bounding boxes, used to find the entities in a region without visiting
+/// every entity in the drawing.
+///
+/// Boxes are compared in the XY plane only; the Z extents are ignored.  Entities without measurable geometry (see
+/// `Entity::bounding_box()`) aren't indexed.  The returned handles can be resolved with `Drawing::item_by_handle()`.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex {
+    root: Node,
+    rects: HashMap<u64, Rect>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        SpatialIndex::new()
+    }
+}
+
+// public implementation
+impl SpatialIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        SpatialIndex {
+            root: Node::Leaf(vec![]),
+            rects: HashMap::new(),
+        }
+    }
+    /// Creates an index of all entities in the drawing, packed in a single pass.
+    pub fn from_drawing(drawing: &Drawing) -> Self {
+        let mut rects = HashMap::new();
+        for entity in drawing.entities() {
+            if let Some(bbox) = entity.bounding_box(drawing) {
+                rects.insert(entity.common.handle.0, Rect::from_bounding_box(&bbox));
+            }
+        }
+
+        let entries = rects
+            .iter()
+            .map(|(handle, rect)| (*rect, Handle(*handle)))
+            .collect();
+        SpatialIndex {
+            root: Node::pack(entries),
+            rects,
+        }
+    }
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+    /// Returns `true` if no entities are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+    /// Adds the entity with the specified handle and bounding box, replacing any box previously indexed for it.
+    pub fn insert(&mut self, handle: Handle, bbox: &BoundingBox) {
+        self.remove(handle);
+        let rect = Rect::from_bounding_box(bbox);
+        self.rects.insert(handle.0, rect);
+        self.insert_entry(rect, handle);
+    }
+    /// Removes the entity with the specified handle, returning `true` if it was indexed.
+    pub fn remove(&mut self, handle: Handle) -> bool {
+        let rect = match self.rects.remove(&handle.0) {
+            Some(rect) => rect,
+            None => return false,
+        };
+
+        let mut orphans = vec![];
+        self.root.remove(&rect, handle, &mut orphans);
+
+        // a root with a single child is replaced by that child
+        loop {
+            match self.root {
+                Node::Branch(ref mut children) if children.len() == 1 => {
+                    let (_, child) = children.pop().unwrap();
+                    self.root = child;
+                }
+                Node::Branch(ref children) if children.is_empty() => self.root = Node::Leaf(vec![]),
+                _ => break,
+            }
+        }
+
+        for (rect, handle) in orphans {
+            self.insert_entry(rect, handle);
+        }
+
+        true
+    }
+    /// Removes all entities from the index.
+    pub fn clear(&mut self) {
+        *self = SpatialIndex::new();
+    }
+    /// Returns the handles of the entities that lie entirely within the region, like a window selection.
+    pub fn window(&self, region: &BoundingBox) -> Vec<Handle> {
+        let region = Rect::from_bounding_box(region);
+        let mut handles = vec![];
+        self.root.search(&region, &mut |rect, handle| {
+            if region.contains(rect) {
+                handles.push(handle);
+            }
+        });
+        handles
+    }
+    /// Returns the handles of the entities whose bounding boxes touch the region, like a crossing selection.
+    pub fn crossing(&self, region: &BoundingBox) -> Vec<Handle> {
+        let region = Rect::from_bounding_box(region);
+        let mut handles = vec![];
+        self.root
+            .search(&region, &mut |_, handle| handles.push(handle));
+        handles
+    }
+    /// Returns the handle of the entity whose bounding box is closest to the point, or `None` if the index is empty.
+    pub fn nearest(&self, p: &Point) -> Option<Handle> {
+        self.nearest_n(p, 1).pop()
+    }
+    /// Returns the handles of up to `count` entities ordered by the distance from their bounding boxes to the point.
+    /// Points inside a box have a distance of zero.
+    pub fn nearest_n(&self, p: &Point, count: usize) -> Vec<Handle> {
+        let mut handles = vec![];
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate {
+            distance: self.root.rect().map_or(0.0, |r| r.distance_to(p)),
+            item: CandidateItem::Node(&self.root),
+        });
+
+        // the queue is ordered so entries are only popped once nothing unvisited can be closer
+        while let Some(candidate) = queue.pop() {
+            if handles.len() >= count {
+                break;
+            }
+
+            match candidate.item {
+                CandidateItem::Entry(handle) => handles.push(handle),
+                CandidateItem::Node(Node::Leaf(entries)) => {
+                    for (rect, handle) in entries {
+                        queue.push(Candidate {
+                            distance: rect.distance_to(p),
+                            item: CandidateItem::Entry(*handle),
+                        });
+                    }
+                }
+                CandidateItem::Node(Node::Branch(children)) => {
+                    for (rect, child) in children {
+                        queue.push(Candidate {
+                            distance: rect.distance_to(p),
+                            item: CandidateItem::Node(child),
+                        });
+                    }
+                }
+            }
+        }
+
+        handles
+    }
+}
+
+// private implementation
+impl SpatialIndex {
+    fn insert_entry(&mut self, rect: Rect, handle: Handle) {
+        if let Some(sibling) = self.root.insert(rect, handle) {
+            // the root was split, so the tree grows a level
+            let old_root = std::mem::replace(&mut self.root, Node::Leaf(vec![]));
+            let old_rect = old_root.rect().unwrap();
+            self.root = Node::Branch(vec![(old_rect, old_root), sibling]);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                          Rect
+//------------------------------------------------------------------------------
+/// The XY extents of a bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+impl Rect {
+    fn from_bounding_box(bbox: &BoundingBox) -> Self {
+        Rect {
+            min_x: bbox.min.x.min(bbox.max.x),
+            min_y: bbox.min.y.min(bbox.max.y),
+            max_x: bbox.min.x.max(bbox.max.x),
+            max_y: bbox.min.y.max(bbox.max.y),
+        }
+    }
+    fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+    fn area(&self) -> f64 {
+        (self.max_x - self.min_x) * (self.max_y - self.min_y)
+    }
+    fn center(&self) -> (f64, f64) {
+        (
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+        )
+    }
+    fn contains(&self, other: &Rect) -> bool {
+        other.min_x >= self.min_x
+            && other.max_x <= self.max_x
+            && other.min_y >= self.min_y
+            && other.max_y <= self.max_y
+    }
+    fn intersects(&self, other: &Rect) -> bool {
+        self.min_x <= other.max_x
+            && self.max_x >= other.min_x
+            && self.min_y <= other.max_y
+            && self.max_y >= other.min_y
+    }
+    fn distance_to(&self, p: &Point) -> f64 {
+        let dx = (self.min_x - p.x).max(p.x - self.max_x).max(0.0);
+        let dy = (self.min_y - p.y).max(p.y - self.max_y).max(0.0);
+        (dx * dx + dy * dy).sqrt()
+    }
+    fn union_all<'a, I>(rects: I) -> Option<Rect>
+    where
+        I: IntoIterator<Item = &'a Rect>,
+    {
+        rects
+            .into_iter()
+            .fold(None, |acc: Option<Rect>, r| match acc {
+                Some(acc) => Some(acc.union(r)),
+                None => Some(*r),
+            })
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                          Node
+//------------------------------------------------------------------------------
+#[derive(Clone, Debug)]
+enum Node {
+    Leaf(Vec<(Rect, Handle)>),
+    Branch(Vec<(Rect, Node)>),
+}
+
+impl Node {
+    /// Builds a balanced tree bottom-up with the sort-tile-recursive algorithm.
+    fn pack(entries: Vec<(Rect, Handle)>) -> Node {
+        if entries.len() <= MAX_ENTRIES {
+            return Node::Leaf(entries);
+        }
+
+        let mut level = tile(entries, Node::Leaf);
+        while level.len() > MAX_ENTRIES {
+            level = tile(level, Node::Branch);
+        }
+
+        Node::Branch(level)
+    }
+    fn rect(&self) -> Option<Rect> {
+        match self {
+            Node::Leaf(entries) => Rect::union_all(entries.iter().map(|(r, _)| r)),
+            Node::Branch(children) => Rect::union_all(children.iter().map(|(r, _)| r)),
+        }
+    }
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(entries) => entries.len(),
+            Node::Branch(children) => children.len(),
+        }
+    }
+    /// Adds the entry below this node, returning the new sibling if the node had to be split.
+    fn insert(&mut self, rect: Rect, handle: Handle) -> Option<(Rect, Node)> {
+        match self {
+            Node::Leaf(entries) => {
+                entries.push((rect, handle));
+                if entries.len() > MAX_ENTRIES {
+                    let sibling = split(entries);
+                    return Some((
+                        Rect::union_all(sibling.iter().map(|(r, _)| r))?,
+                        Node::Leaf(sibling),
+                    ));
+                }
+            }
+            Node::Branch(children) => {
+                // descend into the child that grows the least
+                let index = children
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (r, _))| {
+                        let area = r.area();
+                        (i, r.union(&rect).area() - area, area)
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+                    .map(|(i, _, _)| i)?;
+                let (child_rect, child) = &mut children[index];
+                match child.insert(rect, handle) {
+                    Some(sibling) => {
+                        *child_rect = child.rect()?;
+                        children.push(sibling);
+                    }
+                    None => *child_rect = child_rect.union(&rect),
+                }
+
+                if children.len() > MAX_ENTRIES {
+                    let sibling = split(children);
+                    return Some((
+                        Rect::union_all(sibling.iter().map(|(r, _)| r))?,
+                        Node::Branch(sibling),
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+    /// Removes the entry below this node, returning `true` if it was found.  The entries of any nodes left underfull
+    /// are moved into `orphans` to be reinserted.
+    fn remove(&mut self, rect: &Rect, handle: Handle, orphans: &mut Vec<(Rect, Handle)>) -> bool {
+        match self {
+            Node::Leaf(entries) => match entries.iter().position(|(_, h)| *h == handle) {
+                Some(index) => {
+                    entries.remove(index);
+                    true
+                }
+                None => false,
+            },
+            Node::Branch(children) => {
+                for index in 0..children.len() {
+                    let (child_rect, child) = &mut children[index];
+                    if !child_rect.contains(rect) || !child.remove(rect, handle, orphans) {
+                        continue;
+                    }
+
+                    if child.len() < MIN_ENTRIES {
+                        let (_, child) = children.remove(index);
+                        child.collect_entries(orphans);
+                    } else if let Some(r) = child.rect() {
+                        *child_rect = r;
+                    }
+
+                    return true;
+                }
+
+                false
+            }
+        }
+    }
+    fn collect_entries(self, entries: &mut Vec<(Rect, Handle)>) {
+        match self {
+            Node::Leaf(mut leaf_entries) => entries.append(&mut leaf_entries),
+            Node::Branch(children) => {
+                for (_, child) in children {
+                    child.collect_entries(entries);
+                }
+            }
+        }
+    }
+    /// Reports each entry whose rectangle intersects the region.
+    fn search<F>(&self, region: &Rect, found: &mut F)
+    where
+        F: FnMut(&Rect, Handle),
+    {
+        match self {
+            Node::Leaf(entries) => {
+                for (rect, handle) in entries {
+                    if region.intersects(rect) {
+                        found(rect, *handle);
+                    }
+                }
+            }
+            Node::Branch(children) => {
+                for (rect, child) in children {
+                    if region.intersects(rect) {
+                        child.search(region, found);
+                    }
+                }
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+/// Groups the items into nodes of at most `MAX_ENTRIES` by slicing them into vertical strips by X, then tiling each
+/// strip by Y.
+fn tile<T, F>(mut items: Vec<(Rect, T)>, make_node: F) -> Vec<(Rect, Node)>
+where
+    F: Fn(Vec<(Rect, T)>) -> Node,
+{
+    let node_count = items.len().div_ceil(MAX_ENTRIES);
+    let strip_count = (node_count as f64).sqrt().ceil() as usize;
+    let strip_size = items.len().div_ceil(strip_count);
+    items.sort_by(|a, b| a.0.center().0.total_cmp(&b.0.center().0));
+
+    let mut nodes = vec![];
+    while !items.is_empty() {
+        let rest = items.split_off(strip_size.min(items.len()));
+        let mut strip = std::mem::replace(&mut items, rest);
+        strip.sort_by(|a, b| a.0.center().1.total_cmp(&b.0.center().1));
+        while !strip.is_empty() {
+            let rest = strip.split_off(MAX_ENTRIES.min(strip.len()));
+            let group = std::mem::replace(&mut strip, rest);
+            if let Some(rect) = Rect::union_all(group.iter().map(|(r, _)| r)) {
+                nodes.push((rect, make_node(group)));
+            }
+        }
+    }
+
+    nodes
+}
+
+/// Splits an overfull node by sorting its items along the axis where their centers are most spread out, leaving the
+/// lower half in place and returning the upper half.
+fn split<T>(items: &mut Vec<(Rect, T)>) -> Vec<(Rect, T)> {
+    let spread = |axis: fn(&Rect) -> f64| {
+        let (min, max) = items
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), (r, _)| {
+                let value = axis(r);
+                (min.min(value), max.max(value))
+            });
+        max - min
+    };
+    let x_axis: fn(&Rect) -> f64 = |r| r.center().0;
+    let y_axis: fn(&Rect) -> f64 = |r| r.center().1;
+    let axis = if spread(x_axis) >= spread(y_axis) {
+        x_axis
+    } else {
+        y_axis
+    };
+    items.sort_by(|a, b| axis(&a.0).total_cmp(&axis(&b.0)));
+    items.split_off(items.len() / 2)
+}
+
+/// A node or entry waiting to be visited by a nearest-neighbor search.
+struct Candidate<'a> {
+    distance: f64,
+    item: CandidateItem<'a>,
+}
+
+enum CandidateItem<'a> {
+    Node(&'a Node),
+    Entry(Handle),
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate<'_> {
+    /// Reversed so that the max-heap yields the closest candidate first, with entries ahead of nodes at the same
+    /// distance.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let is_entry = |c: &Candidate| matches!(c.item, CandidateItem::Entry(_));
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then(is_entry(self).cmp(&is_entry(other)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+
+    fn square(x: f64, y: f64, size: f64) -> BoundingBox {
+        BoundingBox::new(Point::new(x, y, 0.0), Point::new(x + size, y + size, 0.0))
+    }
+
+    /// A grid of unit squares with a gap of one between them, keyed by handles 1..=count*count.
+    fn grid(count: usize) -> SpatialIndex {
+        let mut index = SpatialIndex::new();
+        for i in 0..count {
+            for j in 0..count {
+                let handle = Handle((i * count + j + 1) as u64);
+                index.insert(handle, &square(i as f64 * 2.0, j as f64 * 2.0, 1.0));
+            }
+        }
+        index
+    }
+
+    fn sorted(mut handles: Vec<Handle>) -> Vec<u64> {
+        handles.sort_by_key(|h| h.0);
+        handles.into_iter().map(|h| h.0).collect()
+    }
+
+    #[test]
+    fn window_and_crossing() {
+        let index = grid(20);
+        assert_eq!(400, index.len());
+
+        // covers the squares at (0,0) and (2,0) completely and clips the ones at (0,2) and (2,2)
+        let region = BoundingBox::new(Point::new(-0.5, -0.5, 0.0), Point::new(3.5, 2.5, 0.0));
+        assert_eq!(vec![1, 21], sorted(index.window(&region)));
+        assert_eq!(vec![1, 2, 21, 22], sorted(index.crossing(&region)));
+
+        // Z extents are ignored
+        let region = BoundingBox::new(Point::new(-0.5, -0.5, 5.0), Point::new(1.5, 1.5, 5.0));
+        assert_eq!(vec![1], sorted(index.window(&region)));
+    }
+
+    #[test]
+    fn nearest() {
+        let index = grid(20);
+        assert_eq!(Some(Handle(1)), index.nearest(&Point::new(-5.0, -5.0, 0.0)));
+        assert_eq!(Some(Handle(22)), index.nearest(&Point::new(2.5, 2.5, 0.0)));
+        assert_eq!(
+            vec![22, 21, 2],
+            index
+                .nearest_n(&Point::new(2.5, 2.4, 0.0), 3)
+                .iter()
+                .map(|h| h.0)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, SpatialIndex::new().nearest(&Point::origin()));
+    }
+
+    #[test]
+    fn incremental_removal_matches_linear_scan() {
+        let mut index = grid(20);
+        for handle in (1..=400).filter(|h| h % 3 != 0) {
+            assert!(index.remove(Handle(handle)));
+        }
+        assert!(!index.remove(Handle(1)));
+
+        let everything = BoundingBox::new(Point::new(-1.0, -1.0, 0.0), Point::new(50.0, 50.0, 0.0));
+        let expected = (1..=400).filter(|h| h % 3 == 0).collect::<Vec<_>>();
+        assert_eq!(expected, sorted(index.crossing(&everything)));
+        assert_eq!(expected.len(), index.len());
+
+        // re-inserting moves the entry
+        index.insert(Handle(3), &square(100.0, 100.0, 1.0));
+        assert_eq!(
+            Some(Handle(3)),
+            index.nearest(&Point::new(100.0, 100.0, 0.0))
+        );
+        assert_eq!(expected.len(), index.len());
+    }
+
+    #[test]
+    fn drawing_index_follows_added_and_removed_entities() {
+        let mut drawing = Drawing::new();
+        for i in 0..50 {
+            let x = i as f64 * 10.0;
+            drawing.add_entity(Entity::new(EntityType::Line(Line::new(
+                Point::new(x, 0.0, 0.0),
+                Point::new(x + 1.0, 1.0, 0.0),
+            ))));
+        }
+        drawing.build_spatial_index();
+        assert_eq!(50, drawing.spatial_index().unwrap().len());
+
+        let added = drawing
+            .add_entity(Entity::new(EntityType::Circle(Circle::new(
+                Point::new(1000.0, 0.0, 0.0),
+                1.0,
+            ))))
+            .common
+            .handle;
+        let region = BoundingBox::new(Point::new(990.0, -5.0, 0.0), Point::new(1010.0, 5.0, 0.0));
+        let found = drawing.spatial_index().unwrap().window(&region);
+        assert_eq!(vec![added], found);
+        match drawing.item_by_handle(found[0]) {
+            Some(DrawingItem::Entity(e)) => assert!(matches!(e.specific, EntityType::Circle(_))),
+            _ => panic!("expected an entity"),
+        }
+
+        drawing.remove_entity(50);
+        assert!(drawing.spatial_index().unwrap().window(&region).is_empty());
+        assert_eq!(50, drawing.spatial_index().unwrap().len());
+
+        drawing.clear();
+        assert!(drawing.spatial_index().unwrap().is_empty());
+    }
+}
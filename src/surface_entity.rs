@@ -0,0 +1,536 @@
+// other implementation is in `generated/entities.rs`
+
+use enum_primitive::FromPrimitive;
+
+use crate::{CodePair, DxfResult, TransformationMatrix, Vector};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::entities::*;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+//------------------------------------------------------------------------------
+//                                                                  SweepOptions
+//------------------------------------------------------------------------------
+/// The options used to sweep a profile into an `ExtrudedSurface` or a `SweptSurface`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepOptions {
+    pub draft_angle: f64,
+    pub draft_start_distance: f64,
+    pub draft_end_distance: f64,
+    pub twist_angle: f64,
+    pub scale_factor: f64,
+    pub align_angle: f64,
+    pub sweep_entity_transform: TransformationMatrix,
+    pub path_entity_transform: TransformationMatrix,
+    pub is_solid: bool,
+    pub alignment: SweepAlignment,
+    pub is_start_aligned: bool,
+    pub is_banked: bool,
+    pub is_base_point_set: bool,
+    pub is_sweep_entity_transform_computed: bool,
+    pub is_path_entity_transform_computed: bool,
+    /// The reference vector used to control the twist.
+    pub reference_vector: Vector,
+}
+
+impl Default for SweepOptions {
+    fn default() -> Self {
+        SweepOptions {
+            draft_angle: 0.0,
+            draft_start_distance: 0.0,
+            draft_end_distance: 0.0,
+            twist_angle: 0.0,
+            scale_factor: 1.0,
+            align_angle: 0.0,
+            sweep_entity_transform: TransformationMatrix::identity(),
+            path_entity_transform: TransformationMatrix::identity(),
+            is_solid: false,
+            alignment: SweepAlignment::NoAlignment,
+            is_start_aligned: false,
+            is_banked: false,
+            is_base_point_set: false,
+            is_sweep_entity_transform_computed: false,
+            is_path_entity_transform_computed: false,
+            reference_vector: Vector::zero(),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Reader
+//------------------------------------------------------------------------------
+// codes like 40, 70, and 90 mean different things in each subclass, so the readers track the current subclass marker
+impl Entity {
+    pub(crate) fn apply_custom_reader_extrudedsurface(
+        common: &mut EntityCommon,
+        surface: &mut ExtrudedSurface,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        let mut subclass = String::new();
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+
+            let handled = match subclass.as_str() {
+                "AcDbExtrudedSurface" => match pair.code {
+                    90 => {
+                        surface.class_id = pair.assert_i32()?;
+                        true
+                    }
+                    10 => {
+                        surface.sweep_vector.x = pair.assert_f64()?;
+                        true
+                    }
+                    20 => {
+                        surface.sweep_vector.y = pair.assert_f64()?;
+                        true
+                    }
+                    30 => {
+                        surface.sweep_vector.z = pair.assert_f64()?;
+                        true
+                    }
+                    40 => {
+                        surface.extruded_entity_transform = read_matrix(&pair, iter)?;
+                        true
+                    }
+                    _ => apply_sweep_option_pair(&mut surface.sweep_options, &pair, iter)?,
+                },
+                _ => apply_surface_pair(
+                    &subclass,
+                    &pair,
+                    &mut surface.format_version_number,
+                    &mut surface.custom_data,
+                    &mut surface.custom_data2,
+                    &mut surface.u_isoline_count,
+                    &mut surface.v_isoline_count,
+                )?,
+            };
+            if !handled {
+                common.apply_individual_pair(&pair, iter)?;
+            }
+        }
+    }
+    pub(crate) fn apply_custom_reader_loftedsurface(
+        common: &mut EntityCommon,
+        surface: &mut LoftedSurface,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        let mut subclass = String::new();
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+
+            let handled = match subclass.as_str() {
+                "AcDbLoftedSurface" => {
+                    match pair.code {
+                        40 => surface.loft_entity_transform = read_matrix(&pair, iter)?,
+                        70 => surface.plane_normal_lofting_type = pair.assert_i16()?,
+                        41 => surface.start_draft_angle = pair.assert_f64()?,
+                        42 => surface.end_draft_angle = pair.assert_f64()?,
+                        43 => surface.start_draft_magnitude = pair.assert_f64()?,
+                        44 => surface.end_draft_magnitude = pair.assert_f64()?,
+                        290 => surface.is_arc_length_parameterized = pair.assert_bool()?,
+                        291 => surface.has_no_twist = pair.assert_bool()?,
+                        292 => surface.is_direction_aligned = pair.assert_bool()?,
+                        293 => surface.has_simple_surfaces = pair.assert_bool()?,
+                        294 => surface.has_closed_surfaces = pair.assert_bool()?,
+                        295 => surface.is_solid = pair.assert_bool()?,
+                        296 => surface.is_ruled = pair.assert_bool()?,
+                        297 => surface.has_virtual_guide = pair.assert_bool()?,
+                        _ => {
+                            common.apply_individual_pair(&pair, iter)?;
+                        }
+                    }
+                    true
+                }
+                _ => apply_surface_pair(
+                    &subclass,
+                    &pair,
+                    &mut surface.format_version_number,
+                    &mut surface.custom_data,
+                    &mut surface.custom_data2,
+                    &mut surface.u_isoline_count,
+                    &mut surface.v_isoline_count,
+                )?,
+            };
+            if !handled {
+                common.apply_individual_pair(&pair, iter)?;
+            }
+        }
+    }
+    pub(crate) fn apply_custom_reader_revolvedsurface(
+        common: &mut EntityCommon,
+        surface: &mut RevolvedSurface,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        let mut subclass = String::new();
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+
+            let handled = match subclass.as_str() {
+                "AcDbRevolvedSurface" => {
+                    match pair.code {
+                        90 => {
+                            surface.revolve_entity_id = pair.assert_i32()?;
+                            surface.revolve_entity_data = read_entity_data(iter)?;
+                        }
+                        10 => surface.axis_point.x = pair.assert_f64()?,
+                        20 => surface.axis_point.y = pair.assert_f64()?,
+                        30 => surface.axis_point.z = pair.assert_f64()?,
+                        11 => surface.axis_direction.x = pair.assert_f64()?,
+                        21 => surface.axis_direction.y = pair.assert_f64()?,
+                        31 => surface.axis_direction.z = pair.assert_f64()?,
+                        40 => surface.revolve_angle = pair.assert_f64()?,
+                        41 => surface.start_angle = pair.assert_f64()?,
+                        42 => surface.revolved_entity_transform = read_matrix(&pair, iter)?,
+                        43 => surface.draft_angle = pair.assert_f64()?,
+                        44 => surface.draft_start_distance = pair.assert_f64()?,
+                        45 => surface.draft_end_distance = pair.assert_f64()?,
+                        46 => surface.twist_angle = pair.assert_f64()?,
+                        290 => surface.is_solid = pair.assert_bool()?,
+                        291 => surface.is_close_to_axis = pair.assert_bool()?,
+                        _ => {
+                            common.apply_individual_pair(&pair, iter)?;
+                        }
+                    }
+                    true
+                }
+                _ => apply_surface_pair(
+                    &subclass,
+                    &pair,
+                    &mut surface.format_version_number,
+                    &mut surface.custom_data,
+                    &mut surface.custom_data2,
+                    &mut surface.u_isoline_count,
+                    &mut surface.v_isoline_count,
+                )?,
+            };
+            if !handled {
+                common.apply_individual_pair(&pair, iter)?;
+            }
+        }
+    }
+    pub(crate) fn apply_custom_reader_sweptsurface(
+        common: &mut EntityCommon,
+        surface: &mut SweptSurface,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        let mut subclass = String::new();
+        let mut has_read_sweep_entity = false;
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+
+            let handled = match subclass.as_str() {
+                "AcDbSweptSurface" => match pair.code {
+                    // the profile comes first, then the path
+                    90 if !has_read_sweep_entity => {
+                        surface.sweep_entity_id = pair.assert_i32()?;
+                        surface.sweep_entity_data = read_entity_data(iter)?;
+                        has_read_sweep_entity = true;
+                        true
+                    }
+                    90 => {
+                        surface.path_entity_id = pair.assert_i32()?;
+                        surface.path_entity_data = read_entity_data(iter)?;
+                        true
+                    }
+                    40 => {
+                        surface.profile_transform = read_matrix(&pair, iter)?;
+                        true
+                    }
+                    41 => {
+                        surface.path_transform = read_matrix(&pair, iter)?;
+                        true
+                    }
+                    _ => apply_sweep_option_pair(&mut surface.sweep_options, &pair, iter)?,
+                },
+                _ => apply_surface_pair(
+                    &subclass,
+                    &pair,
+                    &mut surface.format_version_number,
+                    &mut surface.custom_data,
+                    &mut surface.custom_data2,
+                    &mut surface.u_isoline_count,
+                    &mut surface.v_isoline_count,
+                )?,
+            };
+            if !handled {
+                common.apply_individual_pair(&pair, iter)?;
+            }
+        }
+    }
+}
+
+/// Applies the modeler geometry and isoline values shared by all surfaces.
+fn apply_surface_pair(
+    subclass: &str,
+    pair: &CodePair,
+    format_version_number: &mut i16,
+    custom_data: &mut Vec<String>,
+    custom_data2: &mut Vec<String>,
+    u_isoline_count: &mut i16,
+    v_isoline_count: &mut i16,
+) -> DxfResult<bool> {
+    match (subclass, pair.code) {
+        ("AcDbModelerGeometry", 70) => *format_version_number = pair.assert_i16()?,
+        ("AcDbModelerGeometry", 1) => custom_data.push(pair.assert_string()?),
+        ("AcDbModelerGeometry", 3) => custom_data2.push(pair.assert_string()?),
+        ("AcDbSurface", 71) => *u_isoline_count = pair.assert_i16()?,
+        ("AcDbSurface", 72) => *v_isoline_count = pair.assert_i16()?,
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn apply_sweep_option_pair(
+    options: &mut SweepOptions,
+    pair: &CodePair,
+    iter: &mut CodePairPutBack,
+) -> DxfResult<bool> {
+    match pair.code {
+        42 => options.draft_angle = pair.assert_f64()?,
+        43 => options.draft_start_distance = pair.assert_f64()?,
+        44 => options.draft_end_distance = pair.assert_f64()?,
+        45 => options.twist_angle = pair.assert_f64()?,
+        48 => options.scale_factor = pair.assert_f64()?,
+        49 => options.align_angle = pair.assert_f64()?,
+        46 => options.sweep_entity_transform = read_matrix(pair, iter)?,
+        47 => options.path_entity_transform = read_matrix(pair, iter)?,
+        290 => options.is_solid = pair.assert_bool()?,
+        70 => {
+            options.alignment =
+                enum_from_number!(SweepAlignment, NoAlignment, from_i16, pair.assert_i16()?)
+        }
+        292 => options.is_start_aligned = pair.assert_bool()?,
+        293 => options.is_banked = pair.assert_bool()?,
+        294 => options.is_base_point_set = pair.assert_bool()?,
+        295 => options.is_sweep_entity_transform_computed = pair.assert_bool()?,
+        296 => options.is_path_entity_transform_computed = pair.assert_bool()?,
+        11 => options.reference_vector.x = pair.assert_f64()?,
+        21 => options.reference_vector.y = pair.assert_f64()?,
+        31 => options.reference_vector.z = pair.assert_f64()?,
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Reads a 4x4 matrix stored as 16 consecutive values with the same code as `first`.
+fn read_matrix(first: &CodePair, iter: &mut CodePairPutBack) -> DxfResult<TransformationMatrix> {
+    let mut values = vec![first.assert_f64()?];
+    for _ in 1..16 {
+        values.push(expect_pair(iter, first.code)?.assert_f64()?);
+    }
+
+    Ok(TransformationMatrix::from_vec(&values))
+}
+
+/// Reads the byte count and the binary chunks that follow the id of a profile or path.
+fn read_entity_data(iter: &mut CodePairPutBack) -> DxfResult<Vec<u8>> {
+    let size = expect_pair(iter, 90)?.assert_i32()?;
+    let mut data = vec![];
+    while (data.len() as i32) < size {
+        let chunk = expect_pair(iter, 310)?.assert_binary()?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        data.extend(chunk);
+    }
+
+    Ok(data)
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Writer
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn add_custom_code_pairs_extrudedsurface(
+        pairs: &mut Vec<CodePair>,
+        surface: &ExtrudedSurface,
+        _version: AcadVersion,
+    ) -> bool {
+        add_surface_code_pairs(
+            pairs,
+            surface.format_version_number,
+            &surface.custom_data,
+            &surface.custom_data2,
+            surface.u_isoline_count,
+            surface.v_isoline_count,
+        );
+        pairs.push(CodePair::new_str(100, "AcDbExtrudedSurface"));
+        pairs.push(CodePair::new_i32(90, surface.class_id));
+        pairs.push(CodePair::new_f64(10, surface.sweep_vector.x));
+        pairs.push(CodePair::new_f64(20, surface.sweep_vector.y));
+        pairs.push(CodePair::new_f64(30, surface.sweep_vector.z));
+        add_matrix_code_pairs(pairs, 40, &surface.extruded_entity_transform);
+        add_sweep_option_code_pairs(pairs, &surface.sweep_options);
+        true
+    }
+    pub(crate) fn add_custom_code_pairs_loftedsurface(
+        pairs: &mut Vec<CodePair>,
+        surface: &LoftedSurface,
+        _version: AcadVersion,
+    ) -> bool {
+        add_surface_code_pairs(
+            pairs,
+            surface.format_version_number,
+            &surface.custom_data,
+            &surface.custom_data2,
+            surface.u_isoline_count,
+            surface.v_isoline_count,
+        );
+        pairs.push(CodePair::new_str(100, "AcDbLoftedSurface"));
+        add_matrix_code_pairs(pairs, 40, &surface.loft_entity_transform);
+        pairs.push(CodePair::new_i16(70, surface.plane_normal_lofting_type));
+        pairs.push(CodePair::new_f64(41, surface.start_draft_angle));
+        pairs.push(CodePair::new_f64(42, surface.end_draft_angle));
+        pairs.push(CodePair::new_f64(43, surface.start_draft_magnitude));
+        pairs.push(CodePair::new_f64(44, surface.end_draft_magnitude));
+        pairs.push(CodePair::new_bool(290, surface.is_arc_length_parameterized));
+        pairs.push(CodePair::new_bool(291, surface.has_no_twist));
+        pairs.push(CodePair::new_bool(292, surface.is_direction_aligned));
+        pairs.push(CodePair::new_bool(293, surface.has_simple_surfaces));
+        pairs.push(CodePair::new_bool(294, surface.has_closed_surfaces));
+        pairs.push(CodePair::new_bool(295, surface.is_solid));
+        pairs.push(CodePair::new_bool(296, surface.is_ruled));
+        pairs.push(CodePair::new_bool(297, surface.has_virtual_guide));
+        true
+    }
+    pub(crate) fn add_custom_code_pairs_revolvedsurface(
+        pairs: &mut Vec<CodePair>,
+        surface: &RevolvedSurface,
+        _version: AcadVersion,
+    ) -> bool {
+        add_surface_code_pairs(
+            pairs,
+            surface.format_version_number,
+            &surface.custom_data,
+            &surface.custom_data2,
+            surface.u_isoline_count,
+            surface.v_isoline_count,
+        );
+        pairs.push(CodePair::new_str(100, "AcDbRevolvedSurface"));
+        add_entity_data_code_pairs(
+            pairs,
+            surface.revolve_entity_id,
+            &surface.revolve_entity_data,
+        );
+        pairs.push(CodePair::new_f64(10, surface.axis_point.x));
+        pairs.push(CodePair::new_f64(20, surface.axis_point.y));
+        pairs.push(CodePair::new_f64(30, surface.axis_point.z));
+        pairs.push(CodePair::new_f64(11, surface.axis_direction.x));
+        pairs.push(CodePair::new_f64(21, surface.axis_direction.y));
+        pairs.push(CodePair::new_f64(31, surface.axis_direction.z));
+        pairs.push(CodePair::new_f64(40, surface.revolve_angle));
+        pairs.push(CodePair::new_f64(41, surface.start_angle));
+        add_matrix_code_pairs(pairs, 42, &surface.revolved_entity_transform);
+        pairs.push(CodePair::new_f64(43, surface.draft_angle));
+        pairs.push(CodePair::new_f64(44, surface.draft_start_distance));
+        pairs.push(CodePair::new_f64(45, surface.draft_end_distance));
+        pairs.push(CodePair::new_f64(46, surface.twist_angle));
+        pairs.push(CodePair::new_bool(290, surface.is_solid));
+        pairs.push(CodePair::new_bool(291, surface.is_close_to_axis));
+        true
+    }
+    pub(crate) fn add_custom_code_pairs_sweptsurface(
+        pairs: &mut Vec<CodePair>,
+        surface: &SweptSurface,
+        _version: AcadVersion,
+    ) -> bool {
+        add_surface_code_pairs(
+            pairs,
+            surface.format_version_number,
+            &surface.custom_data,
+            &surface.custom_data2,
+            surface.u_isoline_count,
+            surface.v_isoline_count,
+        );
+        pairs.push(CodePair::new_str(100, "AcDbSweptSurface"));
+        add_entity_data_code_pairs(pairs, surface.sweep_entity_id, &surface.sweep_entity_data);
+        add_entity_data_code_pairs(pairs, surface.path_entity_id, &surface.path_entity_data);
+        add_matrix_code_pairs(pairs, 40, &surface.profile_transform);
+        add_matrix_code_pairs(pairs, 41, &surface.path_transform);
+        add_sweep_option_code_pairs(pairs, &surface.sweep_options);
+        true
+    }
+}
+
+fn add_surface_code_pairs(
+    pairs: &mut Vec<CodePair>,
+    format_version_number: i16,
+    custom_data: &[String],
+    custom_data2: &[String],
+    u_isoline_count: i16,
+    v_isoline_count: i16,
+) {
+    pairs.push(CodePair::new_str(100, "AcDbModelerGeometry"));
+    pairs.push(CodePair::new_i16(70, format_version_number));
+    for data in custom_data {
+        pairs.push(CodePair::new_str(1, data));
+    }
+    for data in custom_data2 {
+        pairs.push(CodePair::new_str(3, data));
+    }
+    pairs.push(CodePair::new_str(100, "AcDbSurface"));
+    pairs.push(CodePair::new_i16(71, u_isoline_count));
+    pairs.push(CodePair::new_i16(72, v_isoline_count));
+}
+
+fn add_sweep_option_code_pairs(pairs: &mut Vec<CodePair>, options: &SweepOptions) {
+    pairs.push(CodePair::new_f64(42, options.draft_angle));
+    pairs.push(CodePair::new_f64(43, options.draft_start_distance));
+    pairs.push(CodePair::new_f64(44, options.draft_end_distance));
+    pairs.push(CodePair::new_f64(45, options.twist_angle));
+    pairs.push(CodePair::new_f64(48, options.scale_factor));
+    pairs.push(CodePair::new_f64(49, options.align_angle));
+    add_matrix_code_pairs(pairs, 46, &options.sweep_entity_transform);
+    add_matrix_code_pairs(pairs, 47, &options.path_entity_transform);
+    pairs.push(CodePair::new_bool(290, options.is_solid));
+    pairs.push(CodePair::new_i16(70, options.alignment as i16));
+    pairs.push(CodePair::new_bool(292, options.is_start_aligned));
+    pairs.push(CodePair::new_bool(293, options.is_banked));
+    pairs.push(CodePair::new_bool(294, options.is_base_point_set));
+    pairs.push(CodePair::new_bool(
+        295,
+        options.is_sweep_entity_transform_computed,
+    ));
+    pairs.push(CodePair::new_bool(
+        296,
+        options.is_path_entity_transform_computed,
+    ));
+    pairs.push(CodePair::new_f64(11, options.reference_vector.x));
+    pairs.push(CodePair::new_f64(21, options.reference_vector.y));
+    pairs.push(CodePair::new_f64(31, options.reference_vector.z));
+}
+
+fn add_matrix_code_pairs(pairs: &mut Vec<CodePair>, code: i32, matrix: &TransformationMatrix) {
+    for value in matrix.values() {
+        pairs.push(CodePair::new_f64(code, value));
+    }
+}
+
+fn add_entity_data_code_pairs(pairs: &mut Vec<CodePair>, id: i32, data: &[u8]) {
+    pairs.push(CodePair::new_i32(90, id));
+    pairs.push(CodePair::new_i32(90, data.len() as i32));
+    for chunk in data.chunks(128) {
+        pairs.push(CodePair::new_binary(310, chunk.to_vec()));
+    }
+}
@@ -1,7 +1,12 @@
 use crate::enums::*;
 use crate::helper_functions::*;
 use crate::tables::*;
-use crate::{CodePair, Color, Drawing, DxfError, DxfResult, Handle};
+use crate::{
+    CodePair, Color, Drawing, DxfError, DxfResult, Handle, Transparency, XData, XDataItem,
+};
+
+/// The `XData` application that stores the transparency of a `Layer`.
+pub(crate) const LAYER_TRANSPARENCY_APPLICATION_NAME: &str = "AcCmTransparency";
 
 //------------------------------------------------------------------------------
 //                                                                         LineTypeElement
@@ -79,13 +84,13 @@ impl LineType {
                     if nested_element == "STANDARD" {
                         continue;
                     } else if let Some(value) = nested_element.strip_prefix("S=") {
-                        line_type_element.scale_value = Some(parse_f64(value.to_string(), 0)?);
+                        line_type_element.scale_value = Some(parse_f64(value, 0)?);
                     } else if let Some(value) = nested_element.strip_prefix("R=") {
-                        line_type_element.rotation_angle = Some(parse_f64(value.to_string(), 0)?);
+                        line_type_element.rotation_angle = Some(parse_f64(value, 0)?);
                     } else if let Some(value) = nested_element.strip_prefix("X=") {
-                        line_type_element.x_offset = Some(parse_f64(value.to_string(), 0)?);
+                        line_type_element.x_offset = Some(parse_f64(value, 0)?);
                     } else if let Some(value) = nested_element.strip_prefix("Y=") {
-                        line_type_element.y_offset = Some(parse_f64(value.to_string(), 0)?);
+                        line_type_element.y_offset = Some(parse_f64(value, 0)?);
                     }
                 }
             } else {
@@ -97,7 +102,7 @@ impl LineType {
                     line_type_element = LineTypeElement::default();
                 }
 
-                line_type_element.dash_dot_space_length = parse_f64(element.to_string(), 0)?;
+                line_type_element.dash_dot_space_length = parse_f64(element, 0)?;
             }
 
             if i == length - 1 {
@@ -224,8 +229,10 @@ pub(crate) fn custom_read_line_type_code_pairs(
 ) -> DxfResult<()> {
     match pair.code {
         49 => {
-            let mut element = LineTypeElement::default();
-            element.dash_dot_space_length = pair.assert_f64()?;
+            let element = LineTypeElement {
+                dash_dot_space_length: pair.assert_f64()?,
+                ..Default::default()
+            };
             line_type.line_elements.push(element);
         }
         74 => {
@@ -270,6 +277,30 @@ impl Layer {
             _ => (),
         }
     }
+    /// Gets the transparency of the layer from its `XData`.  Layers without one are opaque.
+    pub fn transparency(&self) -> Transparency {
+        self.x_data
+            .iter()
+            .filter(|x| x.application_name == LAYER_TRANSPARENCY_APPLICATION_NAME)
+            .flat_map(|x| x.items.iter())
+            .find_map(|item| match item {
+                XDataItem::Long(v) => Some(Transparency::from_raw_value(*v)),
+                _ => None,
+            })
+            .unwrap_or_else(Transparency::opaque)
+    }
+    /// Sets the transparency of the layer by replacing its transparency `XData`.  BYLAYER and BYBLOCK aren't valid layer
+    /// transparencies and are treated as opaque.
+    pub fn set_transparency(&mut self, transparency: Transparency) {
+        self.x_data
+            .retain(|x| x.application_name != LAYER_TRANSPARENCY_APPLICATION_NAME);
+        if transparency.alpha().is_some() && transparency != Transparency::opaque() {
+            self.x_data.push(XData {
+                application_name: String::from(LAYER_TRANSPARENCY_APPLICATION_NAME),
+                items: vec![XDataItem::Long(transparency.raw_value())],
+            });
+        }
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -316,6 +347,7 @@ impl ViewPort {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use crate::entities::*;
     use crate::enums::*;
@@ -438,6 +470,113 @@ mod tests {
         assert!(!layer.is_layer_on);
     }
 
+    #[test]
+    fn read_and_write_layer_flags() {
+        let drawing = read_table(
+            "LAYER",
+            vec![CodePair::new_str(0, "LAYER"), CodePair::new_i16(70, 5)],
+        );
+        let layer = drawing.layers().next().unwrap();
+        assert!(layer.is_frozen());
+        assert!(!layer.is_frozen_in_new_viewports());
+        assert!(layer.is_locked());
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(100, "AcDbLayerTableRecord"),
+                CodePair::new_str(2, ""),
+                CodePair::new_i16(70, 5),
+            ],
+        );
+    }
+
+    #[test]
+    fn read_layer_true_color() {
+        let drawing = read_table(
+            "LAYER",
+            vec![
+                CodePair::new_str(0, "LAYER"),
+                CodePair::new_i16(62, -5),
+                CodePair::new_i32(420, 0x0000FE),
+                CodePair::new_str(430, "BOOK$BLUE"),
+            ],
+        );
+        let layers = drawing.layers().collect::<Vec<_>>();
+        let layer = layers[0];
+        assert_eq!(Some(5), layer.color.index());
+        assert_eq!(Some((0, 0, 254)), layer.color.true_color());
+        assert_eq!(Some(("BOOK", "BLUE")), layer.color.color_book());
+        assert!(!layer.is_layer_on);
+    }
+
+    #[test]
+    fn write_layer_true_color() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        drawing.add_layer(Layer {
+            name: String::from("layer-name"),
+            color: Color::from_rgb(0, 0, 254),
+            ..Default::default()
+        });
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(2, "layer-name"),
+                CodePair::new_i16(70, 0),
+                CodePair::new_i16(62, 5),
+                CodePair::new_i32(420, 0x0000FE),
+                CodePair::new_str(6, "CONTINUOUS"),
+            ],
+        );
+
+        // older versions only get the closest index
+        drawing.header.version = AcadVersion::R2000;
+        assert_contains_pairs(
+            &drawing,
+            vec![CodePair::new_i16(62, 5), CodePair::new_str(6, "CONTINUOUS")],
+        );
+        assert_not_contains_pairs(&drawing, vec![CodePair::new_i32(420, 0x0000FE)]);
+    }
+
+    #[test]
+    fn read_layer_transparency() {
+        let drawing = read_table(
+            "LAYER",
+            vec![
+                CodePair::new_str(0, "LAYER"),
+                CodePair::new_i16(62, 5),
+                CodePair::new_str(1001, "AcCmTransparency"),
+                CodePair::new_i32(1071, 0x0200_0033),
+            ],
+        );
+        let layers = drawing.layers().collect::<Vec<_>>();
+        assert_eq!(Some(0x33), layers[0].transparency().alpha());
+    }
+
+    #[test]
+    fn write_layer_transparency() {
+        let mut drawing = Drawing::new();
+        drawing.header.version = AcadVersion::R2004;
+        let mut layer = Layer {
+            name: String::from("layer-name"),
+            ..Default::default()
+        };
+        assert_eq!(Transparency::opaque(), layer.transparency());
+        layer.set_transparency(Transparency::from_percent(80.0));
+        layer.set_transparency(Transparency::from_alpha(0x33));
+        assert_eq!(1, layer.x_data.len());
+        drawing.add_layer(layer);
+        drawing.normalize();
+        assert_contains_pairs(
+            &drawing,
+            vec![
+                CodePair::new_str(1001, "AcCmTransparency"),
+                CodePair::new_i32(1071, 0x0200_0033),
+            ],
+        );
+        assert!(drawing.app_ids().any(|a| a.name == "AcCmTransparency"));
+    }
+
     #[test]
     fn write_layer() {
         let mut drawing = Drawing::new();
@@ -0,0 +1,620 @@
+// other implementation is in `generated/entities.rs`
+
+use enum_primitive::FromPrimitive;
+
+use crate::{CodePair, DataTableValue, DxfError, DxfResult, Handle, Point, Vector};
+
+use crate::code_pair_put_back::CodePairPutBack;
+use crate::entities::*;
+use crate::enums::*;
+use crate::helper_functions::*;
+
+const CELL_VALUE_START: &str = "CELL_VALUE";
+const CELL_VALUE_END: &str = "ACVALUE_END";
+const MAX_TEXT_CHUNK_LENGTH: usize = 250;
+
+const VALUE_TYPE_UNKNOWN: i32 = 0;
+const VALUE_TYPE_LONG: i32 = 1;
+const VALUE_TYPE_DOUBLE: i32 = 2;
+const VALUE_TYPE_STRING: i32 = 4;
+const VALUE_TYPE_POINT_2D: i32 = 16;
+const VALUE_TYPE_POINT_3D: i32 = 32;
+const VALUE_TYPE_HANDLE: i32 = 64;
+
+//------------------------------------------------------------------------------
+//                                                                     TableCell
+//------------------------------------------------------------------------------
+/// Represents a single cell of a `Table`.  A text cell displays `text`, and a block cell displays the block record
+/// referenced by `block_handle` with the values of its attributes in `attribute_values`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCell {
+    pub cell_type: TableCellType,
+    pub flags: i32,
+    /// Set on every cell that's part of a merged range.
+    pub is_merged: bool,
+    pub is_autofit: bool,
+    /// The number of columns spanned by the merged range that starts at this cell.
+    pub merged_column_count: i32,
+    /// The number of rows spanned by the merged range that starts at this cell.
+    pub merged_row_count: i32,
+    pub override_flags: i32,
+    pub virtual_edge_flags: i16,
+    /// Text rotation in degrees.
+    pub rotation: f64,
+    /// The `Field` object that computes the cell's text, if any.
+    pub field_handle: Handle,
+    pub text: String,
+    pub text_style_name: String,
+    pub block_handle: Handle,
+    pub block_scale: f64,
+    pub attribute_values: Vec<TableCellAttribute>,
+    pub value: Option<TableCellValue>,
+}
+
+impl Default for TableCell {
+    fn default() -> Self {
+        TableCell {
+            cell_type: TableCellType::Text,
+            flags: 0,
+            is_merged: false,
+            is_autofit: false,
+            merged_column_count: 1,
+            merged_row_count: 1,
+            override_flags: 0,
+            virtual_edge_flags: 0,
+            rotation: 0.0,
+            field_handle: Handle::empty(),
+            text: String::new(),
+            text_style_name: String::new(),
+            block_handle: Handle::empty(),
+            block_scale: 1.0,
+            attribute_values: vec![],
+            value: None,
+        }
+    }
+}
+
+impl TableCell {
+    /// Creates a new text cell.
+    pub fn from_text(text: &str) -> Self {
+        TableCell {
+            text: String::from(text),
+            ..Default::default()
+        }
+    }
+    /// Creates a new block cell that displays the specified block record.
+    pub fn from_block(block_handle: Handle) -> Self {
+        TableCell {
+            cell_type: TableCellType::Block,
+            block_handle,
+            ..Default::default()
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                            TableCellAttribute
+//------------------------------------------------------------------------------
+/// Represents the value of a single attribute of a block displayed in a `TableCell`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCellAttribute {
+    pub attribute_definition_handle: Handle,
+    pub value: String,
+}
+
+//------------------------------------------------------------------------------
+//                                                                TableCellValue
+//------------------------------------------------------------------------------
+/// Represents the typed value behind a `TableCell` and how it's formatted for display.  Dates and binary values
+/// aren't supported and are read as `None`.  Cell values have no boolean type, so `DataTableValue::Boolean` is written
+/// as a long and reads back as `DataTableValue::Integer`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCellValue {
+    pub flags: i32,
+    pub value: Option<DataTableValue>,
+    pub unit_type: i32,
+    pub format: String,
+    pub formatted_value: String,
+}
+
+//------------------------------------------------------------------------------
+//                                                                TableCellRange
+//------------------------------------------------------------------------------
+/// Represents an inclusive rectangular range of cells in a `Table`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCellRange {
+    pub top_row: usize,
+    pub left_column: usize,
+    pub bottom_row: usize,
+    pub right_column: usize,
+}
+
+//------------------------------------------------------------------------------
+//                                                                         Table
+//------------------------------------------------------------------------------
+impl Table {
+    /// Creates a new table with an empty text cell at each row and column.  The anonymous block that holds the
+    /// table's graphics isn't generated.
+    pub fn new(insertion_point: Point, row_heights: Vec<f64>, column_widths: Vec<f64>) -> Self {
+        let cells = vec![TableCell::default(); row_heights.len() * column_widths.len()];
+        Table {
+            insertion_point,
+            row_heights,
+            column_widths,
+            cells,
+            ..Default::default()
+        }
+    }
+    pub fn row_count(&self) -> usize {
+        self.row_heights.len()
+    }
+    pub fn column_count(&self) -> usize {
+        self.column_widths.len()
+    }
+    /// Returns the total width of all columns.
+    pub fn width(&self) -> f64 {
+        self.column_widths.iter().sum()
+    }
+    /// Returns the total height of all rows.
+    pub fn height(&self) -> f64 {
+        self.row_heights.iter().sum()
+    }
+    pub fn cell(&self, row: usize, column: usize) -> Option<&TableCell> {
+        self.cell_index(row, column).and_then(|i| self.cells.get(i))
+    }
+    pub fn cell_mut(&mut self, row: usize, column: usize) -> Option<&mut TableCell> {
+        self.cell_index(row, column)
+            .and_then(move |i| self.cells.get_mut(i))
+    }
+    /// Returns the cells of each row, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[TableCell]> {
+        self.cells.chunks(self.column_count().max(1))
+    }
+    /// Returns the ranges of merged cells.
+    pub fn merged_ranges(&self) -> Vec<TableCellRange> {
+        let mut ranges = vec![];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell.merged_column_count > 1 || cell.merged_row_count > 1 {
+                let top_row = i / self.column_count().max(1);
+                let left_column = i % self.column_count().max(1);
+                ranges.push(TableCellRange {
+                    top_row,
+                    left_column,
+                    bottom_row: top_row + cell.merged_row_count.max(1) as usize - 1,
+                    right_column: left_column + cell.merged_column_count.max(1) as usize - 1,
+                });
+            }
+        }
+
+        ranges
+    }
+    /// Merges the specified range of cells, which is then displayed as its top left cell.
+    pub fn merge_cells(&mut self, range: &TableCellRange) {
+        for row in range.top_row..=range.bottom_row {
+            for column in range.left_column..=range.right_column {
+                if let Some(cell) = self.cell_mut(row, column) {
+                    cell.is_merged = true;
+                    cell.merged_column_count = 1;
+                    cell.merged_row_count = 1;
+                }
+            }
+        }
+        if let Some(cell) = self.cell_mut(range.top_row, range.left_column) {
+            cell.merged_column_count = (range.right_column - range.left_column + 1) as i32;
+            cell.merged_row_count = (range.bottom_row - range.top_row + 1) as i32;
+        }
+    }
+    /// Returns the four corners of the table, starting at the insertion point and going clockwise.
+    pub fn corners(&self) -> [Point; 4] {
+        let right = self.horizontal_direction.normalized();
+        let down = Vector::z_axis().cross(&right).scaled(-1.0);
+        let top_left = self.insertion_point.clone();
+        let top_right = top_left.clone() + right.scaled(self.width());
+        let bottom_right = top_right.clone() + down.scaled(self.height());
+        let bottom_left = top_left.clone() + down.scaled(self.height());
+        [top_left, top_right, bottom_right, bottom_left]
+    }
+}
+
+// private implementation
+impl Table {
+    fn cell_index(&self, row: usize, column: usize) -> Option<usize> {
+        if row < self.row_count() && column < self.column_count() {
+            Some(row * self.column_count() + column)
+        } else {
+            None
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Reader
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn apply_custom_reader_table(
+        common: &mut EntityCommon,
+        table: &mut Table,
+        iter: &mut CodePairPutBack,
+    ) -> DxfResult<bool> {
+        // many codes are reused between the common entity data, the block reference, the table, and its cells
+        let mut subclass = String::new();
+        loop {
+            let pair = next_pair!(iter);
+            if pair.code == 100 {
+                subclass = pair.assert_string()?;
+                continue;
+            }
+
+            match (subclass.as_str(), pair.code) {
+                ("AcDbBlockReference", 2) => {
+                    table.block_name = pair.assert_string()?;
+                }
+                ("AcDbBlockReference", 10) => {
+                    table.insertion_point.x = pair.assert_f64()?;
+                }
+                ("AcDbBlockReference", 20) => {
+                    table.insertion_point.y = pair.assert_f64()?;
+                }
+                ("AcDbBlockReference", 30) => {
+                    table.insertion_point.z = pair.assert_f64()?;
+                }
+                ("AcDbTable", 171) => {
+                    table.cells.push(TableCell {
+                        cell_type: enum_from_number!(
+                            TableCellType,
+                            Text,
+                            from_i16,
+                            pair.assert_i16()?
+                        ),
+                        ..Default::default()
+                    });
+                }
+                ("AcDbTable", _) if !table.cells.is_empty() => {
+                    let cell = vec_last!(table.cells);
+                    if !apply_cell_pair(cell, &pair, iter)? && is_common_pair(&pair) {
+                        common.apply_individual_pair(&pair, iter)?;
+                    }
+                }
+                ("AcDbTable", 280) => {
+                    table.version = pair.assert_i16()?;
+                }
+                ("AcDbTable", 342) => {
+                    table.__table_style_handle = pair.as_handle()?;
+                }
+                ("AcDbTable", 343) => {
+                    table.__block_record_handle = pair.as_handle()?;
+                }
+                ("AcDbTable", 11) => {
+                    table.horizontal_direction.x = pair.assert_f64()?;
+                }
+                ("AcDbTable", 21) => {
+                    table.horizontal_direction.y = pair.assert_f64()?;
+                }
+                ("AcDbTable", 31) => {
+                    table.horizontal_direction.z = pair.assert_f64()?;
+                }
+                ("AcDbTable", 90) => {
+                    table.value_flags = pair.assert_i32()?;
+                }
+                ("AcDbTable", 91) | ("AcDbTable", 92) => (), // row and column counts; taken from the sizes
+                ("AcDbTable", 93) => {
+                    table.override_flags = pair.assert_i32()?;
+                }
+                ("AcDbTable", 94) => {
+                    table.border_color_override_flags = pair.assert_i32()?;
+                }
+                ("AcDbTable", 95) => {
+                    table.border_line_weight_override_flags = pair.assert_i32()?;
+                }
+                ("AcDbTable", 96) => {
+                    table.border_visibility_override_flags = pair.assert_i32()?;
+                }
+                ("AcDbTable", 141) => {
+                    table.row_heights.push(pair.assert_f64()?);
+                }
+                ("AcDbTable", 142) => {
+                    table.column_widths.push(pair.assert_f64()?);
+                }
+                ("AcDbTable", _) => {
+                    // table-wide style overrides aren't modelled
+                    if is_common_pair(&pair) {
+                        common.apply_individual_pair(&pair, iter)?;
+                    }
+                }
+                _ => {
+                    common.apply_individual_pair(&pair, iter)?;
+                }
+            }
+        }
+    }
+}
+
+fn is_common_pair(pair: &CodePair) -> bool {
+    // only extension data and x-data can appear in the table data without being table properties
+    pair.code == 102 || pair.code >= 1000
+}
+
+fn apply_cell_pair(
+    cell: &mut TableCell,
+    pair: &CodePair,
+    iter: &mut CodePairPutBack,
+) -> DxfResult<bool> {
+    match pair.code {
+        1 | 2 => {
+            // long text is split into chunks with code 2 before the final chunk with code 1
+            cell.text.push_str(&pair.assert_string()?);
+        }
+        7 => {
+            cell.text_style_name = pair.assert_string()?;
+        }
+        91 => {
+            cell.override_flags = pair.assert_i32()?;
+        }
+        144 => {
+            cell.block_scale = pair.assert_f64()?;
+        }
+        145 => {
+            cell.rotation = pair.assert_f64()?;
+        }
+        172 => {
+            cell.flags = i32::from(pair.assert_i16()?);
+        }
+        173 => {
+            cell.is_merged = as_bool(pair.assert_i16()?);
+        }
+        174 => {
+            cell.is_autofit = as_bool(pair.assert_i16()?);
+        }
+        175 => {
+            cell.merged_column_count = pair.assert_i32()?;
+        }
+        176 => {
+            cell.merged_row_count = pair.assert_i32()?;
+        }
+        178 => {
+            cell.virtual_edge_flags = pair.assert_i16()?;
+        }
+        179 => (), // attribute definition count; each attribute starts with a 331 pair
+        300 => {
+            vec_last!(cell.attribute_values).value = pair.assert_string()?;
+        }
+        301 if pair.assert_string()? == CELL_VALUE_START => {
+            cell.value = Some(read_cell_value(iter)?);
+        }
+        331 => {
+            cell.attribute_values.push(TableCellAttribute {
+                attribute_definition_handle: pair.as_handle()?,
+                value: String::new(),
+            });
+        }
+        340 => {
+            cell.block_handle = pair.as_handle()?;
+        }
+        344 => {
+            cell.field_handle = pair.as_handle()?;
+        }
+        _ => return Ok(false), // per-cell style overrides aren't modelled
+    }
+
+    Ok(true)
+}
+
+fn read_cell_value(iter: &mut CodePairPutBack) -> DxfResult<TableCellValue> {
+    let mut value = TableCellValue::default();
+    let mut data_type = VALUE_TYPE_UNKNOWN;
+    loop {
+        let pair = next_pair_or_err(iter)?;
+        match pair.code {
+            0 => {
+                // malformed value; leave the entity end for the caller
+                iter.put_back(Ok(pair));
+                break;
+            }
+            1..=3 => {
+                let s = pair.assert_string()?;
+                match value.value {
+                    Some(DataTableValue::Str(ref mut existing)) => existing.push_str(&s),
+                    _ => value.value = Some(DataTableValue::Str(s)),
+                }
+            }
+            11 => {
+                let p = Point::new(pair.assert_f64()?, 0.0, 0.0);
+                value.value = Some(if data_type == VALUE_TYPE_POINT_2D {
+                    DataTableValue::Point2D(p)
+                } else {
+                    DataTableValue::Point3D(p)
+                });
+            }
+            21 => {
+                if let Some(
+                    DataTableValue::Point2D(ref mut p) | DataTableValue::Point3D(ref mut p),
+                ) = value.value
+                {
+                    p.y = pair.assert_f64()?;
+                }
+            }
+            31 => {
+                if let Some(DataTableValue::Point3D(ref mut p)) = value.value {
+                    p.z = pair.assert_f64()?;
+                }
+            }
+            90 => {
+                data_type = pair.assert_i32()?;
+            }
+            91 => {
+                value.value = Some(DataTableValue::Integer(pair.assert_i32()?));
+            }
+            93 => {
+                value.flags = pair.assert_i32()?;
+            }
+            94 => {
+                value.unit_type = pair.assert_i32()?;
+            }
+            140 => {
+                value.value = Some(DataTableValue::Double(pair.assert_f64()?));
+            }
+            300 => {
+                value.format = pair.assert_string()?;
+            }
+            302 => {
+                value.formatted_value = pair.assert_string()?;
+            }
+            304 => break, // ACVALUE_END
+            330 => {
+                value.value = Some(DataTableValue::Handle(pair.as_handle()?));
+            }
+            _ => (), // dates and binary data aren't supported
+        }
+    }
+
+    Ok(value)
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Writer
+//------------------------------------------------------------------------------
+impl Entity {
+    pub(crate) fn add_custom_code_pairs_table(
+        pairs: &mut Vec<CodePair>,
+        table: &Table,
+        _version: AcadVersion,
+    ) -> bool {
+        pairs.push(CodePair::new_str(100, "AcDbBlockReference"));
+        pairs.push(CodePair::new_string(2, &table.block_name));
+        pairs.push(CodePair::new_f64(10, table.insertion_point.x));
+        pairs.push(CodePair::new_f64(20, table.insertion_point.y));
+        pairs.push(CodePair::new_f64(30, table.insertion_point.z));
+        pairs.push(CodePair::new_str(100, "AcDbTable"));
+        pairs.push(CodePair::new_i16(280, table.version));
+        pairs.push(CodePair::new_string(
+            342,
+            &table.__table_style_handle.as_string(),
+        ));
+        pairs.push(CodePair::new_string(
+            343,
+            &table.__block_record_handle.as_string(),
+        ));
+        pairs.push(CodePair::new_f64(11, table.horizontal_direction.x));
+        pairs.push(CodePair::new_f64(21, table.horizontal_direction.y));
+        pairs.push(CodePair::new_f64(31, table.horizontal_direction.z));
+        pairs.push(CodePair::new_i32(90, table.value_flags));
+        pairs.push(CodePair::new_i32(91, table.row_count() as i32));
+        pairs.push(CodePair::new_i32(92, table.column_count() as i32));
+        pairs.push(CodePair::new_i32(93, table.override_flags));
+        pairs.push(CodePair::new_i32(94, table.border_color_override_flags));
+        pairs.push(CodePair::new_i32(
+            95,
+            table.border_line_weight_override_flags,
+        ));
+        pairs.push(CodePair::new_i32(
+            96,
+            table.border_visibility_override_flags,
+        ));
+        for height in &table.row_heights {
+            pairs.push(CodePair::new_f64(141, *height));
+        }
+        for width in &table.column_widths {
+            pairs.push(CodePair::new_f64(142, *width));
+        }
+        for cell in &table.cells {
+            add_cell_code_pairs(pairs, cell);
+        }
+
+        true
+    }
+}
+
+fn add_cell_code_pairs(pairs: &mut Vec<CodePair>, cell: &TableCell) {
+    pairs.push(CodePair::new_i16(171, cell.cell_type as i16));
+    pairs.push(CodePair::new_i16(172, cell.flags as i16));
+    pairs.push(CodePair::new_i16(173, as_i16(cell.is_merged)));
+    pairs.push(CodePair::new_i16(174, as_i16(cell.is_autofit)));
+    pairs.push(CodePair::new_i32(175, cell.merged_column_count));
+    pairs.push(CodePair::new_i32(176, cell.merged_row_count));
+    pairs.push(CodePair::new_i32(91, cell.override_flags));
+    pairs.push(CodePair::new_i16(178, cell.virtual_edge_flags));
+    pairs.push(CodePair::new_f64(145, cell.rotation));
+    if !cell.field_handle.is_empty() {
+        pairs.push(CodePair::new_string(344, &cell.field_handle.as_string()));
+    }
+    match cell.cell_type {
+        TableCellType::Text => {
+            add_text_code_pairs(pairs, &cell.text);
+            pairs.push(CodePair::new_string(7, &cell.text_style_name));
+        }
+        TableCellType::Block => {
+            pairs.push(CodePair::new_string(340, &cell.block_handle.as_string()));
+            pairs.push(CodePair::new_f64(144, cell.block_scale));
+            pairs.push(CodePair::new_i16(179, cell.attribute_values.len() as i16));
+            for attribute in &cell.attribute_values {
+                pairs.push(CodePair::new_string(
+                    331,
+                    &attribute.attribute_definition_handle.as_string(),
+                ));
+                pairs.push(CodePair::new_string(300, &attribute.value));
+            }
+        }
+    }
+    if let Some(ref value) = cell.value {
+        add_cell_value_code_pairs(pairs, value);
+    }
+}
+
+fn add_text_code_pairs(pairs: &mut Vec<CodePair>, text: &str) {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut chunks = chars.chunks(MAX_TEXT_CHUNK_LENGTH).collect::<Vec<_>>();
+    let last = chunks.pop().unwrap_or(&[]);
+    for chunk in chunks {
+        pairs.push(CodePair::new_string(2, &chunk.iter().collect::<String>()));
+    }
+    pairs.push(CodePair::new_string(1, &last.iter().collect::<String>()));
+}
+
+fn add_cell_value_code_pairs(pairs: &mut Vec<CodePair>, value: &TableCellValue) {
+    pairs.push(CodePair::new_str(301, CELL_VALUE_START));
+    pairs.push(CodePair::new_i32(93, value.flags));
+    match value.value {
+        None => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_UNKNOWN));
+        }
+        Some(DataTableValue::Boolean(b)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_LONG));
+            pairs.push(CodePair::new_i32(91, i32::from(as_i16(b))));
+        }
+        Some(DataTableValue::Integer(i)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_LONG));
+            pairs.push(CodePair::new_i32(91, i));
+        }
+        Some(DataTableValue::Double(d)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_DOUBLE));
+            pairs.push(CodePair::new_f64(140, d));
+        }
+        Some(DataTableValue::Str(ref s)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_STRING));
+            add_text_code_pairs(pairs, s);
+        }
+        Some(DataTableValue::Point2D(ref p)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_POINT_2D));
+            pairs.push(CodePair::new_f64(11, p.x));
+            pairs.push(CodePair::new_f64(21, p.y));
+        }
+        Some(DataTableValue::Point3D(ref p)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_POINT_3D));
+            pairs.push(CodePair::new_f64(11, p.x));
+            pairs.push(CodePair::new_f64(21, p.y));
+            pairs.push(CodePair::new_f64(31, p.z));
+        }
+        Some(DataTableValue::Handle(h)) => {
+            pairs.push(CodePair::new_i32(90, VALUE_TYPE_HANDLE));
+            pairs.push(CodePair::new_string(330, &h.as_string()));
+        }
+    }
+    pairs.push(CodePair::new_i32(94, value.unit_type));
+    pairs.push(CodePair::new_string(300, &value.format));
+    pairs.push(CodePair::new_string(302, &value.formatted_value));
+    pairs.push(CodePair::new_str(304, CELL_VALUE_END));
+}
@@ -0,0 +1,611 @@
+// other implementation is in `entity.rs`
+
+use std::f64::consts::PI;
+
+use crate::entities::*;
+use crate::enums::PolylineCurvedAndSmoothSurfaceType;
+use crate::nurbs::NurbsCurve;
+use crate::{LwPolylineSegment, ObjectCoordinateSystem, Point, Vector};
+
+/// The most segments that a single curved piece is split into, regardless of the requested tolerance.
+const MAX_SEGMENTS: usize = 4096;
+
+/// How many times a spline span is halved when looking for a piece that's within the chordal tolerance.
+const MAX_SUBDIVISION_DEPTH: usize = 12;
+
+/// Sweeps within this many radians of a full turn are treated as closed.
+const FULL_TURN_TOLERANCE: f64 = 1e-12;
+
+/// Controls how finely curves are approximated by straight segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tessellation {
+    /// The maximum distance between the curve and the segments that approximate it.
+    ChordalTolerance(f64),
+    /// The number of segments used for each curved piece, i.e., each arc, ellipse, bulged polyline segment, or spline.
+    SegmentCount(usize),
+}
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Approximates the entity's curve by straight segments and returns their points in world coordinates, or `None`
+    /// if the entity isn't a `Line`, `Arc`, `Circle`, `Ellipse`, `Spline`, `LwPolyline`, or a `Polyline` that isn't a
+    /// mesh.  Closed curves end with their first point repeated.
+    ///
+    /// Points in an object coordinate system are converted using the entity's normal, and any thickness is ignored.
+    /// Splines are evaluated from their control points and weights, or interpolated through their fit points when there
+    /// are no control points.  A curve-fit `Polyline` follows its generated vertices and their bulges, while a spline-fit one
+    /// uses its generated vertices or, if there are none, evaluates the B-spline defined by its frame control points.
+    pub fn tessellate(&self, tessellation: Tessellation) -> Option<Vec<Point>> {
+        match self.specific {
+            EntityType::Arc(ref arc) => {
+                let sweep = (arc.end_angle - arc.start_angle).rem_euclid(360.0);
+                let sweep = if sweep == 0.0 { 360.0 } else { sweep };
+                Some(ocs_arc_points(
+                    &arc.normal,
+                    &arc.center,
+                    arc.radius,
+                    arc.start_angle.to_radians(),
+                    sweep.to_radians(),
+                    tessellation,
+                ))
+            }
+            EntityType::Circle(ref circle) => Some(ocs_arc_points(
+                &circle.normal,
+                &circle.center,
+                circle.radius,
+                0.0,
+                2.0 * PI,
+                tessellation,
+            )),
+            EntityType::Ellipse(ref ellipse) => Some(ellipse_points(ellipse, tessellation)),
+            EntityType::Line(ref line) => Some(vec![line.p1.clone(), line.p2.clone()]),
+            EntityType::LwPolyline(ref poly) => {
+                let ocs = ObjectCoordinateSystem::new(&poly.extrusion_direction);
+                let vertices = poly
+                    .vertices
+                    .iter()
+                    .map(|v| (Point::new(v.x, v.y, self.common.elevation), v.bulge))
+                    .collect::<Vec<_>>();
+                Some(bulge_polyline_points(
+                    &ocs,
+                    &vertices,
+                    poly.is_closed(),
+                    tessellation,
+                ))
+            }
+            EntityType::Polyline(ref poly) => polyline_points(poly, tessellation),
+            EntityType::Spline(ref spline) => Some(spline_points(spline, tessellation)),
+            _ => None,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                  Tessellation
+//------------------------------------------------------------------------------
+impl Tessellation {
+    /// Returns the number of segments needed for an arc with the specified radius that spans `sweep` radians.
+    fn arc_segments(&self, radius: f64, sweep: f64) -> usize {
+        let count = match *self {
+            Tessellation::SegmentCount(count) => count,
+            Tessellation::ChordalTolerance(tolerance) => {
+                let sweep = sweep.abs();
+                if radius <= 0.0 || sweep == 0.0 {
+                    1
+                } else if tolerance.is_nan() || tolerance <= 0.0 {
+                    MAX_SEGMENTS
+                } else if tolerance >= radius {
+                    (sweep / PI).ceil() as usize
+                } else {
+                    // the sagitta of each segment's arc is at most the tolerance
+                    let step = 2.0 * (1.0 - tolerance / radius).acos();
+                    (sweep / step).ceil().min(MAX_SEGMENTS as f64) as usize
+                }
+            }
+        };
+
+        count.clamp(1, MAX_SEGMENTS)
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+/// Returns the points of an elliptic arc through `center + u * cos(t) + v * sin(t)`, including both ends.
+fn elliptic_arc_points(
+    center: &Point,
+    u: &Vector,
+    v: &Vector,
+    start: f64,
+    sweep: f64,
+    segments: usize,
+) -> Vec<Point> {
+    let mut points = (0..=segments)
+        .map(|i| {
+            let (sin, cos) = (start + sweep * i as f64 / segments as f64).sin_cos();
+            Point::new(
+                center.x + u.x * cos + v.x * sin,
+                center.y + u.y * cos + v.y * sin,
+                center.z + u.z * cos + v.z * sin,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // close full turns exactly
+    if sweep.abs() >= 2.0 * PI - FULL_TURN_TOLERANCE {
+        points[segments] = points[0].clone();
+    }
+
+    points
+}
+
+fn ocs_arc_points(
+    normal: &Vector,
+    center: &Point,
+    radius: f64,
+    start: f64,
+    sweep: f64,
+    tessellation: Tessellation,
+) -> Vec<Point> {
+    let ocs = ObjectCoordinateSystem::new(normal);
+    elliptic_arc_points(
+        &ocs.to_wcs(center),
+        &ocs.x_axis().scaled(radius),
+        &ocs.y_axis().scaled(radius),
+        start,
+        sweep,
+        tessellation.arc_segments(radius, sweep),
+    )
+}
+
+fn ellipse_points(ellipse: &Ellipse, tessellation: Tessellation) -> Vec<Point> {
+    let minor_axis = ellipse
+        .normal
+        .normalized()
+        .cross(&ellipse.major_axis)
+        .scaled(ellipse.minor_axis_ratio);
+    let sweep = (ellipse.end_parameter - ellipse.start_parameter).rem_euclid(2.0 * PI);
+    let sweep = if sweep == 0.0 { 2.0 * PI } else { sweep };
+
+    // the ellipse is a circle of the major radius squeezed along the minor axis, which can only shrink the sagitta
+    elliptic_arc_points(
+        &ellipse.center,
+        &ellipse.major_axis,
+        &minor_axis,
+        ellipse.start_parameter,
+        sweep,
+        tessellation.arc_segments(ellipse.major_axis.length(), sweep),
+    )
+}
+
+/// Returns the points of a 2D polyline whose vertices are given in the object coordinate system `ocs` along with the
+/// bulge of the segment that starts at each one.
+fn bulge_polyline_points(
+    ocs: &ObjectCoordinateSystem,
+    vertices: &[(Point, f64)],
+    is_closed: bool,
+    tessellation: Tessellation,
+) -> Vec<Point> {
+    let mut points = vec![];
+    let segment_count = if is_closed {
+        vertices.len()
+    } else {
+        vertices.len().saturating_sub(1)
+    };
+    if let Some((first, _)) = vertices.first() {
+        points.push(ocs.to_wcs(first));
+    }
+    for i in 0..segment_count {
+        let (p1, bulge) = &vertices[i];
+        let (p2, _) = &vertices[(i + 1) % vertices.len()];
+        for p in bulge_segment_points(p1, p2, *bulge, tessellation)
+            .iter()
+            .skip(1)
+        {
+            points.push(ocs.to_wcs(p));
+        }
+    }
+
+    points
+}
+
+/// Returns the points of the segment from `p1` to `p2` in their own plane.  A non-zero `bulge` is the tangent of a
+/// quarter of the included angle of an arc that's counter-clockwise when positive.
+fn bulge_segment_points(
+    p1: &Point,
+    p2: &Point,
+    bulge: f64,
+    tessellation: Tessellation,
+) -> Vec<Point> {
+    let mut points = match LwPolylineSegment::from_bulge(p1, p2, bulge) {
+        LwPolylineSegment::Arc {
+            center,
+            radius,
+            start_angle,
+            ..
+        } => {
+            let sweep = 4.0 * bulge.atan();
+            elliptic_arc_points(
+                &Point::new(center.x, center.y, p1.z),
+                &Vector::x_axis().scaled(radius),
+                &Vector::y_axis().scaled(radius),
+                start_angle.to_radians(),
+                sweep,
+                tessellation.arc_segments(radius, sweep),
+            )
+        }
+        LwPolylineSegment::Line { .. } => return vec![p1.clone(), p2.clone()],
+    };
+
+    // land exactly on the next vertex
+    if let Some(last) = points.last_mut() {
+        *last = p2.clone();
+    }
+
+    points
+}
+
+fn polyline_points(poly: &Polyline, tessellation: Tessellation) -> Option<Vec<Point>> {
+    if poly.is_3d_polygon_mesh() || poly.is_polyface_mesh() {
+        return None;
+    }
+
+    let is_closed = poly.is_closed();
+    let close = |mut points: Vec<Point>| {
+        if is_closed && points.len() > 1 && points.first() != points.last() {
+            points.push(points[0].clone());
+        }
+        points
+    };
+    if poly.spline_fit_vertices_added() {
+        let fitted = poly
+            .vertices()
+            .filter(|v| v.is_spline_vertex_created_by_spline_fitting())
+            .map(|v| v.location.clone())
+            .collect::<Vec<_>>();
+        if !fitted.is_empty() {
+            return Some(close(polyline_wcs_points(poly, fitted)));
+        }
+
+        let frame = poly
+            .vertices()
+            .filter(|v| v.is_spline_frame_control_point())
+            .map(|v| v.location.clone())
+            .collect::<Vec<_>>();
+        let degree = match poly.surface_type {
+            PolylineCurvedAndSmoothSurfaceType::QuadraticBSpline => 2,
+            _ => 3,
+        };
+        let curve = if is_closed {
+            let weights = vec![1.0; frame.len()];
+            NurbsCurve::periodic(degree, frame.clone(), weights)
+        } else {
+            NurbsCurve::clamped(degree, frame.clone())
+        };
+        if let Some(curve) = curve {
+            let points = nurbs_points(&curve, tessellation);
+            return Some(polyline_wcs_points(poly, points));
+        }
+
+        return Some(close(polyline_wcs_points(poly, frame)));
+    }
+
+    let vertices = poly
+        .vertices()
+        .filter(|v| !v.is_spline_frame_control_point())
+        .collect::<Vec<_>>();
+    if poly.is_3d_polyline() {
+        return Some(close(vertices.iter().map(|v| v.location.clone()).collect()));
+    }
+
+    let vertices = vertices
+        .iter()
+        .map(|v| {
+            (
+                Point::new(v.location.x, v.location.y, poly.location.z),
+                v.bulge,
+            )
+        })
+        .collect::<Vec<_>>();
+    Some(bulge_polyline_points(
+        &ObjectCoordinateSystem::new(&poly.normal),
+        &vertices,
+        is_closed,
+        tessellation,
+    ))
+}
+
+/// Converts the points of a 2D `Polyline` from its object coordinate system; the points of a 3D one are unchanged.
+fn polyline_wcs_points(poly: &Polyline, points: Vec<Point>) -> Vec<Point> {
+    if poly.is_3d_polyline() {
+        return points;
+    }
+
+    let ocs = ObjectCoordinateSystem::new(&poly.normal);
+    points
+        .iter()
+        .map(|p| ocs.to_wcs(&Point::new(p.x, p.y, poly.location.z)))
+        .collect()
+}
+
+fn spline_points(spline: &Spline, tessellation: Tessellation) -> Vec<Point> {
+    match NurbsCurve::from_spline(spline) {
+        Some(curve) => nurbs_points(&curve, tessellation),
+        None => {
+            let mut points = spline.fit_points.clone();
+            if spline.is_closed() && points.len() > 1 && points.first() != points.last() {
+                points.push(points[0].clone());
+            }
+            points
+        }
+    }
+}
+
+fn nurbs_points(curve: &NurbsCurve, tessellation: Tessellation) -> Vec<Point> {
+    let (start, end) = curve.domain();
+    match tessellation {
+        Tessellation::SegmentCount(count) => {
+            let count = count.clamp(1, MAX_SEGMENTS);
+            (0..=count)
+                .map(|i| curve.point_at(start + (end - start) * i as f64 / count as f64))
+                .collect()
+        }
+        Tessellation::ChordalTolerance(tolerance) => {
+            let mut points = vec![curve.point_at(start)];
+            for span in curve.breakpoints().windows(2) {
+                // start from a few pieces per span so that an S-bend doesn't look straight from its midpoint
+                let pieces = curve.degree + 1;
+                let (a, b) = (span[0], span[1]);
+                for i in 0..pieces {
+                    let u1 = a + (b - a) * i as f64 / pieces as f64;
+                    let u2 = a + (b - a) * (i + 1) as f64 / pieces as f64;
+                    let p1 = points.last().cloned().unwrap_or_else(|| curve.point_at(u1));
+                    subdivide(curve, (u1, p1), u2, tolerance, 0, &mut points);
+                }
+            }
+
+            points
+        }
+    }
+}
+
+/// Adds the points after `start` up to and including the point at `end`, halving the parameter range until the curve's
+/// midpoint is within `tolerance` of the chord.
+fn subdivide(
+    curve: &NurbsCurve,
+    start: (f64, Point),
+    end: f64,
+    tolerance: f64,
+    depth: usize,
+    points: &mut Vec<Point>,
+) {
+    let (u1, p1) = start;
+    let p2 = curve.point_at(end);
+    let middle = (u1 + end) / 2.0;
+    let pm = curve.point_at(middle);
+    if depth < MAX_SUBDIVISION_DEPTH && distance_to_segment(&pm, &p1, &p2) > tolerance.max(0.0) {
+        subdivide(curve, (u1, p1), middle, tolerance, depth + 1, points);
+        subdivide(curve, (middle, pm), end, tolerance, depth + 1, points);
+    } else {
+        points.push(p2);
+    }
+}
+
+fn distance_to_segment(p: &Point, a: &Point, b: &Point) -> f64 {
+    let ab = Vector::new(b.x - a.x, b.y - a.y, b.z - a.z);
+    let ap = Vector::new(p.x - a.x, p.y - a.y, p.z - a.z);
+    let length_squared = ab.dot(&ab);
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (ap.dot(&ab) / length_squared).clamp(0.0, 1.0)
+    };
+    Vector::new(ap.x - ab.x * t, ap.y - ab.y * t, ap.z - ab.z * t).length()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::entities::*;
+    use crate::*;
+
+    fn assert_point(expected: (f64, f64, f64), actual: &Point) {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(
+            close(expected.0, actual.x)
+                && close(expected.1, actual.y)
+                && close(expected.2, actual.z),
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn tessellate(specific: EntityType, tessellation: Tessellation) -> Vec<Point> {
+        Entity::new(specific).tessellate(tessellation).unwrap()
+    }
+
+    #[test]
+    fn arc_segment_count() {
+        let points = tessellate(
+            EntityType::Arc(Arc::new(Point::new(1.0, 1.0, 0.0), 2.0, 0.0, 90.0)),
+            Tessellation::SegmentCount(4),
+        );
+        assert_eq!(5, points.len());
+        assert_point((3.0, 1.0, 0.0), &points[0]);
+        assert_point((1.0, 3.0, 0.0), &points[4]);
+    }
+
+    #[test]
+    fn arc_crossing_zero_degrees() {
+        let points = tessellate(
+            EntityType::Arc(Arc::new(Point::origin(), 1.0, 270.0, 90.0)),
+            Tessellation::SegmentCount(2),
+        );
+        assert_point((0.0, -1.0, 0.0), &points[0]);
+        assert_point((1.0, 0.0, 0.0), &points[1]);
+        assert_point((0.0, 1.0, 0.0), &points[2]);
+    }
+
+    #[test]
+    fn circle_chordal_tolerance() {
+        let tolerance = 0.01;
+        let points = tessellate(
+            EntityType::Circle(Circle::new(Point::origin(), 10.0)),
+            Tessellation::ChordalTolerance(tolerance),
+        );
+
+        // closed, and each chord's midpoint is within the tolerance of the circle
+        assert_eq!(points.first(), points.last());
+        for pair in points.windows(2) {
+            let (mx, my) = ((pair[0].x + pair[1].x) / 2.0, (pair[0].y + pair[1].y) / 2.0);
+            assert!(10.0 - (mx * mx + my * my).sqrt() <= tolerance);
+        }
+    }
+
+    #[test]
+    fn circle_with_flipped_normal() {
+        let mut circle = Circle::new(Point::new(1.0, 0.0, 2.0), 1.0);
+        circle.normal = Vector::new(0.0, 0.0, -1.0);
+
+        // the OCS of -Z has its X axis along -X
+        let points = tessellate(EntityType::Circle(circle), Tessellation::SegmentCount(4));
+        assert_point((-2.0, 0.0, -2.0), &points[0]);
+        assert_point((-1.0, 1.0, -2.0), &points[1]);
+    }
+
+    #[test]
+    fn elliptic_arc() {
+        let ellipse = Ellipse {
+            center: Point::new(1.0, 0.0, 0.0),
+            major_axis: Vector::new(0.0, 2.0, 0.0),
+            minor_axis_ratio: 0.5,
+            start_parameter: 0.0,
+            end_parameter: PI,
+            ..Default::default()
+        };
+        let points = tessellate(EntityType::Ellipse(ellipse), Tessellation::SegmentCount(2));
+        assert_point((1.0, 2.0, 0.0), &points[0]);
+        assert_point((0.0, 0.0, 0.0), &points[1]);
+        assert_point((1.0, -2.0, 0.0), &points[2]);
+    }
+
+    #[test]
+    fn lw_polyline_with_bulge() {
+        let mut poly = LwPolyline::default();
+        poly.vertices.push(LwPolylineVertex {
+            x: 0.0,
+            y: 0.0,
+            bulge: 1.0,
+            ..Default::default()
+        });
+        poly.vertices.push(LwPolylineVertex {
+            x: 2.0,
+            y: 0.0,
+            ..Default::default()
+        });
+        poly.set_is_closed(true);
+        let mut entity = Entity::new(EntityType::LwPolyline(poly));
+        entity.common.elevation = 3.0;
+
+        // a bulge of 1 is a counter-clockwise half circle, which passes below the chord
+        let points = entity.tessellate(Tessellation::SegmentCount(2)).unwrap();
+        assert_eq!(4, points.len());
+        assert_point((0.0, 0.0, 3.0), &points[0]);
+        assert_point((1.0, -1.0, 3.0), &points[1]);
+        assert_point((2.0, 0.0, 3.0), &points[2]);
+        assert_point((0.0, 0.0, 3.0), &points[3]);
+    }
+
+    #[test]
+    fn spline_within_tolerance() {
+        let spline = Spline {
+            degree_of_curve: 2,
+            knot_values: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            weight_values: vec![1.0, 0.5f64.sqrt(), 1.0],
+            control_points: vec![
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            ..Default::default()
+        };
+        let tolerance = 0.001;
+        let points = tessellate(
+            EntityType::Spline(spline),
+            Tessellation::ChordalTolerance(tolerance),
+        );
+        assert_point((1.0, 0.0, 0.0), points.first().unwrap());
+        assert_point((0.0, 1.0, 0.0), points.last().unwrap());
+        for pair in points.windows(2) {
+            let (mx, my) = ((pair[0].x + pair[1].x) / 2.0, (pair[0].y + pair[1].y) / 2.0);
+            assert!(1.0 - (mx * mx + my * my).sqrt() <= tolerance);
+        }
+    }
+
+    #[test]
+    fn spline_with_only_fit_points() {
+        let spline = Spline {
+            fit_points: vec![Point::new(0.0, 0.0, 0.0), Point::new(1.0, 1.0, 0.0)],
+            ..Default::default()
+        };
+        let points = tessellate(EntityType::Spline(spline), Tessellation::SegmentCount(8));
+        assert_eq!(9, points.len());
+        assert_point((0.5, 0.5, 0.0), &points[4]);
+        assert_point((1.0, 1.0, 0.0), &points[8]);
+    }
+
+    #[test]
+    fn spline_fit_polyline_uses_generated_vertices() {
+        let mut drawing = Drawing::new();
+        let mut poly = Polyline::default();
+        poly.set_is_3d_polyline(true);
+        poly.set_spline_fit_vertices_added(true);
+        for (x, flags) in [(0.0, 16), (5.0, 8), (10.0, 16), (6.0, 8)] {
+            poly.add_vertex(
+                &mut drawing,
+                Vertex {
+                    location: Point::new(x, 0.0, 0.0),
+                    flags,
+                    ..Default::default()
+                },
+            );
+        }
+        let points = tessellate(EntityType::Polyline(poly), Tessellation::SegmentCount(8));
+        assert_eq!(2, points.len());
+        assert_point((5.0, 0.0, 0.0), &points[0]);
+        assert_point((6.0, 0.0, 0.0), &points[1]);
+    }
+
+    #[test]
+    fn spline_fit_polyline_from_frame() {
+        let mut drawing = Drawing::new();
+        let mut poly = Polyline::default();
+        poly.set_spline_fit_vertices_added(true);
+        poly.surface_type = enums::PolylineCurvedAndSmoothSurfaceType::QuadraticBSpline;
+        for (x, y) in [(0.0, 0.0), (1.0, 2.0), (2.0, 0.0)] {
+            poly.add_vertex(
+                &mut drawing,
+                Vertex {
+                    location: Point::new(x, y, 0.0),
+                    flags: 16,
+                    ..Default::default()
+                },
+            );
+        }
+        let points = tessellate(EntityType::Polyline(poly), Tessellation::SegmentCount(2));
+        assert_eq!(3, points.len());
+        assert_point((1.0, 1.0, 0.0), &points[1]);
+    }
+
+    #[test]
+    fn non_curves_are_not_tessellated() {
+        assert!(Entity::new(EntityType::Text(Text::default()))
+            .tessellate(Tessellation::SegmentCount(4))
+            .is_none());
+    }
+}
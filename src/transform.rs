@@ -0,0 +1,1324 @@
+// other implementation is in `entity.rs`
+
+use std::f64::consts::PI;
+
+use crate::entities::*;
+use crate::enums::AttachmentPoint;
+use crate::helper_functions::normalize_degrees;
+use crate::{
+    HatchBoundaryPath, HatchEdge, ObjectCoordinateSystem, Point, TransformationMatrix, Vector,
+};
+
+/// Relative tolerance used to decide whether a transformation preserves circles.
+const SIMILARITY_TOLERANCE: f64 = 1e-9;
+
+//------------------------------------------------------------------------------
+//                                                                        Entity
+//------------------------------------------------------------------------------
+impl Entity {
+    /// Applies the transformation to every geometric value of the entity.
+    ///
+    /// Entities defined in an object coordinate system get a new normal and have their points, angles, and sizes
+    /// re-expressed in it.  A mirroring transformation keeps the normal on the same side of the plane, so arc angles
+    /// are swapped, polyline bulges are negated, `Text` is flagged as upside down, and `Insert` gets a negative Y
+    /// scale factor.  A `Circle` or `Arc` that is scaled non-uniformly becomes an `Ellipse`.  Text-like entities and
+    /// `Insert` can't represent shearing, so their sizes follow the scaling along their own axes.  `MText` can't be
+    /// mirrored and is kept readable instead.
+    ///
+    /// The geometry of entities stored as opaque data (e.g., `Solid3D`, `Body`, `Region`, and `ProxyEntity`) isn't
+    /// changed, and neither are the anonymous blocks of dimensions, which are expected to be regenerated.
+    pub fn transform(&mut self, matrix: &TransformationMatrix) {
+        if let Some(ellipse) = self.as_ellipse_if_distorted(matrix) {
+            self.specific = EntityType::Ellipse(ellipse);
+        }
+
+        let elevation = self.common.elevation;
+        match self.specific {
+            EntityType::Face3D(ref mut face) => {
+                transform_points(
+                    matrix,
+                    [
+                        &mut face.first_corner,
+                        &mut face.second_corner,
+                        &mut face.third_corner,
+                        &mut face.fourth_corner,
+                    ],
+                );
+            }
+            EntityType::Arc(ref mut arc) => {
+                let plane = PlaneTransform::new(matrix, &arc.normal);
+                arc.center = plane.point(&arc.center);
+                arc.radius *= plane.scale();
+                let (start, end) = plane.arc_angles(arc.start_angle, arc.end_angle);
+                arc.start_angle = start;
+                arc.end_angle = end;
+                arc.thickness = plane.thickness(arc.thickness);
+                arc.normal = plane.normal();
+            }
+            EntityType::ArcAlignedText(ref mut text) => {
+                let plane = PlaneTransform::new(matrix, &text.extrusion_direction);
+                text.center_point = plane.point(&text.center_point);
+                text.arc_radius *= plane.scale();
+                text.offset_from_arc *= plane.scale();
+                text.text_height *= plane.scale();
+                let (start, end) = plane.arc_angles(text.start_angle, text.end_angle);
+                text.start_angle = start;
+                text.end_angle = end;
+                text.extrusion_direction = plane.normal();
+            }
+            EntityType::AttributeDefinition(ref mut att) => {
+                transform_text(
+                    matrix,
+                    TextFields {
+                        location: &mut att.location,
+                        second_alignment_point: &mut att.second_alignment_point,
+                        normal: &mut att.normal,
+                        height: &mut att.text_height,
+                        rotation: &mut att.rotation,
+                        x_scale: &mut att.relative_x_scale_factor,
+                        oblique_angle: &mut att.oblique_angle,
+                        thickness: &mut att.thickness,
+                        text_generation_flags: &mut att.text_generation_flags,
+                    },
+                );
+            }
+            EntityType::Attribute(ref mut att) => {
+                transform_attribute(matrix, att);
+            }
+            EntityType::Circle(ref mut circle) => {
+                let plane = PlaneTransform::new(matrix, &circle.normal);
+                circle.center = plane.point(&circle.center);
+                circle.radius *= plane.scale();
+                circle.thickness = plane.thickness(circle.thickness);
+                circle.normal = plane.normal();
+            }
+            EntityType::RotatedDimension(ref mut dim) => {
+                let plane = transform_dimension_base(matrix, &mut dim.dimension_base);
+                dim.insertion_point = plane.point(&dim.insertion_point);
+                transform_points(
+                    matrix,
+                    [&mut dim.definition_point_2, &mut dim.definition_point_3],
+                );
+                dim.rotation_angle = plane.angle_degrees(dim.rotation_angle);
+                dim.extension_line_angle = plane.angle_degrees(dim.extension_line_angle);
+            }
+            EntityType::RadialDimension(ref mut dim) => {
+                let plane = transform_dimension_base(matrix, &mut dim.dimension_base);
+                dim.definition_point_2 = matrix.transform_point(&dim.definition_point_2);
+                dim.leader_length *= plane.scale();
+            }
+            EntityType::DiameterDimension(ref mut dim) => {
+                let plane = transform_dimension_base(matrix, &mut dim.dimension_base);
+                dim.definition_point_2 = matrix.transform_point(&dim.definition_point_2);
+                dim.leader_length *= plane.scale();
+            }
+            EntityType::AngularThreePointDimension(ref mut dim) => {
+                transform_dimension_base(matrix, &mut dim.dimension_base);
+                transform_points(
+                    matrix,
+                    [
+                        &mut dim.definition_point_2,
+                        &mut dim.definition_point_3,
+                        &mut dim.definition_point_4,
+                        &mut dim.definition_point_5,
+                    ],
+                );
+            }
+            EntityType::OrdinateDimension(ref mut dim) => {
+                transform_dimension_base(matrix, &mut dim.dimension_base);
+                transform_points(
+                    matrix,
+                    [&mut dim.definition_point_2, &mut dim.definition_point_3],
+                );
+            }
+            EntityType::ArcDimension(ref mut dim) => {
+                let plane = transform_dimension_base(matrix, &mut dim.dimension_base);
+                transform_points(
+                    matrix,
+                    [
+                        &mut dim.definition_point_2,
+                        &mut dim.definition_point_3,
+                        &mut dim.arc_center,
+                        &mut dim.leader_point_1,
+                        &mut dim.leader_point_2,
+                    ],
+                );
+                // unlike the arc entity, these angles are stored in radians
+                let (start, end) = plane.arc_angles(
+                    dim.arc_start_angle.to_degrees(),
+                    dim.arc_end_angle.to_degrees(),
+                );
+                dim.arc_start_angle = start.to_radians();
+                dim.arc_end_angle = end.to_radians();
+            }
+            EntityType::JoggedRadialDimension(ref mut dim) => {
+                // the jog angle is relative to the dimension line, so it's unaffected
+                transform_dimension_base(matrix, &mut dim.dimension_base);
+                transform_points(
+                    matrix,
+                    [
+                        &mut dim.definition_point_2,
+                        &mut dim.override_center,
+                        &mut dim.jog_point,
+                    ],
+                );
+            }
+            EntityType::Ellipse(ref mut ellipse) => {
+                transform_ellipse(matrix, ellipse);
+            }
+            EntityType::Hatch(ref mut hatch) => {
+                transform_hatch(matrix, hatch);
+            }
+            EntityType::Helix(ref mut helix) => {
+                let plane = PlaneTransform::new(matrix, &helix.axis_vector);
+                transform_points(matrix, [&mut helix.axis_base_point, &mut helix.start_point]);
+                helix.radius *= plane.scale();
+                helix.turn_height = plane.thickness(helix.turn_height);
+                helix.axis_vector = matrix.transform_vector(&helix.axis_vector);
+                if plane.is_mirrored {
+                    helix.is_right_handed = !helix.is_right_handed;
+                }
+            }
+            EntityType::Image(ref mut image) => {
+                image.location = matrix.transform_point(&image.location);
+                image.u_vector = matrix.transform_vector(&image.u_vector);
+                image.v_vector = matrix.transform_vector(&image.v_vector);
+            }
+            EntityType::Insert(ref mut insert) => {
+                transform_insert(matrix, insert);
+            }
+            EntityType::Leader(ref mut leader) => {
+                let plane = PlaneTransform::new(matrix, &leader.normal);
+                transform_points(matrix, leader.vertices.iter_mut());
+                leader.right = matrix.transform_vector(&leader.right).normalized();
+                leader.block_offset = matrix.transform_vector(&leader.block_offset);
+                leader.annotation_offset = matrix.transform_vector(&leader.annotation_offset);
+                leader.text_annotation_height *= plane.scale();
+                leader.text_annotation_width *= plane.scale();
+                leader.normal = plane.normal();
+            }
+            EntityType::Line(ref mut line) => {
+                let plane = PlaneTransform::new(matrix, &line.extrusion_direction);
+                transform_points(matrix, [&mut line.p1, &mut line.p2]);
+                line.thickness = plane.thickness(line.thickness);
+                line.extrusion_direction = plane.normal();
+            }
+            EntityType::LwPolyline(ref mut poly) => {
+                let plane = PlaneTransform::new(matrix, &poly.extrusion_direction);
+                for v in poly.vertices.iter_mut() {
+                    let p = plane.point(&Point::new(v.x, v.y, elevation));
+                    v.x = p.x;
+                    v.y = p.y;
+                    v.starting_width *= plane.scale();
+                    v.ending_width *= plane.scale();
+                    v.bulge = plane.bulge(v.bulge);
+                }
+                poly.constant_width *= plane.scale();
+                poly.thickness = plane.thickness(poly.thickness);
+                poly.extrusion_direction = plane.normal();
+                self.common.elevation = plane.elevation(elevation);
+            }
+            EntityType::Mesh(ref mut mesh) => {
+                transform_points(matrix, mesh.vertices.iter_mut());
+            }
+            EntityType::MLine(ref mut mline) => {
+                let plane = PlaneTransform::new(matrix, &mline.normal);
+                mline.start_point = matrix.transform_point(&mline.start_point);
+                transform_points(matrix, mline.vertices.iter_mut());
+                for v in mline
+                    .segment_directions
+                    .iter_mut()
+                    .chain(mline.miter_directions.iter_mut())
+                {
+                    *v = matrix.transform_vector(v).normalized();
+                }
+                mline.scale_factor *= plane.scale();
+                mline.normal = plane.normal();
+            }
+            EntityType::MLeader(ref mut mleader) => {
+                let plane = PlaneTransform::new(matrix, &mleader.text_normal_direction);
+                transform_points(
+                    matrix,
+                    [
+                        &mut mleader.content_base_point,
+                        &mut mleader.text_location,
+                        &mut mleader.vertex,
+                        &mut mleader.last_leader_line_point,
+                        &mut mleader.break_start_point,
+                        &mut mleader.break_end_point,
+                        &mut mleader.break_start_point_line,
+                        &mut mleader.break_end_point_line,
+                    ],
+                );
+                transform_points(matrix, mleader.vertices.iter_mut());
+                mleader.text_direction = matrix
+                    .transform_vector(&mleader.text_direction)
+                    .normalized();
+                mleader.dogleg_vector =
+                    matrix.transform_vector(&mleader.dogleg_vector).normalized();
+                let scale = plane.scale();
+                mleader.dogleg_length *= scale;
+                mleader.dogleg_length_leader *= scale;
+                mleader.arrowhead_size *= scale;
+                mleader.arrow_head_size *= scale;
+                mleader.landing_gap *= scale;
+                mleader.content_scale *= scale;
+                mleader.text_height *= scale;
+                mleader.text_height_context *= scale;
+                mleader.text_width *= scale;
+                mleader.text_column_width *= scale;
+                mleader.text_column_gutter_width *= scale;
+                mleader.text_column_height *= scale;
+                mleader.text_normal_direction = plane.normal();
+            }
+            EntityType::MText(ref mut mtext) => {
+                transform_mtext(matrix, mtext);
+            }
+            EntityType::ModelPoint(ref mut point) => {
+                let plane = PlaneTransform::new(matrix, &point.extrusion_direction);
+                point.location = matrix.transform_point(&point.location);
+                point.angle = plane.angle_degrees(point.angle);
+                point.thickness = plane.thickness(point.thickness);
+                point.extrusion_direction = plane.normal();
+            }
+            EntityType::Polyline(ref mut poly) => {
+                transform_polyline(matrix, poly);
+            }
+            EntityType::Ray(ref mut ray) => {
+                ray.start_point = matrix.transform_point(&ray.start_point);
+                ray.unit_direction_vector = matrix
+                    .transform_vector(&ray.unit_direction_vector)
+                    .normalized();
+            }
+            EntityType::RText(ref mut text) => {
+                let plane = PlaneTransform::new(matrix, &text.extrusion_direction);
+                text.insertion_point = matrix.transform_point(&text.insertion_point);
+                text.rotation_angle = plane.angle_degrees(text.rotation_angle);
+                text.text_height *= plane.scale();
+                text.extrusion_direction = plane.normal();
+            }
+            EntityType::Section(ref mut section) => {
+                transform_points(
+                    matrix,
+                    section
+                        .vertices
+                        .iter_mut()
+                        .chain(section.back_line_vertices.iter_mut()),
+                );
+                let vertical = matrix.transform_vector(&section.vertical_direction);
+                section.top_height *= vertical.length();
+                section.bottom_height *= vertical.length();
+                section.vertical_direction = vertical.normalized();
+            }
+            EntityType::Shape(ref mut shape) => {
+                let plane = PlaneTransform::new(matrix, &shape.extrusion_direction);
+                let rotation = shape.rotation_angle.to_radians();
+                let (width_scale, height_scale) = plane.axis_scales(rotation);
+                shape.location = plane.point(&shape.location);
+                shape.rotation_angle = plane.angle(rotation).to_degrees();
+                shape.size *= height_scale;
+                shape.relative_x_scale_factor *= width_scale / height_scale;
+                if plane.is_mirrored {
+                    shape.oblique_angle = -shape.oblique_angle;
+                }
+                shape.thickness = plane.thickness(shape.thickness);
+                shape.extrusion_direction = plane.normal();
+            }
+            EntityType::Solid(ref mut solid) => {
+                let plane = PlaneTransform::new(matrix, &solid.extrusion_direction);
+                for p in [
+                    &mut solid.first_corner,
+                    &mut solid.second_corner,
+                    &mut solid.third_corner,
+                    &mut solid.fourth_corner,
+                ] {
+                    *p = plane.point(p);
+                }
+                solid.thickness = plane.thickness(solid.thickness);
+                solid.extrusion_direction = plane.normal();
+            }
+            EntityType::Spline(ref mut spline) => {
+                let plane = PlaneTransform::new(matrix, &spline.normal);
+                transform_points(
+                    matrix,
+                    spline
+                        .control_points
+                        .iter_mut()
+                        .chain(spline.fit_points.iter_mut()),
+                );
+                for tangent in [&mut spline.start_tangent, &mut spline.end_tangent] {
+                    let v = matrix.transform_vector(&Vector::new(tangent.x, tangent.y, tangent.z));
+                    *tangent = Point::new(v.x, v.y, v.z);
+                }
+                spline.normal = plane.normal();
+            }
+            EntityType::Table(ref mut table) => {
+                let right = table.horizontal_direction.normalized();
+                let vertical = Vector::z_axis().cross(&right);
+                let width_scale = matrix.transform_vector(&right).length();
+                let height_scale = matrix.transform_vector(&vertical).length();
+                table.insertion_point = matrix.transform_point(&table.insertion_point);
+                table.horizontal_direction = matrix.transform_vector(&right).normalized();
+                for width in &mut table.column_widths {
+                    *width *= width_scale;
+                }
+                for height in &mut table.row_heights {
+                    *height *= height_scale;
+                }
+            }
+            EntityType::Text(ref mut text) => {
+                transform_text(
+                    matrix,
+                    TextFields {
+                        location: &mut text.location,
+                        second_alignment_point: &mut text.second_alignment_point,
+                        normal: &mut text.normal,
+                        height: &mut text.text_height,
+                        rotation: &mut text.rotation,
+                        x_scale: &mut text.relative_x_scale_factor,
+                        oblique_angle: &mut text.oblique_angle,
+                        thickness: &mut text.thickness,
+                        text_generation_flags: &mut text.text_generation_flags,
+                    },
+                );
+            }
+            EntityType::Tolerance(ref mut tolerance) => {
+                let plane = PlaneTransform::new(matrix, &tolerance.extrusion_direction);
+                tolerance.insertion_point = matrix.transform_point(&tolerance.insertion_point);
+                tolerance.direction_vector = matrix.transform_vector(&tolerance.direction_vector);
+                tolerance.extrusion_direction = plane.normal();
+            }
+            EntityType::Trace(ref mut trace) => {
+                let plane = PlaneTransform::new(matrix, &trace.extrusion_direction);
+                for p in [
+                    &mut trace.first_corner,
+                    &mut trace.second_corner,
+                    &mut trace.third_corner,
+                    &mut trace.fourth_corner,
+                ] {
+                    *p = plane.point(p);
+                }
+                trace.thickness = plane.thickness(trace.thickness);
+                trace.extrusion_direction = plane.normal();
+            }
+            EntityType::DgnUnderlay(ref mut underlay) => transform_underlay(
+                matrix,
+                UnderlayFields {
+                    insertion_point: &mut underlay.insertion_point,
+                    x_scale: &mut underlay.x_scale,
+                    y_scale: &mut underlay.y_scale,
+                    z_scale: &mut underlay.z_scale,
+                    rotation_angle: &mut underlay.rotation_angle,
+                    normal: &mut underlay.normal,
+                },
+            ),
+            EntityType::DwfUnderlay(ref mut underlay) => transform_underlay(
+                matrix,
+                UnderlayFields {
+                    insertion_point: &mut underlay.insertion_point,
+                    x_scale: &mut underlay.x_scale,
+                    y_scale: &mut underlay.y_scale,
+                    z_scale: &mut underlay.z_scale,
+                    rotation_angle: &mut underlay.rotation_angle,
+                    normal: &mut underlay.normal,
+                },
+            ),
+            EntityType::PdfUnderlay(ref mut underlay) => transform_underlay(
+                matrix,
+                UnderlayFields {
+                    insertion_point: &mut underlay.insertion_point,
+                    x_scale: &mut underlay.x_scale,
+                    y_scale: &mut underlay.y_scale,
+                    z_scale: &mut underlay.z_scale,
+                    rotation_angle: &mut underlay.rotation_angle,
+                    normal: &mut underlay.normal,
+                },
+            ),
+            EntityType::Vertex(ref mut vertex) => {
+                vertex.location = matrix.transform_point(&vertex.location);
+            }
+            EntityType::Viewport(ref mut viewport) => {
+                // the viewport frame stays axis-aligned in paper space; only its placement and size follow the matrix
+                viewport.center = matrix.transform_point(&viewport.center);
+                viewport.width *= matrix.transform_vector(&Vector::x_axis()).length();
+                viewport.height *= matrix.transform_vector(&Vector::y_axis()).length();
+            }
+            EntityType::Wipeout(ref mut wipeout) => {
+                wipeout.location = matrix.transform_point(&wipeout.location);
+                wipeout.u_vector = matrix.transform_vector(&wipeout.u_vector);
+                wipeout.v_vector = matrix.transform_vector(&wipeout.v_vector);
+            }
+            EntityType::XLine(ref mut xline) => {
+                xline.first_point = matrix.transform_point(&xline.first_point);
+                xline.unit_direction_vector = matrix
+                    .transform_vector(&xline.unit_direction_vector)
+                    .normalized();
+            }
+            EntityType::Light(ref mut light) => {
+                transform_points(matrix, [&mut light.position, &mut light.target_location]);
+            }
+            EntityType::Ole2Frame(ref mut frame) => {
+                transform_points(
+                    matrix,
+                    [&mut frame.upper_left_corner, &mut frame.lower_right_corner],
+                );
+            }
+            EntityType::Solid3D(_)
+            | EntityType::ProxyEntity(_)
+            | EntityType::Body(_)
+            | EntityType::ExtrudedSurface(_)
+            | EntityType::LoftedSurface(_)
+            | EntityType::NurbSurface(_)
+            | EntityType::PlaneSurface(_)
+            | EntityType::RevolvedSurface(_)
+            | EntityType::SweptSurface(_)
+            | EntityType::OleFrame(_)
+            | EntityType::Region(_)
+            | EntityType::Seqend(_)
+            | EntityType::Unknown(_) => (), // opaque or no geometry
+        }
+    }
+}
+
+// private implementation
+impl Entity {
+    /// Returns the equivalent `Ellipse` if the entity is a `Circle` or `Arc` that the transformation wouldn't keep
+    /// circular.
+    fn as_ellipse_if_distorted(&self, matrix: &TransformationMatrix) -> Option<Ellipse> {
+        let (center, radius, normal, start, end) = match self.specific {
+            EntityType::Circle(ref circle) => {
+                (&circle.center, circle.radius, &circle.normal, 0.0, 2.0 * PI)
+            }
+            EntityType::Arc(ref arc) => (
+                &arc.center,
+                arc.radius,
+                &arc.normal,
+                arc.start_angle.to_radians(),
+                arc.end_angle.to_radians(),
+            ),
+            _ => return None,
+        };
+        if PlaneTransform::new(matrix, normal).is_similarity() {
+            return None;
+        }
+
+        let ocs = ObjectCoordinateSystem::new(normal);
+        Some(Ellipse {
+            center: ocs.to_wcs(center),
+            major_axis: ocs.x_axis().scaled(radius),
+            normal: ocs.normal().clone(),
+            minor_axis_ratio: 1.0,
+            start_parameter: start,
+            end_parameter: end,
+        })
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                PlaneTransform
+//------------------------------------------------------------------------------
+/// Maps values given in an entity's object coordinate system to the object coordinate system of the transformed
+/// entity.  When the transformation mirrors, the new normal is kept on the same side of the plane, so the mapping
+/// within the plane is a reflection.
+struct PlaneTransform<'a> {
+    matrix: &'a TransformationMatrix,
+    old_ocs: ObjectCoordinateSystem,
+    new_ocs: ObjectCoordinateSystem,
+    is_mirrored: bool,
+}
+
+impl<'a> PlaneTransform<'a> {
+    fn new(matrix: &'a TransformationMatrix, normal: &Vector) -> Self {
+        let old_ocs = ObjectCoordinateSystem::new(normal);
+        let is_mirrored = matrix.linear_determinant() < 0.0;
+        let mut new_normal = matrix
+            .transform_vector(old_ocs.x_axis())
+            .cross(&matrix.transform_vector(old_ocs.y_axis()));
+        if new_normal.length() == 0.0 {
+            // the plane collapsed to a line; keep whatever direction the normal maps to
+            new_normal = matrix.transform_vector(old_ocs.normal());
+        } else if is_mirrored {
+            new_normal = new_normal.scaled(-1.0);
+        }
+
+        PlaneTransform {
+            matrix,
+            old_ocs,
+            new_ocs: ObjectCoordinateSystem::new(&new_normal),
+            is_mirrored,
+        }
+    }
+    fn normal(&self) -> Vector {
+        self.new_ocs.normal().clone()
+    }
+    fn point(&self, p: &Point) -> Point {
+        let wcs = self.old_ocs.to_wcs(p);
+        self.new_ocs.from_wcs(&self.matrix.transform_point(&wcs))
+    }
+    fn vector(&self, v: &Vector) -> Vector {
+        let wcs = self.old_ocs.vector_to_wcs(v);
+        self.new_ocs
+            .vector_from_wcs(&self.matrix.transform_vector(&wcs))
+    }
+    /// Returns the new Z value of points in the plane at the specified elevation.
+    fn elevation(&self, elevation: f64) -> f64 {
+        self.point(&Point::new(0.0, 0.0, elevation)).z
+    }
+    fn direction(angle: f64) -> Vector {
+        Vector::new(angle.cos(), angle.sin(), 0.0)
+    }
+    /// Maps the direction given by an angle in radians.
+    fn angle(&self, angle: f64) -> f64 {
+        let v = self.vector(&PlaneTransform::direction(angle));
+        v.y.atan2(v.x)
+    }
+    fn angle_degrees(&self, angle: f64) -> f64 {
+        normalize_degrees(self.angle(angle.to_radians()).to_degrees())
+    }
+    /// Maps the start and end angles, in degrees, of a counter-clockwise arc.
+    fn arc_angles(&self, start: f64, end: f64) -> (f64, f64) {
+        let (start, end) = (self.angle_degrees(start), self.angle_degrees(end));
+        if self.is_mirrored {
+            (end, start)
+        } else {
+            (start, end)
+        }
+    }
+    fn bulge(&self, bulge: f64) -> f64 {
+        if self.is_mirrored {
+            -bulge
+        } else {
+            bulge
+        }
+    }
+    /// Returns the scale of lengths in the plane, averaged over all directions when the scaling isn't uniform.
+    fn scale(&self) -> f64 {
+        let x = self.vector(&Vector::x_axis());
+        let y = self.vector(&Vector::y_axis());
+        (x.x * y.y - x.y * y.x).abs().sqrt()
+    }
+    /// Returns how much lengths along, and perpendicular to, the direction given by `angle` are scaled.
+    fn axis_scales(&self, angle: f64) -> (f64, f64) {
+        let along = self.vector(&PlaneTransform::direction(angle));
+        let across = self.vector(&PlaneTransform::direction(angle + PI / 2.0));
+        let along_length = (along.x * along.x + along.y * along.y).sqrt();
+        let area = (along.x * across.y - along.y * across.x).abs();
+        if along_length == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (along_length, area / along_length)
+        }
+    }
+    /// Maps a distance along the normal, e.g., a thickness.
+    fn thickness(&self, thickness: f64) -> f64 {
+        self.vector(&Vector::z_axis()).z * thickness
+    }
+    fn is_similarity(&self) -> bool {
+        let x = self.vector(&Vector::x_axis());
+        let y = self.vector(&Vector::y_axis());
+        let (x_length, y_length) = (x.length(), y.length());
+        let tolerance = SIMILARITY_TOLERANCE * x_length.max(y_length);
+        (x_length - y_length).abs() <= tolerance
+            && x.dot(&y).abs() <= tolerance * x_length.max(y_length)
+    }
+}
+
+//------------------------------------------------------------------------------
+//                                                                       helpers
+//------------------------------------------------------------------------------
+fn normalize_radians(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(2.0 * PI);
+    if angle >= 2.0 * PI {
+        0.0
+    } else {
+        angle
+    }
+}
+
+fn transform_points<'a, I>(matrix: &TransformationMatrix, points: I)
+where
+    I: IntoIterator<Item = &'a mut Point>,
+{
+    for p in points {
+        *p = matrix.transform_point(p);
+    }
+}
+
+/// The values shared by `Text`, `Attribute`, and `AttributeDefinition` that describe where the text is drawn.
+struct TextFields<'a> {
+    location: &'a mut Point,
+    second_alignment_point: &'a mut Point,
+    normal: &'a mut Vector,
+    height: &'a mut f64,
+    rotation: &'a mut f64,
+    x_scale: &'a mut f64,
+    oblique_angle: &'a mut f64,
+    thickness: &'a mut f64,
+    text_generation_flags: &'a mut i32,
+}
+
+fn transform_text(matrix: &TransformationMatrix, text: TextFields) {
+    const UPSIDE_DOWN: i32 = 4;
+    let plane = PlaneTransform::new(matrix, text.normal);
+    let rotation = text.rotation.to_radians();
+    let (width_scale, height_scale) = plane.axis_scales(rotation);
+    *text.location = plane.point(text.location);
+    *text.second_alignment_point = plane.point(text.second_alignment_point);
+    *text.rotation = normalize_degrees(plane.angle(rotation).to_degrees());
+    *text.height *= height_scale;
+    if height_scale != 0.0 {
+        *text.x_scale *= width_scale / height_scale;
+    }
+    if plane.is_mirrored {
+        // the baseline still runs along the mapped direction, but the glyphs are flipped across it
+        *text.text_generation_flags ^= UPSIDE_DOWN;
+        *text.oblique_angle = -*text.oblique_angle;
+    }
+    *text.thickness = plane.thickness(*text.thickness);
+    *text.normal = plane.normal();
+}
+
+fn transform_attribute(matrix: &TransformationMatrix, att: &mut Attribute) {
+    transform_text(
+        matrix,
+        TextFields {
+            location: &mut att.location,
+            second_alignment_point: &mut att.second_alignment_point,
+            normal: &mut att.normal,
+            height: &mut att.text_height,
+            rotation: &mut att.rotation,
+            x_scale: &mut att.relative_x_scale_factor,
+            oblique_angle: &mut att.oblique_angle,
+            thickness: &mut att.thickness,
+            text_generation_flags: &mut att.text_generation_flags,
+        },
+    );
+}
+
+fn transform_mtext(matrix: &TransformationMatrix, mtext: &mut MText) {
+    let plane = PlaneTransform::new(matrix, &mtext.extrusion_direction);
+
+    // the x-axis direction takes precedence over the rotation angle, which is in radians
+    let ocs = ObjectCoordinateSystem::new(&mtext.extrusion_direction);
+    let x_direction = if mtext.rotation_angle != 0.0 && mtext.x_axis_direction == Vector::x_axis() {
+        let (sin, cos) = mtext.rotation_angle.sin_cos();
+        ocs.vector_to_wcs(&Vector::new(cos, sin, 0.0))
+    } else {
+        mtext.x_axis_direction.clone()
+    };
+    let ocs_x_direction = ocs.vector_from_wcs(&x_direction);
+    let rotation = ocs_x_direction.y.atan2(ocs_x_direction.x);
+    let (width_scale, height_scale) = plane.axis_scales(rotation);
+    let mut new_rotation = plane.angle(rotation);
+    if plane.is_mirrored {
+        // keep the text readable by running it the other way from the mirrored attachment point
+        new_rotation += PI;
+        mtext.attachment_point = match mtext.attachment_point {
+            AttachmentPoint::TopLeft => AttachmentPoint::TopRight,
+            AttachmentPoint::TopRight => AttachmentPoint::TopLeft,
+            AttachmentPoint::MiddleLeft => AttachmentPoint::MiddleRight,
+            AttachmentPoint::MiddleRight => AttachmentPoint::MiddleLeft,
+            AttachmentPoint::BottomLeft => AttachmentPoint::BottomRight,
+            AttachmentPoint::BottomRight => AttachmentPoint::BottomLeft,
+            other => other,
+        };
+    }
+
+    mtext.insertion_point = matrix.transform_point(&mtext.insertion_point);
+    mtext.x_axis_direction = plane
+        .new_ocs
+        .vector_to_wcs(&PlaneTransform::direction(new_rotation));
+    mtext.rotation_angle = normalize_radians(new_rotation);
+    mtext.initial_text_height *= height_scale;
+    mtext.reference_rectangle_width *= width_scale;
+    mtext.horizontal_width *= width_scale;
+    mtext.vertical_height *= height_scale;
+    mtext.column_width *= width_scale;
+    mtext.column_gutter *= width_scale;
+    for height in mtext.column_heights.iter_mut() {
+        *height *= height_scale;
+    }
+    mtext.extrusion_direction = plane.normal();
+}
+
+fn transform_ellipse(matrix: &TransformationMatrix, ellipse: &mut Ellipse) {
+    let plane = PlaneTransform::new(matrix, &ellipse.normal);
+    let minor_axis = ellipse
+        .normal
+        .normalized()
+        .cross(&ellipse.major_axis)
+        .scaled(ellipse.minor_axis_ratio);
+    let u = matrix.transform_vector(&ellipse.major_axis);
+    let v = matrix.transform_vector(&minor_axis);
+
+    // `u` and `v` are conjugate semi-diameters; the real axes are where the distance from the center is extreme
+    let t0 = 0.5 * (2.0 * u.dot(&v)).atan2(u.dot(&u) - v.dot(&v));
+    let (sin, cos) = t0.sin_cos();
+    let mut major = Vector::new(
+        u.x * cos + v.x * sin,
+        u.y * cos + v.y * sin,
+        u.z * cos + v.z * sin,
+    );
+    let mut minor = Vector::new(
+        v.x * cos - u.x * sin,
+        v.y * cos - u.y * sin,
+        v.z * cos - u.z * sin,
+    );
+    let mut shift = t0;
+    if minor.length() > major.length() {
+        let previous_major = major;
+        major = minor;
+        minor = previous_major.scaled(-1.0);
+        shift += PI / 2.0;
+    }
+
+    let normal = plane.normal();
+    let is_full = (ellipse.end_parameter - ellipse.start_parameter)
+        .rem_euclid(2.0 * PI)
+        .abs()
+        < 1e-12;
+    let (start, end) = if major.cross(&minor).dot(&normal) < 0.0 {
+        // the curve now runs clockwise about the normal
+        (
+            -(ellipse.end_parameter - shift),
+            -(ellipse.start_parameter - shift),
+        )
+    } else {
+        (
+            ellipse.start_parameter - shift,
+            ellipse.end_parameter - shift,
+        )
+    };
+
+    ellipse.center = matrix.transform_point(&ellipse.center);
+    ellipse.minor_axis_ratio = if major.length() == 0.0 {
+        0.0
+    } else {
+        minor.length() / major.length()
+    };
+    ellipse.major_axis = major;
+    ellipse.normal = normal;
+    if is_full {
+        ellipse.start_parameter = 0.0;
+        ellipse.end_parameter = 2.0 * PI;
+    } else {
+        ellipse.start_parameter = normalize_radians(start);
+        ellipse.end_parameter = normalize_radians(end);
+    }
+}
+
+fn transform_polyline(matrix: &TransformationMatrix, poly: &mut Polyline) {
+    let plane = PlaneTransform::new(matrix, &poly.normal);
+    if poly.is_3d_polyline() || poly.is_3d_polygon_mesh() || poly.is_polyface_mesh() {
+        // vertices are in world coordinates
+        for vertex in poly.vertices_mut() {
+            let is_face_record = vertex.is_polyface_mesh_vertex() && !vertex.is_3d_polygon_mesh();
+            if !is_face_record {
+                vertex.location = matrix.transform_point(&vertex.location);
+            }
+        }
+    } else {
+        let elevation = poly.location.z;
+        let scale = plane.scale();
+        for vertex in poly.vertices_mut() {
+            let p = plane.point(&Point::new(vertex.location.x, vertex.location.y, elevation));
+            vertex.location.x = p.x;
+            vertex.location.y = p.y;
+            vertex.starting_width *= scale;
+            vertex.ending_width *= scale;
+            vertex.bulge = plane.bulge(vertex.bulge);
+            if vertex.curve_fit_tangent_direction != 0.0 {
+                vertex.curve_fit_tangent_direction =
+                    plane.angle_degrees(vertex.curve_fit_tangent_direction);
+            }
+        }
+        poly.location = Point::new(0.0, 0.0, plane.elevation(elevation));
+        poly.default_starting_width *= scale;
+        poly.default_ending_width *= scale;
+        poly.thickness = plane.thickness(poly.thickness);
+    }
+
+    poly.normal = plane.normal();
+}
+
+fn transform_dimension_base<'a>(
+    matrix: &'a TransformationMatrix,
+    dimension_base: &mut DimensionBase,
+) -> PlaneTransform<'a> {
+    let plane = PlaneTransform::new(matrix, &dimension_base.normal);
+    dimension_base.definition_point_1 = matrix.transform_point(&dimension_base.definition_point_1);
+    dimension_base.text_mid_point = plane.point(&dimension_base.text_mid_point);
+    dimension_base.normal = plane.normal();
+    plane
+}
+
+fn transform_insert(matrix: &TransformationMatrix, insert: &mut Insert) {
+    let plane = PlaneTransform::new(matrix, &insert.extrusion_direction);
+    let rotation = insert.rotation.to_radians();
+    let (width_scale, height_scale) = plane.axis_scales(rotation);
+    insert.location = plane.point(&insert.location);
+    insert.rotation = normalize_degrees(plane.angle(rotation).to_degrees());
+    insert.x_scale_factor *= width_scale;
+    insert.y_scale_factor *= if plane.is_mirrored {
+        -height_scale
+    } else {
+        height_scale
+    };
+    insert.z_scale_factor = plane.thickness(insert.z_scale_factor);
+    insert.column_spacing *= width_scale;
+    insert.row_spacing *= height_scale;
+    insert.extrusion_direction = plane.normal();
+    for att in insert.attributes_mut() {
+        transform_attribute(matrix, att);
+    }
+}
+
+/// The values shared by the underlay entities that describe where the underlay is placed.
+struct UnderlayFields<'a> {
+    insertion_point: &'a mut Point,
+    x_scale: &'a mut f64,
+    y_scale: &'a mut f64,
+    z_scale: &'a mut f64,
+    rotation_angle: &'a mut f64,
+    normal: &'a mut Vector,
+}
+
+fn transform_underlay(matrix: &TransformationMatrix, underlay: UnderlayFields) {
+    let plane = PlaneTransform::new(matrix, underlay.normal);
+    let rotation = underlay.rotation_angle.to_radians();
+    let (width_scale, height_scale) = plane.axis_scales(rotation);
+    *underlay.insertion_point = plane.point(underlay.insertion_point);
+    *underlay.rotation_angle = normalize_degrees(plane.angle(rotation).to_degrees());
+    *underlay.x_scale *= width_scale;
+    *underlay.y_scale *= if plane.is_mirrored {
+        -height_scale
+    } else {
+        height_scale
+    };
+    *underlay.z_scale = plane.thickness(*underlay.z_scale);
+    *underlay.normal = plane.normal();
+}
+
+fn transform_hatch(matrix: &TransformationMatrix, hatch: &mut Hatch) {
+    let plane = PlaneTransform::new(matrix, &hatch.extrusion_direction);
+    let elevation = hatch.elevation_point.z;
+    let map_2d = |p: &Point| {
+        let mapped = plane.point(&Point::new(p.x, p.y, elevation));
+        Point::new(mapped.x, mapped.y, p.z)
+    };
+    for path in hatch.boundary_paths.iter_mut() {
+        transform_hatch_path(&plane, elevation, path);
+    }
+    for seed in hatch.seed_points.iter_mut() {
+        *seed = map_2d(seed);
+    }
+    for line in hatch.pattern_definition_lines.iter_mut() {
+        line.angle = plane.angle_degrees(line.angle);
+        line.base_point = map_2d(&line.base_point);
+        line.offset = plane.vector(&line.offset);
+        line.offset.z = 0.0;
+        for dash in line.dash_lengths.iter_mut() {
+            *dash *= plane.scale();
+        }
+    }
+    hatch.pattern_angle = plane.angle_degrees(hatch.pattern_angle);
+    hatch.pattern_scale *= plane.scale();
+    hatch.elevation_point = Point::new(0.0, 0.0, plane.elevation(elevation));
+    hatch.extrusion_direction = plane.normal();
+}
+
+fn transform_hatch_path(plane: &PlaneTransform, elevation: f64, path: &mut HatchBoundaryPath) {
+    let map_xy = |x: f64, y: f64| {
+        let p = plane.point(&Point::new(x, y, elevation));
+        (p.x, p.y)
+    };
+    let map_2d = |p: &Point| {
+        let (x, y) = map_xy(p.x, p.y);
+        Point::new(x, y, p.z)
+    };
+    let map_vector_2d = |v: &Vector| {
+        let mapped = plane.vector(v);
+        Vector::new(mapped.x, mapped.y, v.z)
+    };
+    for vertex in path.vertices.iter_mut() {
+        let (x, y) = map_xy(vertex.x, vertex.y);
+        vertex.x = x;
+        vertex.y = y;
+        vertex.bulge = plane.bulge(vertex.bulge);
+    }
+
+    for edge in path.edges.iter_mut() {
+        match edge {
+            HatchEdge::Line { start, end } => {
+                *start = map_2d(start);
+                *end = map_2d(end);
+            }
+            HatchEdge::CircularArc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                *center = map_2d(center);
+                *radius *= plane.scale();
+                let (start, end, ccw) = map_hatch_arc(
+                    *start_angle,
+                    *end_angle,
+                    *is_counter_clockwise,
+                    plane.is_mirrored,
+                    |angle| plane.angle_degrees(angle),
+                );
+                *start_angle = start;
+                *end_angle = end;
+                *is_counter_clockwise = ccw;
+            }
+            HatchEdge::EllipticArc {
+                center,
+                major_axis,
+                minor_axis_ratio,
+                start_angle,
+                end_angle,
+                is_counter_clockwise,
+            } => {
+                let minor_axis = Vector::new(
+                    -major_axis.y * *minor_axis_ratio,
+                    major_axis.x * *minor_axis_ratio,
+                    0.0,
+                );
+                let new_major = map_vector_2d(major_axis);
+                let new_minor = map_vector_2d(&minor_axis);
+                *center = map_2d(center);
+                *minor_axis_ratio = if new_major.length() == 0.0 {
+                    0.0
+                } else {
+                    new_minor.length() / new_major.length()
+                };
+                *major_axis = new_major;
+
+                // parameters are relative to the major axis, so only a reflection changes them
+                let (start, end, ccw) = map_hatch_arc(
+                    *start_angle,
+                    *end_angle,
+                    *is_counter_clockwise,
+                    plane.is_mirrored,
+                    |angle| if plane.is_mirrored { -angle } else { angle },
+                );
+                *start_angle = start;
+                *end_angle = end;
+                *is_counter_clockwise = ccw;
+            }
+            HatchEdge::Spline {
+                control_points,
+                fit_points,
+                start_tangent,
+                end_tangent,
+                ..
+            } => {
+                for p in control_points.iter_mut().chain(fit_points.iter_mut()) {
+                    *p = map_2d(p);
+                }
+                *start_tangent = map_vector_2d(start_tangent);
+                *end_tangent = map_vector_2d(end_tangent);
+            }
+        }
+    }
+}
+
+/// Maps the angles of a hatch arc edge, in degrees, so that the edge is traversed from the image of its start point to
+/// the image of its end point.  Clockwise edges store their angles mirrored.
+fn map_hatch_arc<F>(
+    start_angle: f64,
+    end_angle: f64,
+    is_counter_clockwise: bool,
+    is_mirrored: bool,
+    map_angle: F,
+) -> (f64, f64, bool)
+where
+    F: Fn(f64) -> f64,
+{
+    let (actual_start, actual_end) = if is_counter_clockwise {
+        (start_angle, end_angle)
+    } else {
+        (-start_angle, -end_angle)
+    };
+    let (new_start, new_end) = (map_angle(actual_start), map_angle(actual_end));
+    let new_is_counter_clockwise = is_counter_clockwise != is_mirrored;
+    if new_is_counter_clockwise {
+        (new_start, new_end, true)
+    } else {
+        (
+            normalize_degrees(-new_start),
+            normalize_degrees(-new_end),
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_point(expected: Point, actual: &Point) {
+        assert_close(expected.x, actual.x);
+        assert_close(expected.y, actual.y);
+        assert_close(expected.z, actual.z);
+    }
+
+    fn transformed(specific: EntityType, matrix: &TransformationMatrix) -> EntityType {
+        let mut entity = Entity::new(specific);
+        entity.transform(matrix);
+        entity.specific
+    }
+
+    fn mirror_across_y_axis() -> TransformationMatrix {
+        TransformationMatrix::mirror(&Point::origin(), &Vector::x_axis())
+    }
+
+    #[test]
+    fn translate_line() {
+        let line = Line::new(Point::new(1.0, 2.0, 3.0), Point::new(4.0, 5.0, 6.0));
+        let matrix = TransformationMatrix::translation(&Vector::new(1.0, 1.0, 1.0));
+        match transformed(EntityType::Line(line), &matrix) {
+            EntityType::Line(line) => {
+                assert_point(Point::new(2.0, 3.0, 4.0), &line.p1);
+                assert_point(Point::new(5.0, 6.0, 7.0), &line.p2);
+            }
+            _ => panic!("expected a line"),
+        }
+    }
+
+    #[test]
+    fn rotate_and_scale_circle() {
+        let circle = Circle::new(Point::new(1.0, 0.0, 0.0), 2.0);
+        let matrix = TransformationMatrix::rotation_about_axis(&Vector::z_axis(), FRAC_PI_2)
+            * TransformationMatrix::scale(3.0, 3.0, 3.0);
+        match transformed(EntityType::Circle(circle), &matrix) {
+            EntityType::Circle(circle) => {
+                assert_point(Point::new(0.0, 3.0, 0.0), &circle.center);
+                assert_close(6.0, circle.radius);
+                assert_eq!(Vector::z_axis(), circle.normal);
+            }
+            _ => panic!("expected a circle"),
+        }
+    }
+
+    #[test]
+    fn mirror_arc() {
+        // the first quadrant arc becomes the second quadrant arc, still counter-clockwise about +Z
+        let arc = Arc::new(Point::new(1.0, 0.0, 0.0), 1.0, 0.0, 90.0);
+        match transformed(EntityType::Arc(arc), &mirror_across_y_axis()) {
+            EntityType::Arc(arc) => {
+                assert_point(Point::new(-1.0, 0.0, 0.0), &arc.center);
+                assert_eq!(Vector::z_axis(), arc.normal);
+                assert_close(90.0, arc.start_angle);
+                assert_close(180.0, arc.end_angle);
+            }
+            _ => panic!("expected an arc"),
+        }
+    }
+
+    #[test]
+    fn arc_with_flipped_normal() {
+        // an arc drawn on the underside of the plane has its OCS x-axis along -X
+        let mut arc = Arc::new(Point::new(2.0, 0.0, 0.0), 1.0, 0.0, 90.0);
+        arc.normal = Vector::new(0.0, 0.0, -1.0);
+        let matrix = TransformationMatrix::translation(&Vector::new(10.0, 0.0, 0.0));
+        match transformed(EntityType::Arc(arc), &matrix) {
+            EntityType::Arc(arc) => {
+                // WCS center moves from (-2, 0) to (8, 0), which is (-8, 0) in this OCS
+                assert_point(Point::new(-8.0, 0.0, 0.0), &arc.center);
+                assert_close(0.0, arc.start_angle);
+                assert_close(90.0, arc.end_angle);
+            }
+            _ => panic!("expected an arc"),
+        }
+    }
+
+    #[test]
+    fn non_uniform_scale_turns_arc_into_ellipse() {
+        let arc = Arc::new(Point::origin(), 1.0, 0.0, 90.0);
+        let matrix = TransformationMatrix::scale(1.0, 2.0, 1.0);
+        match transformed(EntityType::Arc(arc), &matrix) {
+            EntityType::Ellipse(ellipse) => {
+                assert_point(Point::origin(), &ellipse.center);
+                assert_close(2.0, ellipse.major_axis.length());
+                assert_close(0.5, ellipse.minor_axis_ratio);
+
+                // the major axis is along Y, so the arc from +X to +Y now ends where the major axis starts
+                let start = ellipse.start_parameter;
+                let end = ellipse.end_parameter;
+                assert_close(0.0, (end - start).rem_euclid(2.0 * PI) - FRAC_PI_2);
+            }
+            _ => panic!("expected an ellipse"),
+        }
+    }
+
+    #[test]
+    fn mirror_ellipse_arc() {
+        let ellipse = Ellipse {
+            major_axis: Vector::new(2.0, 0.0, 0.0),
+            minor_axis_ratio: 0.5,
+            start_parameter: 0.0,
+            end_parameter: FRAC_PI_2,
+            ..Default::default()
+        };
+        match transformed(EntityType::Ellipse(ellipse), &mirror_across_y_axis()) {
+            EntityType::Ellipse(ellipse) => {
+                assert_eq!(Vector::z_axis(), ellipse.normal);
+
+                // the arc from (2, 0) to (0, 1) becomes the arc from (0, 1) to (-2, 0)
+                let at = |t: f64| {
+                    let minor = ellipse
+                        .normal
+                        .cross(&ellipse.major_axis)
+                        .scaled(ellipse.minor_axis_ratio);
+                    Point::new(
+                        ellipse.major_axis.x * t.cos() + minor.x * t.sin(),
+                        ellipse.major_axis.y * t.cos() + minor.y * t.sin(),
+                        0.0,
+                    )
+                };
+                assert_point(Point::new(0.0, 1.0, 0.0), &at(ellipse.start_parameter));
+                assert_point(Point::new(-2.0, 0.0, 0.0), &at(ellipse.end_parameter));
+            }
+            _ => panic!("expected an ellipse"),
+        }
+    }
+
+    #[test]
+    fn mirror_lw_polyline_negates_bulges() {
+        let mut poly = LwPolyline::default();
+        poly.vertices.push(LwPolylineVertex {
+            x: 1.0,
+            bulge: 0.5,
+            ..Default::default()
+        });
+        match transformed(EntityType::LwPolyline(poly), &mirror_across_y_axis()) {
+            EntityType::LwPolyline(poly) => {
+                assert_close(-1.0, poly.vertices[0].x);
+                assert_close(-0.5, poly.vertices[0].bulge);
+            }
+            _ => panic!("expected an lw polyline"),
+        }
+    }
+
+    #[test]
+    fn rotate_and_mirror_text() {
+        let text = Text {
+            location: Point::new(1.0, 0.0, 0.0),
+            text_height: 1.0,
+            rotation: 10.0,
+            ..Default::default()
+        };
+        let matrix = TransformationMatrix::rotation_about_axis(&Vector::z_axis(), FRAC_PI_2);
+        match transformed(EntityType::Text(text.clone()), &matrix) {
+            EntityType::Text(text) => {
+                assert_point(Point::new(0.0, 1.0, 0.0), &text.location);
+                assert_close(100.0, text.rotation);
+                assert!(!text.is_text_upside_down());
+            }
+            _ => panic!("expected text"),
+        }
+        match transformed(EntityType::Text(text), &mirror_across_y_axis()) {
+            EntityType::Text(text) => {
+                assert_point(Point::new(-1.0, 0.0, 0.0), &text.location);
+                assert_close(170.0, text.rotation);
+                assert!(text.is_text_upside_down());
+            }
+            _ => panic!("expected text"),
+        }
+    }
+
+    #[test]
+    fn scale_and_mirror_insert() {
+        let insert = Insert {
+            location: Point::new(1.0, 1.0, 0.0),
+            ..Default::default()
+        };
+        let matrix = mirror_across_y_axis() * TransformationMatrix::scale(2.0, 2.0, 2.0);
+        match transformed(EntityType::Insert(insert), &matrix) {
+            EntityType::Insert(insert) => {
+                assert_point(Point::new(-2.0, 2.0, 0.0), &insert.location);
+                assert_close(180.0, insert.rotation);
+                assert_close(2.0, insert.x_scale_factor);
+                assert_close(-2.0, insert.y_scale_factor);
+                assert_close(2.0, insert.z_scale_factor);
+            }
+            _ => panic!("expected an insert"),
+        }
+    }
+
+    #[test]
+    fn transform_dimension_points() {
+        let dim = RotatedDimension {
+            dimension_base: DimensionBase {
+                definition_point_1: Point::new(1.0, 0.0, 0.0),
+                text_mid_point: Point::new(0.0, 1.0, 0.0),
+                ..Default::default()
+            },
+            definition_point_2: Point::new(2.0, 0.0, 0.0),
+            definition_point_3: Point::new(3.0, 0.0, 0.0),
+            ..Default::default()
+        };
+        let matrix = TransformationMatrix::translation(&Vector::new(0.0, 5.0, 0.0));
+        match transformed(EntityType::RotatedDimension(dim), &matrix) {
+            EntityType::RotatedDimension(dim) => {
+                assert_point(
+                    Point::new(1.0, 5.0, 0.0),
+                    &dim.dimension_base.definition_point_1,
+                );
+                assert_point(
+                    Point::new(0.0, 6.0, 0.0),
+                    &dim.dimension_base.text_mid_point,
+                );
+                assert_point(Point::new(2.0, 5.0, 0.0), &dim.definition_point_2);
+                assert_point(Point::new(3.0, 5.0, 0.0), &dim.definition_point_3);
+            }
+            _ => panic!("expected a rotated dimension"),
+        }
+    }
+
+    #[test]
+    fn mirror_hatch_arc_edge_keeps_traversal() {
+        let path = HatchBoundaryPath::from_edges(vec![HatchEdge::CircularArc {
+            center: Point::origin(),
+            radius: 1.0,
+            start_angle: 0.0,
+            end_angle: 90.0,
+            is_counter_clockwise: true,
+        }]);
+        let hatch = Hatch {
+            boundary_paths: vec![path],
+            ..Default::default()
+        };
+        match transformed(EntityType::Hatch(hatch), &mirror_across_y_axis()) {
+            EntityType::Hatch(hatch) => match hatch.boundary_paths[0].edges[0] {
+                HatchEdge::CircularArc {
+                    start_angle,
+                    end_angle,
+                    is_counter_clockwise,
+                    ..
+                } => {
+                    // now runs clockwise from 180 to 90 degrees, stored mirrored
+                    assert!(!is_counter_clockwise);
+                    assert_close(180.0, start_angle);
+                    assert_close(270.0, end_angle);
+                }
+                _ => panic!("expected an arc edge"),
+            },
+            _ => panic!("expected a hatch"),
+        }
+    }
+}
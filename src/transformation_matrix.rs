@@ -1,4 +1,11 @@
+use std::ops::Mul;
+
+use crate::{Point, Vector};
+
 /// Applies a transformation to a point.
+///
+/// Points are treated as column vectors, so `m14`, `m24`, and `m34` hold the translation and the product `a * b`
+/// applies `b` first, then `a`.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransformationMatrix {
@@ -31,6 +38,117 @@ impl TransformationMatrix {
             ..Default::default()
         }
     }
+    /// Creates a transformation that moves points by the specified offset.
+    pub fn translation(offset: &Vector) -> Self {
+        TransformationMatrix {
+            m14: offset.x,
+            m24: offset.y,
+            m34: offset.z,
+            ..TransformationMatrix::identity()
+        }
+    }
+    /// Creates a transformation that scales about the origin by the specified factor along each axis.
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        TransformationMatrix {
+            m11: x,
+            m22: y,
+            m33: z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Creates a transformation that rotates counter-clockwise about `axis` (as seen looking down the axis toward the
+    /// origin) by `angle` radians.
+    pub fn rotation_about_axis(axis: &Vector, angle: f64) -> Self {
+        let axis = axis.normalized();
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        TransformationMatrix {
+            m11: t * x * x + cos,
+            m12: t * x * y - sin * z,
+            m13: t * x * z + sin * y,
+            m21: t * x * y + sin * z,
+            m22: t * y * y + cos,
+            m23: t * y * z - sin * x,
+            m31: t * x * z - sin * y,
+            m32: t * y * z + sin * x,
+            m33: t * z * z + cos,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Creates a transformation that reflects points across the plane through `point` with the specified `normal`.
+    pub fn mirror(point: &Point, normal: &Vector) -> Self {
+        let n = normal.normalized();
+        let d = n.x * point.x + n.y * point.y + n.z * point.z;
+        TransformationMatrix {
+            m11: 1.0 - 2.0 * n.x * n.x,
+            m12: -2.0 * n.x * n.y,
+            m13: -2.0 * n.x * n.z,
+            m14: 2.0 * d * n.x,
+            m21: -2.0 * n.y * n.x,
+            m22: 1.0 - 2.0 * n.y * n.y,
+            m23: -2.0 * n.y * n.z,
+            m24: 2.0 * d * n.y,
+            m31: -2.0 * n.z * n.x,
+            m32: -2.0 * n.z * n.y,
+            m33: 1.0 - 2.0 * n.z * n.z,
+            m34: 2.0 * d * n.z,
+            m44: 1.0,
+            ..Default::default()
+        }
+    }
+    /// Returns the determinant of the full 4x4 matrix.  A value of zero means the matrix can't be inverted.
+    pub fn determinant(&self) -> f64 {
+        let m = self.rows();
+        let mut result = 0.0;
+        for (column, value) in m[0].iter().enumerate() {
+            let sign = if column % 2 == 0 { 1.0 } else { -1.0 };
+            result += sign * value * TransformationMatrix::minor(&m, 0, column);
+        }
+
+        result
+    }
+    /// Returns the inverse transformation, or `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant.abs() < f64::EPSILON {
+            return None;
+        }
+
+        let m = self.rows();
+        let mut values = vec![0.0; 16];
+        for row in 0..4 {
+            for column in 0..4 {
+                let sign = if (row + column) % 2 == 0 { 1.0 } else { -1.0 };
+
+                // the inverse is the transposed cofactor matrix divided by the determinant
+                values[column * 4 + row] =
+                    sign * TransformationMatrix::minor(&m, row, column) / determinant;
+            }
+        }
+
+        Some(TransformationMatrix::from_vec(&values))
+    }
+    /// Applies the transformation to a point.
+    pub fn transform_point(&self, p: &Point) -> Point {
+        let w = self.m41 * p.x + self.m42 * p.y + self.m43 * p.z + self.m44;
+        let w = if w == 0.0 { 1.0 } else { w };
+        Point::new(
+            (self.m11 * p.x + self.m12 * p.y + self.m13 * p.z + self.m14) / w,
+            (self.m21 * p.x + self.m22 * p.y + self.m23 * p.z + self.m24) / w,
+            (self.m31 * p.x + self.m32 * p.y + self.m33 * p.z + self.m34) / w,
+        )
+    }
+    /// Applies the transformation to a direction or offset; the translation is ignored.
+    pub fn transform_vector(&self, v: &Vector) -> Vector {
+        Vector::new(
+            self.m11 * v.x + self.m12 * v.y + self.m13 * v.z,
+            self.m21 * v.x + self.m22 * v.y + self.m23 * v.z,
+            self.m31 * v.x + self.m32 * v.y + self.m33 * v.z,
+        )
+    }
 }
 
 // internal visibility only
@@ -55,6 +173,11 @@ impl TransformationMatrix {
             m44: TransformationMatrix::value_or_default(values, 15),
         }
     }
+    /// Returns the determinant of the upper-left 3x3 part of the matrix; a negative value means the transformation
+    /// mirrors.
+    pub(crate) fn linear_determinant(&self) -> f64 {
+        TransformationMatrix::minor(&self.rows(), 3, 3)
+    }
     pub(crate) fn values(&self) -> Vec<f64> {
         vec![
             self.m11, self.m12, self.m13, self.m14, self.m21, self.m22, self.m23, self.m24,
@@ -71,6 +194,27 @@ impl TransformationMatrix {
 
 // private implementation
 impl TransformationMatrix {
+    fn rows(&self) -> [[f64; 4]; 4] {
+        [
+            [self.m11, self.m12, self.m13, self.m14],
+            [self.m21, self.m22, self.m23, self.m24],
+            [self.m31, self.m32, self.m33, self.m34],
+            [self.m41, self.m42, self.m43, self.m44],
+        ]
+    }
+    /// Returns the determinant of the 3x3 matrix left after removing the specified row and column.
+    fn minor(m: &[[f64; 4]; 4], row: usize, column: usize) -> f64 {
+        let mut values = [[0.0; 3]; 3];
+        for (r, source_row) in (0..4).filter(|&r| r != row).enumerate() {
+            for (c, source_column) in (0..4).filter(|&c| c != column).enumerate() {
+                values[r][c] = m[source_row][source_column];
+            }
+        }
+
+        values[0][0] * (values[1][1] * values[2][2] - values[1][2] * values[2][1])
+            - values[0][1] * (values[1][0] * values[2][2] - values[1][2] * values[2][0])
+            + values[0][2] * (values[1][0] * values[2][1] - values[1][1] * values[2][0])
+    }
     fn value_or_default(values: &[f64], index: usize) -> f64 {
         if values.len() > index {
             values[index]
@@ -79,3 +223,84 @@ impl TransformationMatrix {
         }
     }
 }
+
+impl Mul for TransformationMatrix {
+    type Output = TransformationMatrix;
+
+    /// Combines the transformations so that `rhs` is applied first.
+    fn mul(self, rhs: TransformationMatrix) -> TransformationMatrix {
+        let (a, b) = (self.rows(), rhs.rows());
+        let mut values = vec![0.0; 16];
+        for row in 0..4 {
+            for column in 0..4 {
+                values[row * 4 + column] = (0..4).map(|i| a[row][i] * b[i][column]).sum();
+            }
+        }
+
+        TransformationMatrix::from_vec(&values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_close(expected: Point, actual: Point) {
+        assert!(
+            (expected.x - actual.x).abs() < 1e-9
+                && (expected.y - actual.y).abs() < 1e-9
+                && (expected.z - actual.z).abs() < 1e-9,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn rotate_about_z_axis() {
+        let m = TransformationMatrix::rotation_about_axis(&Vector::z_axis(), FRAC_PI_2);
+        assert_close(
+            Point::new(0.0, 1.0, 0.0),
+            m.transform_point(&Point::new(1.0, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn multiplication_applies_right_hand_side_first() {
+        let translate = TransformationMatrix::translation(&Vector::new(1.0, 0.0, 0.0));
+        let scale = TransformationMatrix::scale(2.0, 2.0, 2.0);
+        let m = translate * scale;
+        assert_close(
+            Point::new(3.0, 2.0, 0.0),
+            m.transform_point(&Point::new(1.0, 1.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn mirror_across_offset_plane() {
+        let m = TransformationMatrix::mirror(&Point::new(2.0, 0.0, 0.0), &Vector::x_axis());
+        assert_close(
+            Point::new(3.0, 5.0, 0.0),
+            m.transform_point(&Point::new(1.0, 5.0, 0.0)),
+        );
+        assert!(m.determinant() < 0.0);
+    }
+
+    #[test]
+    fn inverse_undoes_transformation() {
+        let m = TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0))
+            * TransformationMatrix::rotation_about_axis(&Vector::new(1.0, 1.0, 0.0), 0.3)
+            * TransformationMatrix::scale(2.0, 3.0, 4.0);
+        let inverse = m.inverse().unwrap();
+        let p = Point::new(-4.0, 5.0, 6.0);
+        assert_close(p.clone(), inverse.transform_point(&m.transform_point(&p)));
+        assert_eq!(None, TransformationMatrix::scale(1.0, 0.0, 1.0).inverse());
+    }
+
+    #[test]
+    fn vectors_ignore_translation() {
+        let m = TransformationMatrix::translation(&Vector::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector::x_axis(), m.transform_vector(&Vector::x_axis()));
+    }
+}
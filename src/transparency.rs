@@ -0,0 +1,103 @@
+/// The flag set on raw values that defer to the containing block's transparency.
+const BY_BLOCK_FLAG: i32 = 0x0100_0000;
+
+/// The flag set on raw values that specify an alpha value.
+const ALPHA_FLAG: i32 = 0x0200_0000;
+
+/// Represents the transparency of an entity or layer.
+///
+/// The raw value is either `0` for BYLAYER, `0x01000000` for BYBLOCK, or `0x02000000` combined with an alpha value in
+/// the lowest byte, where `0` is fully transparent and `255` is opaque.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transparency {
+    raw_value: i32,
+}
+
+impl Transparency {
+    pub fn from_raw_value(v: i32) -> Transparency {
+        Transparency { raw_value: v }
+    }
+    /// Creates a new `Transparency` that defaults back to the item's layer's transparency.
+    pub fn by_layer() -> Transparency {
+        Transparency::from_raw_value(0)
+    }
+    /// Creates a new `Transparency` that defaults back to the containing block's transparency.
+    pub fn by_block() -> Transparency {
+        Transparency::from_raw_value(BY_BLOCK_FLAG)
+    }
+    /// Creates a new fully opaque `Transparency`.
+    pub fn opaque() -> Transparency {
+        Transparency::from_alpha(255)
+    }
+    /// Creates a new `Transparency` from the specified alpha value, where `0` is fully transparent and `255` is opaque.
+    pub fn from_alpha(alpha: u8) -> Transparency {
+        Transparency::from_raw_value(ALPHA_FLAG | i32::from(alpha))
+    }
+    /// Creates a new `Transparency` from the specified percentage, where `0.0` is opaque and `100.0` is fully
+    /// transparent.  This is how transparency is presented in AutoCAD.
+    pub fn from_percent(percent: f64) -> Transparency {
+        let opacity = 1.0 - percent.clamp(0.0, 100.0) / 100.0;
+        Transparency::from_alpha((opacity * 255.0).round() as u8)
+    }
+    /// Gets the raw value of the `Transparency`.
+    pub fn raw_value(&self) -> i32 {
+        self.raw_value
+    }
+    /// Returns `true` if the `Transparency` is BYLAYER.
+    pub fn is_by_layer(&self) -> bool {
+        self.raw_value & (BY_BLOCK_FLAG | ALPHA_FLAG) == 0
+    }
+    /// Returns `true` if the `Transparency` is BYBLOCK.
+    pub fn is_by_block(&self) -> bool {
+        self.raw_value & BY_BLOCK_FLAG != 0
+    }
+    /// Gets the alpha value, where `0` is fully transparent and `255` is opaque, or `None` if the value is BYLAYER or
+    /// BYBLOCK.
+    pub fn alpha(&self) -> Option<u8> {
+        if self.raw_value & ALPHA_FLAG != 0 && !self.is_by_block() {
+            Some((self.raw_value & 0xFF) as u8)
+        } else {
+            None
+        }
+    }
+    /// Gets the transparency as a percentage, where `0.0` is opaque and `100.0` is fully transparent, or `None` if the
+    /// value is BYLAYER or BYBLOCK.
+    pub fn percent(&self) -> Option<f64> {
+        self.alpha()
+            .map(|alpha| (1.0 - f64::from(alpha) / 255.0) * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Transparency;
+
+    #[test]
+    fn by_layer_and_by_block() {
+        assert!(Transparency::default().is_by_layer());
+        assert_eq!(None, Transparency::by_layer().alpha());
+        assert!(Transparency::by_block().is_by_block());
+        assert!(!Transparency::by_block().is_by_layer());
+        assert_eq!(None, Transparency::by_block().alpha());
+    }
+
+    #[test]
+    fn alpha_raw_value() {
+        let transparency = Transparency::from_alpha(0x7F);
+        assert_eq!(0x0200_007F, transparency.raw_value());
+        assert_eq!(
+            Some(0x7F),
+            Transparency::from_raw_value(0x0200_007F).alpha()
+        );
+        assert!(!transparency.is_by_layer());
+    }
+
+    #[test]
+    fn percent() {
+        assert_eq!(Some(255), Transparency::from_percent(0.0).alpha());
+        assert_eq!(Some(0), Transparency::from_percent(100.0).alpha());
+        assert_eq!(Some(51), Transparency::from_percent(80.0).alpha());
+        assert_eq!(Some(80.0), Transparency::from_alpha(51).percent());
+    }
+}
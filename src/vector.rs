@@ -1,4 +1,6 @@
-use crate::{CodePair, DxfError, DxfResult};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{CodePair, DxfError, DxfResult, Point};
 
 /// Represents a simple vector in Cartesian space.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -33,6 +35,35 @@ impl Vector {
     pub fn z_axis() -> Vector {
         Vector::new(0.0, 0.0, 1.0)
     }
+    /// Returns the dot product of the two vectors.
+    pub fn dot(&self, other: &Vector) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    /// Returns the cross product of the two vectors, which follows the right-hand rule.
+    pub fn cross(&self, other: &Vector) -> Vector {
+        Vector::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+    /// Returns the length of the vector.
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+    /// Returns the vector with each component multiplied by `factor`.
+    pub fn scaled(&self, factor: f64) -> Vector {
+        Vector::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+    /// Returns a unit vector in the same direction, or the vector itself if it has no length.
+    pub fn normalized(&self) -> Vector {
+        let length = self.length();
+        if length == 0.0 {
+            self.clone()
+        } else {
+            self.scaled(1.0 / length)
+        }
+    }
     pub(crate) fn set(&mut self, pair: &CodePair) -> DxfResult<()> {
         match pair.code {
             10 => self.x = pair.assert_f64()?,
@@ -49,3 +80,66 @@ impl Vector {
         Ok(())
     }
 }
+
+impl From<Point> for Vector {
+    fn from(p: Point) -> Self {
+        Vector::new(p.x, p.y, p.z)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: f64) -> Vector {
+        self.scaled(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(4.0, 5.0, 6.0);
+        assert_eq!(Vector::new(5.0, 7.0, 9.0), a.clone() + b.clone());
+        assert_eq!(Vector::new(3.0, 3.0, 3.0), b.clone() - a.clone());
+        assert_eq!(Vector::new(-2.0, -4.0, -6.0), -a.clone() * 2.0);
+        assert_eq!(32.0, a.dot(&b));
+        assert_eq!(Vector::new(-3.0, 6.0, -3.0), a.cross(&b));
+    }
+
+    #[test]
+    fn normalize() {
+        let v = Vector::new(3.0, 0.0, 4.0);
+        assert_eq!(5.0, v.length());
+        let n = v.normalized();
+        assert!((n.x - 0.6).abs() < 1e-12 && n.y == 0.0 && (n.z - 0.8).abs() < 1e-12);
+        assert_eq!(Vector::zero(), Vector::zero().normalized());
+    }
+}